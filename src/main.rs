@@ -1,18 +1,56 @@
-use std::{borrow::Cow, env, fs::{self, OpenOptions}, path::Path, process::Command};
+use std::{fs::{File, OpenOptions}, os::{fd::AsRawFd, unix::process::CommandExt}, path::Path, process::Command, sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering}, time::Instant};
 #[allow(unused_imports)]
 use std::io::{self, Write};
 
-use is_executable::IsExecutable;
-use rustyline::{Editor, EventHandler, KeyCode, KeyEvent, Modifiers, error::ReadlineError};
-
-use crate::rustyline_editor::tab_handler::MyTabHandler;
+use rustyline::{CompletionType, Config, Editor, error::ReadlineError};
+
+use crate::rustyline_editor::key_binding::{apply_key_bindings, load_inputrc};
+use crate::rustyline_editor::prompt::get_prompt;
+use crate::rustyline_editor::shell_helper::ShellHelper;
+use crate::shell_builtin::bind_command::command_bind;
+use crate::shell_builtin::cd_command::{command_cd, command_pwd};
+use crate::shell_builtin::complete_command::{command_compgen, command_complete};
+use crate::shell_builtin::dirs_command::{command_dirs, command_popd, command_pushd};
+use crate::shell_builtin::echo_command::command_echo;
+use crate::shell_builtin::hash_command::command_hash;
+use crate::shell_builtin::history_command::command_history;
+use crate::shell_builtin::printf_command::command_printf;
+use crate::shell_builtin::read_command::{command_read, read_input_line};
+use crate::shell_builtin::set_command::command_set;
+use crate::shell_builtin::test_command::{command_bracket, command_test, execute_conditional_expression};
+use crate::shell_builtin::z_command::command_z;
+use crate::shell_parser::command_list::{CommandList, CommandNode, GroupRedirection, ListOperator, parse_command_list, split_redirections};
+use crate::shell_parser::expansion::{evaluate_arithmetic_expression, expand_command_line};
+use crate::shell_parser::history_expansion::expand_history;
+use crate::shell_parser::input_completeness::{InputCompleteness, check_input_completeness, remove_line_continuations};
+use crate::shell_parser::pattern::glob_match;
+use crate::shell_state::command_hash::{find_command_path, get_executable_command_names, get_executable_path, get_hashed_command};
+use crate::shell_state::history::{SharedHistory, finish_history_entry, load_history, new_history_metadata, save_history};
+use crate::shell_state::history_database::append_history_record;
+use crate::shell_state::options::{find_shell_option, is_shell_option_enabled, set_shell_option};
+use crate::shell_state::functions::{get_function, set_function};
+use crate::shell_state::variables::{get_positional_parameters, get_temporary_assignments, get_variable, is_valid_variable_name, pop_temporary_assignments, push_temporary_assignments, replace_positional_parameters, set_array_variable, set_variable};
 
 mod rustyline_editor;
+mod shell_builtin;
+mod shell_parser;
+mod shell_state;
 
 
-const COMMAND: [&str; 5]= ["exit", "echo", "type", "pwd", "cd"];
+const COMMAND: [&str; 21]= ["exit", "echo", "printf", "read", "type", "pwd", "cd", "test", "[", "history", "set", "return", "complete", "compgen", "hash", "bind", "pushd", "popd", "dirs", "z", "j"];
 const COMMAND_PATH: [&str; 4] = ["cat", "ls", "cat.exe", "ls.exe"];
 
+// 마지막으로 실행된 command 의 exit status
+static LAST_EXIT_STATUS: AtomicI32 = AtomicI32::new(0);
+// exit 가 호출되면 남은 command list 실행을 멈추고 shell(혹은 subshell) 을 종료
+static IS_EXIT_REQUESTED: AtomicBool = AtomicBool::new(false);
+// return 이 호출되면 남은 command list 실행을 멈추고 함수에서 빠져나감
+static IS_RETURN_REQUESTED: AtomicBool = AtomicBool::new(false);
+// 현재 실행중인 함수 호출 깊이
+static FUNCTION_DEPTH: AtomicUsize = AtomicUsize::new(0);
+// here string, here document 임시 파일 이름이 겹치지 않게 붙이는 번호
+static HERE_DOCUMENT_COUNT: AtomicUsize = AtomicUsize::new(0);
+
 #[derive(PartialEq, Default)]
 enum CommandResult {
     Success,
@@ -20,43 +58,49 @@ enum CommandResult {
     #[default] CommandError
 }
 
-#[derive(PartialEq, Clone, Copy)]
-enum CommandOutput {
-    StdOut,
-    StdOutNewLine,
-    File,
-    FileAppend,
-}
-
 #[derive(Default)]
 struct CommandExecutableResult {
     pub command: String,
     pub full_path: String,
     pub result: CommandResult,
-}
-
-#[derive(Default)]
-struct RedirectionArgsBuilderResult {
-    pub command_args: String,
-    pub output: String,
-    pub redirect: String,
-    pub result: CommandResult
+    pub is_builtin: bool,
 }
 
 fn main() {
-    let mut readline_editor: Editor<(), _> = Editor::new().expect("rustyline editor fail");
-
-    {
-        let my_tab_handler = MyTabHandler::new(get_all_executable_command());
-        readline_editor.bind_sequence(KeyEvent(KeyCode::Tab, Modifiers::NONE), EventHandler::Conditional(Box::new(my_tab_handler)));
+    // menu completion 에서 단어를 교체할 때 한번에 교체되도록 List
+    let config = Config::builder().completion_type(CompletionType::List).build();
+    let mut readline_editor: Editor<ShellHelper, SharedHistory> = Editor::with_history(config, SharedHistory).expect("rustyline editor fail");
+    readline_editor.set_helper(Some(ShellHelper::default()));
+    load_history();
+    load_inputrc();
+
+    // bash 처럼 history expansion 은 interactive shell 에서만, stdin 으로 받은 script 의 ! 는 그대로
+    if unsafe { libc::isatty(libc::STDIN_FILENO) } == 0
+        && let Some(histexpand) = find_shell_option("histexpand") {
+        set_shell_option(histexpand, false);
     }
 
     loop {
-        let readline = readline_editor.readline("$ ");
+        run_prompt_command();
+
+        // 터미널 제목 같은 sequence 는 prompt 너비에 포함되지 않게 먼저 출력
+        // set -o vi/emacs 나 bind 로 바뀐 설정 반영
+        apply_key_bindings(&mut readline_editor);
+
+        let prompt = get_prompt("PS1", "$ ");
+        if false == prompt.invisible.is_empty() {
+            print!("{}", prompt.invisible);
+            io::stdout().flush().ok();
+        }
+        let readline = read_input(&mut readline_editor, &prompt.text);
         
         let input_command: String = match readline {
             Ok(line) => {
-                line
+                // 쿼터나 if 등이 닫히지 않았으면 이어서 읽음
+                match read_continuation_lines(line) {
+                    Some(input_command) => input_command,
+                    None => continue,
+                }
             }
             Err(ReadlineError::Interrupted) => {
                 println!("Ctrl-C");
@@ -72,181 +116,849 @@ fn main() {
             }
         };
 
-        // command 는 command_args_builder 함수를 태워야 되기 때문에 다 owned 로 한다.
-        let mut command = String::new();
-        // command_args 는 단순 slice 로 가능해서 borrowed 로 한다.
-        let command_args;
-
-        // command 가 쿼터로 묶여 있으면
-        if input_command.starts_with('\'') || input_command.starts_with('\"') {
-            let split_pattern;
-            if input_command.starts_with('\'') {
-                split_pattern = '\'';
-            } else {
-                split_pattern = '\"';
+        // !!, !$ 같은 history expansion 은 tokenize 전에 원본 line 에 적용
+        let input_command = if is_shell_option_enabled("histexpand") {
+            match expand_history(&input_command) {
+                Ok(history_expansion_result) => {
+                    if history_expansion_result.is_expanded {
+                        println!("{}", history_expansion_result.line);
+                    }
+                    // :p 는 출력하고 history 에만 추가
+                    if history_expansion_result.is_print_only {
+                        readline_editor.add_history_entry(history_expansion_result.line.as_str()).ok();
+                        continue;
+                    }
+                    history_expansion_result.line
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    LAST_EXIT_STATUS.store(1, Ordering::Relaxed);
+                    continue;
+                }
             }
+        } else {
+            input_command
+        };
 
-            // 마지막 쿼터까지 한번 파싱
-            let Some(command_filter) = input_command.strip_prefix(split_pattern) else {
-                println!("command quotes invalid");
-                continue;
-            };
-            let Some(command_quote_end_idx) = command_filter.find(split_pattern) else {
-                println!("command single quotes invalid");
-                continue;
-            };
+        // 무시 규칙(HISTCONTROL) 은 history 저장소에서 처리
+        let is_history_added = readline_editor.add_history_entry(input_command.as_str()).unwrap_or(false);
 
-            // command_filter 섀도잉
-            let command_filter = &command_filter[..command_quote_end_idx];
+        // 디렉토리는 command 가 cd 하기 전 위치로 기록
+        let mut history_metadata = new_history_metadata();
+        let start_time = Instant::now();
+        let exit_status = run_command_line(&input_command);
+        let duration = start_time.elapsed();
+        if is_history_added {
+            finish_history_entry(&input_command, exit_status, duration);
+        }
+        history_metadata.exit_status = Some(exit_status);
+        history_metadata.duration_ms = Some(duration.as_millis() as u64);
+        append_history_record(&input_command, &history_metadata);
 
-            // 기존에 묶여있던 쿼터로 다시 한번 묶어줌
-            let mut command_args_builder_param = String::new();
-            if split_pattern == '\'' {
-                command_args_builder_param.push('\'');
-            } else {
-                command_args_builder_param.push('"');
+        if IS_EXIT_REQUESTED.load(Ordering::Relaxed) {
+            break;
+        }
+    }
+
+    save_history();
+}
+
+// 터미널이면 ShellHelper 의 Validator 가 입력이 끝날 때까지 여러 줄을 받고,
+// 아니면 입력이 끝날 때까지 다음 줄을 읽어서 붙임 (입력이 먼저 끝나면 입력 전체를 버림)
+// 줄 끝의 \ 는 개행과 함께 지움
+fn read_continuation_lines(line: String) -> Option<String> {
+    let mut input_command = line;
+
+    while check_input_completeness(&input_command) == InputCompleteness::Incomplete {
+        let Some(next_line) = read_input_line() else {
+            eprintln!("syntax error: unexpected end of file");
+            LAST_EXIT_STATUS.store(2, Ordering::Relaxed);
+            return None;
+        };
+        input_command.push('\n');
+        input_command.push_str(&next_line);
+    }
+
+    Some(remove_line_continuations(&input_command))
+}
+
+// 입력이 터미널이 아니면 rustyline 은 stdin 을 버퍼에 미리 읽어서 read builtin 이 다음 줄을 읽지 못하므로
+// read 와 같이 버퍼 없이 한 줄씩 읽음 (rustyline 도 이때는 prompt 를 출력하지 않음)
+fn read_input(readline_editor: &mut Editor<ShellHelper, SharedHistory>, prompt: &str) -> Result<String, ReadlineError> {
+    if unsafe { libc::isatty(libc::STDIN_FILENO) } == 0 {
+        return read_input_line().ok_or(ReadlineError::Eof);
+    }
+    readline_editor.readline(prompt)
+}
+
+fn run_command_line(input_command: &str) -> i32 {
+    let command_list = match parse_command_list(input_command) {
+        Ok(command_list) => command_list,
+        Err(e) => {
+            println!("{}", e);
+            LAST_EXIT_STATUS.store(2, Ordering::Relaxed);
+            return 2;
+        }
+    };
+
+    execute_command_list(&command_list)
+}
+
+// 매 prompt 출력 전에 PROMPT_COMMAND 실행 (ex. PROMPT_COMMAND='history -a')
+fn run_prompt_command() {
+    let Some(prompt_command) = get_variable("PROMPT_COMMAND") else {
+        return;
+    };
+    if prompt_command.trim().is_empty() {
+        return;
+    }
+
+    // PROMPT_COMMAND 의 결과가 $? 를 덮어쓰지 않게 복구
+    let last_exit_status = LAST_EXIT_STATUS.load(Ordering::Relaxed);
+    run_command_line(&prompt_command);
+    LAST_EXIT_STATUS.store(last_exit_status, Ordering::Relaxed);
+}
+
+fn execute_command_list(command_list: &CommandList) -> i32 {
+    let mut status = LAST_EXIT_STATUS.load(Ordering::Relaxed);
+
+    for (operator, command_node) in &command_list.commands {
+        // && 는 이전 command 가 성공, || 는 이전 command 가 실패 했을 때만 실행
+        match operator {
+            ListOperator::And if status != 0 => continue,
+            ListOperator::Or if status == 0 => continue,
+            _ => {}
+        }
+
+        status = execute_command_node(command_node);
+        LAST_EXIT_STATUS.store(status, Ordering::Relaxed);
+
+        if IS_EXIT_REQUESTED.load(Ordering::Relaxed) || IS_RETURN_REQUESTED.load(Ordering::Relaxed) {
+            break;
+        }
+    }
+
+    status
+}
+
+fn execute_command_node(command_node: &CommandNode) -> i32 {
+    match command_node {
+        CommandNode::Simple(input_command) => execute_simple_command(input_command),
+        // (( expr )) 는 결과가 0 이 아니면 성공
+        CommandNode::Arithmetic(expression) => match evaluate_arithmetic_expression(expression) {
+            Ok(0) => 1,
+            Ok(_) => 0,
+            Err(e) => {
+                println!("{}", e);
+                1
             }
-            command_args_builder_param.push_str(command_filter);
-            if split_pattern == '\'' {
-                command_args_builder_param.push('\'');
-            } else {
-                command_args_builder_param.push('"');
+        },
+        CommandNode::Conditional(expression) => execute_conditional_expression(expression),
+        CommandNode::FunctionDefinition(name, body) => {
+            set_function(name, body.as_ref().clone());
+            0
+        }
+        CommandNode::Subshell(command_list, redirections) => execute_subshell(command_list, redirections),
+        CommandNode::BraceGroup(command_list, redirections) => {
+            // group 은 현재 shell 에서 실행하되 redirection 은 group 전체에 공유
+            let Some(saved_fds) = apply_group_redirections(redirections) else {
+                return 1;
+            };
+            let status = execute_command_list(command_list);
+            restore_group_redirections(saved_fds);
+            status
+        }
+        CommandNode::While(condition, body, redirections) => execute_loop(condition, body, redirections, false),
+        CommandNode::Until(condition, body, redirections) => execute_loop(condition, body, redirections, true),
+        CommandNode::If(branches, else_body, redirections) => execute_if(branches, else_body.as_ref(), redirections),
+        CommandNode::For(name, words, body, redirections) => execute_for(name, words.as_deref(), body, redirections),
+        CommandNode::Case(word, items, redirections) => execute_case(word, items, redirections),
+        CommandNode::Pipeline(command_nodes) => execute_pipeline(command_nodes),
+    }
+}
+
+// exit 나 return 이 호출되어서 남은 command 를 실행하지 않아야 하는지
+fn is_stop_requested() -> bool {
+    IS_EXIT_REQUESTED.load(Ordering::Relaxed) || IS_RETURN_REQUESTED.load(Ordering::Relaxed)
+}
+
+// while 은 조건이 성공하는 동안, until 은 실패하는 동안 본문을 실행
+// `done < file` 같은 redirection 은 반복 전체에 공유되어 조건의 read 가 한 줄씩 이어서 읽음
+// 종료 상태는 마지막으로 실행한 본문의 상태, 본문을 한번도 실행하지 않았으면 0
+fn execute_loop(condition: &CommandList, body: &CommandList, redirections: &[GroupRedirection], is_until: bool) -> i32 {
+    let Some(saved_fds) = apply_group_redirections(redirections) else {
+        return 1;
+    };
+
+    let mut status = 0;
+    loop {
+        let condition_status = execute_command_list(condition);
+        if is_stop_requested() {
+            status = condition_status;
+            break;
+        }
+        if (condition_status == 0) == is_until {
+            break;
+        }
+
+        status = execute_command_list(body);
+        if is_stop_requested() {
+            break;
+        }
+    }
+
+    restore_group_redirections(saved_fds);
+    status
+}
+
+// 처음으로 성공한 조건의 본문을 실행하고, 모두 실패하면 else 본문을 실행
+// 종료 상태는 실행한 본문의 상태, 실행한 본문이 없으면 0
+fn execute_if(branches: &[(CommandList, CommandList)], else_body: Option<&CommandList>, redirections: &[GroupRedirection]) -> i32 {
+    let Some(saved_fds) = apply_group_redirections(redirections) else {
+        return 1;
+    };
+
+    let mut status = 0;
+    let mut is_branch_executed = false;
+    for (condition, body) in branches {
+        let condition_status = execute_command_list(condition);
+        if is_stop_requested() {
+            status = condition_status;
+            is_branch_executed = true;
+            break;
+        }
+        if condition_status == 0 {
+            status = execute_command_list(body);
+            is_branch_executed = true;
+            break;
+        }
+    }
+    if false == is_branch_executed && let Some(else_body) = else_body {
+        status = execute_command_list(else_body);
+    }
+
+    restore_group_redirections(saved_fds);
+    status
+}
+
+// in 뒤의 단어들을 확장해서 (in 이 없으면 positional parameter) 하나씩 변수에 대입하고 본문을 실행
+// 종료 상태는 마지막으로 실행한 본문의 상태, 단어가 없으면 0
+fn execute_for(name: &str, words: Option<&str>, body: &CommandList, redirections: &[GroupRedirection]) -> i32 {
+    let words = match words {
+        Some(words) => match expand_command_line(words) {
+            Ok(expanded) => special_char_args_builder(&expanded),
+            Err(e) => {
+                eprintln!("{}", e);
+                return 1;
             }
+        },
+        None => get_positional_parameters(),
+    };
 
-            let command_args_builder_result = special_char_args_builder(&command_args_builder_param);
-            command = command_args_builder_result.join(" ");
+    let Some(saved_fds) = apply_group_redirections(redirections) else {
+        return 1;
+    };
 
-            let Some(command_args_filter) = input_command.strip_prefix(&command_args_builder_param) else {
-                println!("command single quotes invalid");
-                continue;
-            };
-            command_args = command_args_filter.trim();
+    let mut status = 0;
+    for word in words {
+        set_variable(name, &word);
+        status = execute_command_list(body);
+        if is_stop_requested() {
+            break;
+        }
+    }
 
-        // 아무것도 묶여있지 않으면 공백으로 커맨드, 커맨드 파라미터 분리
-        } else if input_command.contains(" ") {
-            if let Some((cmd, rest)) = input_command.split_once(' ') {
-                command = cmd.to_string();
-                command_args = rest.trim();
-            } else {
-                command_args = "";
+    restore_group_redirections(saved_fds);
+    status
+}
+
+// 확장한 단어와 처음으로 일치하는 pattern 의 본문을 실행
+// 종료 상태는 실행한 본문의 상태, 일치하는 pattern 이 없으면 0
+fn execute_case(word: &str, items: &[(Vec<String>, CommandList)], redirections: &[GroupRedirection]) -> i32 {
+    let word = match expand_command_line(word) {
+        Ok(expanded) => special_char_args_builder(&expanded).join(" "),
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    let Some(saved_fds) = apply_group_redirections(redirections) else {
+        return 1;
+    };
+
+    let mut status = 0;
+    for (patterns, body) in items {
+        let is_matched = patterns.iter().any(|pattern| match expand_command_line(pattern) {
+            Ok(expanded) => glob_match(&case_pattern_to_glob(&expanded), &word),
+            Err(e) => {
+                eprintln!("{}", e);
+                false
             }
-        // 공백 조차 없으면 파라미터가 없는거
-        } else {
-            command = input_command.to_string();
-            command_args = "";
+        });
+        if is_matched {
+            status = execute_command_list(body);
+            break;
         }
+    }
+
+    restore_group_redirections(saved_fds);
+    status
+}
 
-        // command 섀도잉
-        let command = &command[..];
-
-        match command {
-            // 파라미터가 불필요한 명령어
-            "exit" => break,
-            "pwd" => command_pwd(),
-            // 파라미터가 필요한 명령어
-            _ => {
-                match command {
-                    "echo" => command_echo(&command_args),
-                    "type" => command_type(&command_args),
-                    "cd" => command_cd(&command_args),
-                    // cat 과 ls 는 구현이 아닌 외부에 이미 있는 command 를 사용 하게끔 한다
-                    "cat" => command_cat(&command_args),
-                    "ls" => command_ls(&command_args),
-                    _ => command_execute(command, &command_args)
-                };
+// case pattern 의 쿼터를 지우고, 쿼터 안의 * ? [ ] 는 글자 그대로 매칭되도록 \ 를 붙임
+fn case_pattern_to_glob(pattern: &str) -> String {
+    let mut glob = String::with_capacity(pattern.len());
+    let mut quote = None;
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match (quote, char) {
+            (Some(quote_char), _) if char == quote_char => quote = None,
+            (None, '\'' | '"') => quote = Some(char),
+            // "" 안의 \ 는 \ " $ ` 앞에서만 escape
+            (Some('"'), '\\') if matches!(chars.peek(), Some('\\' | '"' | '$' | '`')) => {
+                glob.push('\\');
+                glob.extend(chars.next());
             }
-        };
+            (None, '\\') => {
+                glob.push('\\');
+                glob.extend(chars.next());
+            }
+            (Some(_), '*' | '?' | '[' | ']' | '\\') => {
+                glob.push('\\');
+                glob.push(char);
+            }
+            _ => glob.push(char),
+        }
     }
+
+    glob
 }
 
-fn get_all_executable_command() -> Vec<String> {
-    let mut result: Vec<String> = COMMAND.into_iter().map(String::from).collect();
+// ( ... ) 는 fork 된 자식 프로세스에서 실행해서 cd 나 변수 변경이 부모 shell 로 새어나가지 않게 한다
+fn execute_subshell(command_list: &CommandList, redirections: &[GroupRedirection]) -> i32 {
+    // fork 전에 버퍼를 비워야 자식과 부모에서 같은 내용이 두번 출력되지 않음
+    io::stdout().flush().ok();
 
-    // get PATH
-    let Some(paths) = env::var_os("PATH") else {
-        println!("var os PATH not found");
-        return result;
-    };
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        println!("subshell fork error: {}", io::Error::last_os_error());
+        return 1;
+    }
 
-    for path in env::split_paths(&paths) {
-        // 환경변수 하위의 전체 dir
-        let Ok(read_dir) = fs::read_dir(&path) else {
-            continue;
+    // 자식 프로세스
+    if pid == 0 {
+        let status = match apply_group_redirections(redirections) {
+            Some(_) => execute_command_list(command_list),
+            None => 1,
         };
+        io::stdout().flush().ok();
+        std::process::exit(status);
+    }
 
-        for dir in read_dir {
-            let sub_path = match dir {
-                Ok(v) => v.path(),
-                Err(_) => continue
-            };
-
-            {
-                let full = path.join(&sub_path);
+    // 부모 프로세스는 자식이 끝날때까지 대기
+    wait_for_child(pid)
+}
 
-                // Check if a file with the command name exists.
-                if fs::metadata(&full).is_err() {
-                    continue;
+// 각 command 를 fork 된 자식에서 실행하고 앞 command 의 stdout 을 다음 command 의 stdin 으로 연결
+// 종료 상태는 마지막 command 의 상태
+fn execute_pipeline(command_nodes: &[CommandNode]) -> i32 {
+    io::stdout().flush().ok();
+
+    let mut pids = vec![];
+    // 이전 command 의 출력을 읽을 pipe
+    let mut previous_read_fd: Option<i32> = None;
+    for (idx, command_node) in command_nodes.iter().enumerate() {
+        let is_last = idx + 1 == command_nodes.len();
+        let mut pipe_fds = [-1; 2];
+        if false == is_last && unsafe { libc::pipe(pipe_fds.as_mut_ptr()) } < 0 {
+            eprintln!("pipe error: {}", io::Error::last_os_error());
+            break;
+        }
+        let [read_fd, write_fd] = pipe_fds;
+
+        let pid = unsafe { libc::fork() };
+        if pid < 0 {
+            eprintln!("pipeline fork error: {}", io::Error::last_os_error());
+            if false == is_last {
+                unsafe {
+                    libc::close(read_fd);
+                    libc::close(write_fd);
                 }
+            }
+            break;
+        }
 
-                // 실행 가능한 경로인지 체크
-                if false == full.is_executable() {
-                    continue;
+        // 자식 프로세스
+        if pid == 0 {
+            unsafe {
+                // 읽는 쪽이 먼저 끝나면 쓰는 쪽은 에러 대신 SIGPIPE 로 조용히 종료
+                libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+                if let Some(previous_read_fd) = previous_read_fd {
+                    libc::dup2(previous_read_fd, libc::STDIN_FILENO);
+                    libc::close(previous_read_fd);
+                }
+                if false == is_last {
+                    libc::close(read_fd);
+                    libc::dup2(write_fd, libc::STDOUT_FILENO);
+                    libc::close(write_fd);
                 }
             }
+            let status = execute_command_node(command_node);
+            io::stdout().flush().ok();
+            std::process::exit(status);
+        }
 
-            // 환경변수 full path 제거된 맨 마지막 실행파일 이름만 따로 추출
-            let Some(execute_file_name) = sub_path.file_name() else {
-                continue;
-            };
-            let Some(execute_file_name) = execute_file_name.to_str() else {
-                continue;
-            };
+        // 부모는 자식에게 넘긴 fd 를 닫음 (열어두면 읽는 쪽이 EOF 를 받지 못함)
+        if let Some(previous_read_fd) = previous_read_fd.take() {
+            unsafe { libc::close(previous_read_fd) };
+        }
+        if false == is_last {
+            unsafe { libc::close(write_fd) };
+            previous_read_fd = Some(read_fd);
+        }
+        pids.push(pid);
+    }
 
-            result.push(execute_file_name.to_string());
+    if let Some(previous_read_fd) = previous_read_fd {
+        unsafe { libc::close(previous_read_fd) };
+    }
+
+    // 모든 자식을 기다리고 마지막 command 의 상태를 반환
+    let mut status = 1;
+    for pid in pids {
+        status = wait_for_child(pid);
+    }
+    status
+}
+
+// 자식 프로세스가 끝날때까지 대기하고 exit status 로 변환 (signal 로 종료되면 128 + signal)
+fn wait_for_child(pid: libc::pid_t) -> i32 {
+    let mut wait_status = 0;
+    if unsafe { libc::waitpid(pid, &mut wait_status, 0) } < 0 {
+        eprintln!("wait error: {}", io::Error::last_os_error());
+        return 1;
+    }
+
+    if libc::WIFEXITED(wait_status) {
+        libc::WEXITSTATUS(wait_status)
+    } else if libc::WIFSIGNALED(wait_status) {
+        128 + libc::WTERMSIG(wait_status)
+    } else {
+        1
+    }
+}
+
+// $(command) 처럼 fork 된 자식 프로세스에서 실행하고 stdout 을 문자열로 (마지막 줄바꿈은 제거)
+fn capture_command_output(input_command: &str) -> String {
+    let mut pipe_fds = [0; 2];
+    if unsafe { libc::pipe(pipe_fds.as_mut_ptr()) } < 0 {
+        return String::new();
+    }
+    let [read_fd, write_fd] = pipe_fds;
+
+    io::stdout().flush().ok();
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        unsafe {
+            libc::close(read_fd);
+            libc::close(write_fd);
         }
+        return String::new();
+    }
 
+    // 자식 프로세스
+    if pid == 0 {
+        unsafe {
+            libc::close(read_fd);
+            libc::dup2(write_fd, libc::STDOUT_FILENO);
+            libc::close(write_fd);
+        }
+        // PS4 안의 명령어 치환이 다시 trace 를 출력하면서 반복되지 않게
+        if let Some(xtrace) = find_shell_option("xtrace") {
+            set_shell_option(xtrace, false);
+        }
+        let status = run_command_line(input_command);
+        io::stdout().flush().ok();
+        std::process::exit(status);
     }
 
-    result.sort();
-    result.dedup();
+    // pipe 가 가득 차면 자식이 멈추기 때문에 끝까지 읽은 뒤 대기
+    unsafe { libc::close(write_fd) };
+    let mut output = vec![];
+    let mut buffer = [0u8; 4096];
+    loop {
+        let read_len = unsafe { libc::read(read_fd, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len()) };
+        if read_len <= 0 {
+            break;
+        }
+        output.extend_from_slice(&buffer[..read_len as usize]);
+    }
+    unsafe {
+        libc::close(read_fd);
+        libc::waitpid(pid, std::ptr::null_mut(), 0);
+    }
 
-    result
+    String::from_utf8_lossy(&output).trim_end_matches('\n').to_string()
 }
 
-fn command_output(enum_output: CommandOutput, args: &str, writer_output: &str) {
-    if enum_output == CommandOutput::StdOut && false == args.is_empty() {
-        print!("{}", args);
-        return;
+// group redirection 대상 fd 를 파일로 바꾸고 (< 는 stdin, >& 는 다른 fd 의 복제), 복구를 위해 원래 fd 를 복제해서 반환
+fn apply_group_redirections(redirections: &[GroupRedirection]) -> Option<Vec<(i32, i32)>> {
+    let mut saved_fds = vec![];
+
+    for redirection in redirections {
+        let is_input = redirection.redirect.ends_with('<');
+        let target_fd = if is_input { 0 } else if redirection.redirect.starts_with('2') { 2 } else { 1 };
+        let is_append = redirection.redirect.ends_with(">>");
+
+        // 2>&1 처럼 앞서 적용한 redirection 까지 반영된 fd 를 복제
+        if redirection.redirect.ends_with('&') {
+            let source_fd = redirection.output.parse::<i32>().unwrap_or(-1);
+            if source_fd < 0 || unsafe { libc::fcntl(source_fd, libc::F_GETFD) } < 0 {
+                eprintln!("{}: Bad file descriptor", redirection.output);
+                restore_group_redirections(saved_fds);
+                return None;
+            }
+            io::stdout().flush().ok();
+            let saved_fd = unsafe { libc::dup(target_fd) };
+            unsafe { libc::dup2(source_fd, target_fd) };
+            saved_fds.push((target_fd, saved_fd));
+            continue;
+        }
+
+        let open_result = if redirection.redirect.ends_with("<<<") {
+            open_here_document(&format!("{}\n", redirection.output))
+        } else if redirection.redirect.ends_with("<<") {
+            open_here_document(&redirection.output)
+        } else if is_input {
+            File::open(&redirection.output)
+        } else {
+            OpenOptions::new()
+                .write(true)
+                .create(true)
+                .append(is_append)
+                .truncate(false == is_append)
+                .open(&redirection.output)
+        };
+        let file = match open_result {
+            Ok(f) => f,
+            Err(e) => {
+                println!("{}: {}", redirection.output, e);
+                restore_group_redirections(saved_fds);
+                return None;
+            }
+        };
+
+        io::stdout().flush().ok();
+        let saved_fd = unsafe { libc::dup(target_fd) };
+        unsafe { libc::dup2(file.as_raw_fd(), target_fd) };
+        saved_fds.push((target_fd, saved_fd));
     }
 
-    if enum_output == CommandOutput::StdOutNewLine && false == args.is_empty() {
-        println!("{}", args);
-        return;
+    Some(saved_fds)
+}
+
+// here string, here document 의 내용을 임시 파일에 쓰고 읽기용으로 다시 열어서 반환
+// 연 뒤에 바로 지우기 때문에 fd 가 닫히면 파일도 사라짐
+fn open_here_document(contents: &str) -> io::Result<File> {
+    let count = HERE_DOCUMENT_COUNT.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!("shell-here-{}-{}", std::process::id(), count));
+
+    OpenOptions::new().write(true).create_new(true).open(&path)?.write_all(contents.as_bytes())?;
+    let file = File::open(&path);
+    std::fs::remove_file(&path).ok();
+
+    file
+}
+
+fn restore_group_redirections(saved_fds: Vec<(i32, i32)>) {
+    io::stdout().flush().ok();
+
+    // 나중에 바꾼 fd 부터 역순으로 복구
+    for (target_fd, saved_fd) in saved_fds.into_iter().rev() {
+        unsafe {
+            libc::dup2(saved_fd, target_fd);
+            libc::close(saved_fd);
+        }
+    }
+}
+
+// builtin 인자의 redirection 을 fd 에 적용하고 실행한 후 복구
+fn run_builtin_with_redirections(command_args: &str, builtin: impl FnOnce(&[String]) -> i32) -> i32 {
+    let (command_args, redirections) = match split_redirections(command_args) {
+        Ok(splited) => splited,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+
+    let Some(saved_fds) = apply_group_redirections(&redirections) else {
+        return 1;
+    };
+    let status = builtin(&special_char_args_builder(&command_args));
+    restore_group_redirections(saved_fds);
+
+    status
+}
+
+fn execute_simple_command(input_command: &str) -> i32 {
+    let input_command = match expand_command_line(input_command) {
+        Ok(expanded) => expanded,
+        Err(e) => {
+            println!("{}", e);
+            return 1;
+        }
+    };
+
+    // set -x
+    if is_shell_option_enabled("xtrace") {
+        eprintln!("{}{}", get_prompt("PS4", "+ ").text, input_command);
     }
 
-    if enum_output == CommandOutput::File {
-        match fs::write(writer_output, args) {
-            Ok(_) => {},
-            Err(e) => println!("file write error. path: {}, e: {}", writer_output, e)
+    // NAME=value 만 있는 경우 변수 대입
+    if let Some(status) = try_variable_assignment(&input_command) {
+        return status;
+    }
+
+    // NAME=value command 는 command 실행 동안만 변수 대입
+    let (assignments, input_command) = split_assignment_prefix(&input_command);
+    if assignments.is_empty() {
+        return run_expanded_command(input_command);
+    }
+
+    let previous_variables = push_temporary_assignments(assignments);
+    let status = run_expanded_command(input_command);
+    pop_temporary_assignments(previous_variables);
+
+    status
+}
+
+fn run_expanded_command(input_command: &str) -> i32 {
+    // command 는 command_args_builder 함수를 태워야 되기 때문에 다 owned 로 한다.
+    let mut command = String::new();
+    // command_args 는 단순 slice 로 가능해서 borrowed 로 한다.
+    let command_args;
+
+    // command 가 쿼터로 묶여 있으면
+    if input_command.starts_with('\'') || input_command.starts_with('\"') {
+        let split_pattern;
+        if input_command.starts_with('\'') {
+            split_pattern = '\'';
+        } else {
+            split_pattern = '\"';
+        }
+
+        // 마지막 쿼터까지 한번 파싱
+        let Some(command_filter) = input_command.strip_prefix(split_pattern) else {
+            println!("command quotes invalid");
+            return 1;
         };
-        return;
+        let Some(command_quote_end_idx) = command_filter.find(split_pattern) else {
+            println!("command single quotes invalid");
+            return 1;
+        };
+
+        // command_filter 섀도잉
+        let command_filter = &command_filter[..command_quote_end_idx];
+
+        // 기존에 묶여있던 쿼터로 다시 한번 묶어줌
+        let mut command_args_builder_param = String::new();
+        if split_pattern == '\'' {
+            command_args_builder_param.push('\'');
+        } else {
+            command_args_builder_param.push('"');
+        }
+        command_args_builder_param.push_str(command_filter);
+        if split_pattern == '\'' {
+            command_args_builder_param.push('\'');
+        } else {
+            command_args_builder_param.push('"');
+        }
+
+        let command_args_builder_result = special_char_args_builder(&command_args_builder_param);
+        command = command_args_builder_result.join(" ");
+
+        let Some(command_args_filter) = input_command.strip_prefix(&command_args_builder_param) else {
+            println!("command single quotes invalid");
+            return 1;
+        };
+        command_args = command_args_filter.trim();
+
+    // 아무것도 묶여있지 않으면 공백으로 커맨드, 커맨드 파라미터 분리
+    } else if input_command.contains(" ") {
+        if let Some((cmd, rest)) = input_command.split_once(' ') {
+            command = cmd.to_string();
+            command_args = rest.trim();
+        } else {
+            command_args = "";
+        }
+    // 공백 조차 없으면 파라미터가 없는거
+    } else {
+        command = input_command.to_string();
+        command_args = "";
+    }
+
+    // command 섀도잉
+    let command = &command[..];
+
+    // 같은 이름이면 builtin 보다 함수가 우선
+    if let Some(function_body) = get_function(command) {
+        return call_function(&function_body, special_char_args_builder(command_args));
+    }
+
+    match command {
+        // 파라미터가 불필요한 명령어
+        "exit" => command_exit(&command_args),
+        // 파라미터가 필요한 명령어
+        _ => {
+            match command {
+                "echo" => run_builtin_with_redirections(command_args, command_echo),
+                "printf" => run_builtin_with_redirections(command_args, command_printf),
+                "read" => run_builtin_with_redirections(command_args, command_read),
+                "type" => command_type(&command_args),
+                "pwd" => command_pwd(&special_char_args_builder(command_args)),
+                "cd" => command_cd(&special_char_args_builder(command_args)),
+                "test" => command_test(&special_char_args_builder(command_args)),
+                "[" => command_bracket(&special_char_args_builder(command_args)),
+                "history" => command_history(&special_char_args_builder(command_args)),
+                "set" => command_set(&special_char_args_builder(command_args)),
+                "return" => command_return(&command_args),
+                "complete" => command_complete(&special_char_args_builder(command_args)),
+                "compgen" => command_compgen(&special_char_args_builder(command_args)),
+                "hash" => command_hash(&special_char_args_builder(command_args)),
+                "bind" => command_bind(&special_char_args_builder(command_args)),
+                "pushd" => command_pushd(&special_char_args_builder(command_args)),
+                "popd" => command_popd(&special_char_args_builder(command_args)),
+                "dirs" => command_dirs(&special_char_args_builder(command_args)),
+                "z" | "j" => command_z(command, &special_char_args_builder(command_args)),
+                // cat 과 ls 는 구현이 아닌 외부에 이미 있는 command 를 사용 하게끔 한다
+                "cat" => command_cat(&command_args),
+                "ls" => command_ls(&command_args),
+                _ => command_execute(command, &command_args)
+            }
+        }
     }
+}
+
+fn call_function(function_body: &CommandNode, args: Vec<String>) -> i32 {
+    let previous_parameters = replace_positional_parameters(args);
+    FUNCTION_DEPTH.fetch_add(1, Ordering::Relaxed);
+
+    let status = execute_command_node(function_body);
+
+    FUNCTION_DEPTH.fetch_sub(1, Ordering::Relaxed);
+    replace_positional_parameters(previous_parameters);
+    // return 은 호출한 함수까지만 빠져나감
+    IS_RETURN_REQUESTED.store(false, Ordering::Relaxed);
+
+    status
+}
 
-    if enum_output == CommandOutput::FileAppend {
-        match OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open(writer_output) {
-            Ok(mut f) => {
-                match write!(f, "{}", args) {
-                    Ok(_) => {},
-                    Err(e) => println!("file contents write error. path: {}, e: {}", writer_output, e)
+fn command_return(args: &str) -> i32 {
+    if FUNCTION_DEPTH.load(Ordering::Relaxed) == 0 {
+        println!("return: can only `return' from a function");
+        return 1;
+    }
+
+    IS_RETURN_REQUESTED.store(true, Ordering::Relaxed);
+
+    let args = args.trim();
+    if args.is_empty() {
+        return LAST_EXIT_STATUS.load(Ordering::Relaxed);
+    }
+
+    match args.parse::<i32>() {
+        Ok(status) => status & 0xff,
+        Err(_) => {
+            println!("return: {}: numeric argument required", args);
+            2
+        }
+    }
+}
+
+// 앞쪽의 NAME=value 단어들과 나머지 command 를 분리 (쿼터 안의 공백은 단어를 나누지 않음)
+fn split_assignment_prefix(input_command: &str) -> (Vec<(String, String)>, &str) {
+    let mut assignments = vec![];
+    let mut rest = input_command.trim_start();
+
+    loop {
+        let mut is_single_quote = false;
+        let mut is_double_quote = false;
+        let mut is_ignore_next = false;
+        let word_end = rest
+            .char_indices()
+            .find(|(_, char)| {
+                if is_ignore_next {
+                    is_ignore_next = false;
+                    return false;
+                }
+                match char {
+                    '\\' if false == is_single_quote => is_ignore_next = true,
+                    '\'' if false == is_double_quote => is_single_quote = !is_single_quote,
+                    '"' if false == is_single_quote => is_double_quote = !is_double_quote,
+                    ' ' | '\t' => return false == is_single_quote && false == is_double_quote,
+                    _ => {}
                 }
-            },
-            Err(e) => println!("file write error. path: {}, e: {}", writer_output, e)
+                false
+            })
+            .map(|(idx, _)| idx)
+            .unwrap_or(rest.len());
+
+        let Some((name, value)) = rest[..word_end].split_once('=') else {
+            break;
         };
-        return;
+        if false == is_valid_variable_name(name) {
+            break;
+        }
+
+        assignments.push((name.to_string(), special_char_args_builder(value).join(" ")));
+        rest = rest[word_end..].trim_start();
+    }
+
+    (assignments, rest)
+}
+
+fn try_variable_assignment(input_command: &str) -> Option<i32> {
+    // NAME=(a b c) 배열 대입
+    if let Some((name, values)) = input_command.trim().split_once("=(")
+        && is_valid_variable_name(name)
+        && let Some(values) = values.strip_suffix(')') {
+        set_array_variable(name, special_char_args_builder(values));
+        return Some(0);
+    }
+
+    let words = special_char_args_builder(input_command);
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut assignments = vec![];
+    for word in &words {
+        let (name, value) = word.split_once('=')?;
+        if false == is_valid_variable_name(name) {
+            return None;
+        }
+        assignments.push((name, value));
+    }
+
+    for (name, value) in assignments {
+        set_variable(name, value);
     }
+
+    Some(0)
+}
+
+// builtin 과 PATH 의 실행 파일 (PATH 는 command_hash 의 cache 를 사용)
+fn get_all_executable_command() -> Vec<String> {
+    let mut result: Vec<String> = COMMAND.into_iter().map(String::from).collect();
+    result.extend(get_executable_command_names());
+
+    result.sort();
+    result.dedup();
+
+    result
 }
 
 //// single quotes ///
@@ -254,6 +966,7 @@ fn command_output(enum_output: CommandOutput, args: &str, writer_output: &str) {
 // hello    world :   hello world : 연속된 공백은 따옴표로 묶지 않는 한 축소됩니다.
 // 'hello''world' : helloworld    : 인접한 따옴표로 묶인 문자열 'hello'은 'world'연결됩니다.
 // hello''world   : helloworld    : 빈 따옴표 ''는 무시됩니다.
+// '' "  "        : [0] , [1]     : 쿼터로 묶인 빈 문자열과 공백만 있는 문자열도 인자 하나로 유지됩니다.
 
 /// double quotes ///
 // "hello    world" : hello    world
@@ -264,6 +977,7 @@ fn command_output(enum_output: CommandOutput, args: &str, writer_output: &str) {
 // "example\"insidequotes"world\" : example"insidequotesworld"
 // \'\"world example\"\' : '"world example"'
 // "mixed\"quote'world'\\" : mixed"quote'world'\
+// "a\nb" : a\nb  : 더블 쿼터 안의 백슬래쉬는 $ ` " \ 개행 앞에서만 escape
 // "test  world"  "shell""script" : test  world shellscript
 // "script\"insidequotes"example\" : script"insidequotesexample"
 // /tmp/dog/"number 41" /tmp/dog/"doublequote \" 22" /tmp/dog/"backslash \\ 82" : [0] /tmp/dog/number 41 , [1] /tmp/dog/doublequote " 22 , [2] /tmp/dog/backslash \ 82
@@ -276,6 +990,8 @@ fn special_char_args_builder(args: &str) -> Vec<String> {
     let mut is_ignore_next = false;
     let mut is_ignore_backslash = false;
     let mut is_ignore_result_push = false;
+    // 현재 단어에 쿼터가 있었으면 비어 있어도 인자
+    let mut is_word_quoted = false;
 
     for (idx, char) in args.char_indices() {
         let mut before_char = '\0';
@@ -290,8 +1006,12 @@ fn special_char_args_builder(args: &str) -> Vec<String> {
                 is_ignore_next = true;
                 continue;
             }
-            // 더블 쿼터로 묶여 있을 경우
+            // 더블 쿼터로 묶여 있을 경우 $ ` " \ 개행 앞에서만 escape, 그 외에는 백슬래쉬 그대로
             if true == is_quote_start && true == is_double_quote {
+                if false == matches!(args[idx + 1..].chars().next(), Some('$' | '`' | '"' | '\\' | '\n')) {
+                    result_tmp.push(char);
+                    continue;
+                }
                 is_ignore_next = true;
                 continue;
             }
@@ -340,6 +1060,7 @@ fn special_char_args_builder(args: &str) -> Vec<String> {
                 continue;
             } else {
                 is_quote_start = true;
+                is_word_quoted = true;
                 if char == '\"' {
                     is_double_quote = true;
                 }
@@ -367,12 +1088,13 @@ fn special_char_args_builder(args: &str) -> Vec<String> {
                     }
 
                     // 공백 기준 구분으로 result 에 push
-                    if result_tmp.trim().is_empty() {
+                    if result_tmp.trim().is_empty() && false == is_word_quoted {
                         continue;
                     }
 
                     result.push(result_tmp);
                     result_tmp = String::with_capacity(args.len());
+                    is_word_quoted = false;
                     continue;
                 // string push
                 } else {
@@ -383,256 +1105,150 @@ fn special_char_args_builder(args: &str) -> Vec<String> {
         }
     }
 
-    if false == result_tmp.is_empty() {
+    if false == result_tmp.is_empty() || is_word_quoted {
         result.push(result_tmp);
     }
 
     result
 }
 
-fn is_redirection_args(args: &str) -> bool {
-    if args.contains(">") {
-        return true;
-    }
-    false
-}
-
-fn redirection_args_builder(args: &str) -> RedirectionArgsBuilderResult {
-    let mut result = RedirectionArgsBuilderResult::default();
-
-    let splited_redirection_str;
-
-    if args.contains("1>>") {
-        splited_redirection_str = "1>>";
-        result.redirect = "1>>".to_string();
-    } else if args.contains("2>>") {
-        splited_redirection_str = "2>>";
-        result.redirect = "2>>".to_string();
-    } else if args.contains(">>") {
-        splited_redirection_str = ">>";
-        result.redirect = ">>".to_string();
-    } else if args.contains("1>") {
-        splited_redirection_str = "1>";
-        result.redirect = "1>".to_string();
-    } else if args.contains("2>") {
-        splited_redirection_str = "2>";
-        result.redirect = "2>".to_string();
-    } else {
-        splited_redirection_str = ">";
-        result.redirect = ">".to_string();
-    }
-
-    let mut splited_redirection_args:Vec<&str> = args.split(splited_redirection_str).collect();
-    let Some(output) = splited_redirection_args.pop() else {
-        println!("command args output error 1. args: {}", args);
-        return result;
-    };
-
-    result.output = output.trim().to_string();
-
-    let Some(command_args) = splited_redirection_args.first() else {
-        println!("command args output error 2. args: {}", args);
-        return result;
-    };
-
-    let mut command_args = command_args.trim().to_string();
-    command_args.push_str("\r\n");
-    result.command_args = command_args;
-
-    result.result = CommandResult::Success;
-
-    result
-}
-
-fn command_echo(args: &str) {
-    let echo_args_builder;
-    let command_output_enum;
-    let writer_output;
-
-    if is_redirection_args(args) {
-        let redirection_args_builder_result: RedirectionArgsBuilderResult = redirection_args_builder(args);
-        if redirection_args_builder_result.result != CommandResult::Success {
-            return;
-        }
-
-        echo_args_builder = redirection_args_builder_result.command_args;
-        writer_output = redirection_args_builder_result.output;
-
-        if redirection_args_builder_result.redirect == "2>" || redirection_args_builder_result.redirect == "2>>" {
-            command_output_enum = CommandOutput::StdOut;
-            // 파일 없더라도 생성 필요
-            command_output(CommandOutput::File, "", &writer_output);
-        } else if redirection_args_builder_result.redirect == ">>" || redirection_args_builder_result.redirect == "1>>" {
-            command_output_enum = CommandOutput::FileAppend;
-        } else {
-            command_output_enum = CommandOutput::File;
-        }
-    } else {
-        echo_args_builder = args.to_string();
-        command_output_enum = CommandOutput::StdOutNewLine;
-        writer_output = "".to_string();
-    }
-
-    let echo_args_builder = special_char_args_builder(&echo_args_builder);
-    if echo_args_builder.is_empty() {
-        return;
-    }
-
-    let echo_args_builder = echo_args_builder.join(" ");
-    command_output(command_output_enum, &echo_args_builder, &writer_output);
-}
-
-fn command_type(args: &str) {
+fn command_type(args: &str) -> i32 {
     let check_command_executable_result = check_command_executable(args);
     if CommandResult::Success == check_command_executable_result.result {
         println!("{} is {}", check_command_executable_result.command, check_command_executable_result.full_path);
+        return 0;
     }
-}
-
-fn command_pwd() {
-    let Ok(current_path) = env::current_dir() else {
-        println!("command_pwd current_dir error");
-        return;
-    };
-
-    println!("{}", current_path.display());
-}
 
-fn command_cd(args: &str) {
-    // cd HOME environment
-    let try_change_path: Cow<'_, str> = if args == "~" {
-        match env::var("HOME") {
-            Ok(home) => Cow::Owned(home),
-            Err(e) => {
-                println!("command_cd HOME env cant found. error: {}", e);
-                return;
-            }
-        }
-    // else args path
-    } else {
-        Cow::Borrowed(args)
-    };
+    // builtin 은 check_command_executable 에서 출력까지 끝남
+    if COMMAND.contains(&args) {
+        return 0;
+    }
 
-    let change_path = Path::new(try_change_path.as_ref());
-    let Ok(_) = env::set_current_dir(&change_path) else {
-        println!("cd: {}: No such file or directory", args);
-        return;
-    };
+    1
 }
 
-fn command_cat(args: &str) {
-    command_execute("cat", args);
+fn command_cat(args: &str) -> i32 {
+    command_execute("cat", args)
 }
 
-fn command_ls(args: &str) {
-    command_execute("ls", args);
+fn command_ls(args: &str) -> i32 {
+    command_execute("ls", args)
 }
 
-fn command_execute(command: &str, command_args: &str) {
+fn command_execute(command: &str, command_args: &str) -> i32 {
     let check_command_executable_result = check_command_executable(command);
     if CommandResult::Success != check_command_executable_result.result {
-        return;
+        return 127;
     }
 
-    let command_execute_args_builder;
-    let mut command_output_enum;
-    let writer_output;
-    let mut is_error_redirect = false;
-
-    if is_redirection_args(command_args) {
-        let redirection_args_builder_result: RedirectionArgsBuilderResult = redirection_args_builder(command_args);
-        if redirection_args_builder_result.result != CommandResult::Success {
-            return;
-        }
-
-        command_execute_args_builder = redirection_args_builder_result.command_args;
-        if redirection_args_builder_result.redirect == "1>>" 
-            || redirection_args_builder_result.redirect == ">>"
-            || redirection_args_builder_result.redirect == "2>>" {
-            command_output_enum = CommandOutput::FileAppend
-        } else {
-            command_output_enum = CommandOutput::File;
+    let (command_args, redirections) = match split_redirections(command_args) {
+        Ok(splited) => splited,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
         }
-        writer_output = redirection_args_builder_result.output;
+    };
 
-        if redirection_args_builder_result.redirect == "2>" || redirection_args_builder_result.redirect == "2>>" {
-            is_error_redirect = true;
-        }
+    // builtin 과 같이 redirection 을 fd 에 적용하고, 외부 command 는 stdin, stdout, stderr 를 그대로 물려받음
+    let Some(saved_fds) = apply_group_redirections(&redirections) else {
+        return 1;
+    };
+    let status = spawn_command(command, &check_command_executable_result, &command_args);
+    restore_group_redirections(saved_fds);
 
-        // redirection 은 내용 상관 없이 일단 파일 생성
-        command_output(command_output_enum, "", &writer_output);
-    } else {
-        command_execute_args_builder = command_args.to_string();
-        command_output_enum = CommandOutput::StdOut;
-        writer_output = "".to_string();
-    }
+    status
+}
 
+fn spawn_command(command: &str, check_command_executable_result: &CommandExecutableResult, command_args: &str) -> i32 {
     let mut valid_command_args:Vec<String> = vec![];
+    let mut is_path_error = false;
 
-    {
-        let command_args_vec = special_char_args_builder(command_execute_args_builder.trim());
-        let mut error_messages:Vec<String> = vec![];
+    for command_arg in special_char_args_builder(command_args.trim()) {
+        // 하이푼이 붙은 옵션이면 무시, 옵션이 아니면 경로 존재 하는지 확인
+        if command_arg.starts_with("-") {
+            valid_command_args.push(command_arg);
+            continue;
+        }
 
-        for command_arg in command_args_vec {
-            let command_arg = command_arg.trim();
+        // 명확하게 path 가 들어오는 command 인 경우 있는 path 인지 확인
+        if COMMAND_PATH.contains(&command) && false == Path::new(&command_arg).exists() {
+            eprintln!("{}: {}: No such file or directory", check_command_executable_result.command, command_arg);
+            is_path_error = true;
+            continue;
+        }
 
-            if command_arg.is_empty() {
-                continue;
-            }
+        valid_command_args.push(command_arg);
+    }
 
-            // 하이푼이 붙은 옵션이면 무시, 옵션이 아니면 경로 존재 하는지 확인
-            if command_arg.starts_with("-") {
-                valid_command_args.push(command_arg.to_string());
-                continue;
-            }
+    // path 에러가 있었으면 valid_command_args 요소중 "-" 로 시작하는 옵션 외에 있을 경우만 실행
+    let is_command_execute = false == is_path_error || valid_command_args.iter().any(|v| false == v.starts_with("-"));
+    if false == is_command_execute {
+        return 1;
+    }
 
-            // 명확하게 path 가 들어오는 command 인 경우 있는 path 인지 확인
-            if COMMAND_PATH.contains(&command) {
-                let path = Path::new(&command_arg);
-                if false == path.exists() {
-                    let error_message = format!("{}: {}: No such file or directory\r\n", check_command_executable_result.command, command_arg);
-                    error_messages.push(error_message);
-                    continue;
-                }
+    // execute command
+    // hash -p 로 기억된 경로도 실행할 수 있게 경로로 실행하고, argv[0] 은 입력한 이름
+    io::stdout().flush().ok();
+    match Command::new(&check_command_executable_result.full_path).arg0(&check_command_executable_result.command).args(valid_command_args).envs(get_temporary_assignments()).status() {
+        Ok(exit_status) => {
+            let status = exit_status_code(&exit_status);
+            // 일부 path 가 없었으면 실행은 했더라도 실패로 처리
+            if is_path_error && status == 0 {
+                return 1;
             }
-
-            valid_command_args.push(command_arg.to_string());
+            status
+        },
+        Err(e) => {
+            eprintln!("{}: {}", command, e);
+            126
         }
+    }
+}
 
-        {
-            let error_message = &error_messages.join("");
-            // 에러가 있는 경우
-            if false == error_message.is_empty() {
-                // 2> 혹은 2>> 인 경우 에러 내용을 기록
-                if is_error_redirect {
-                    command_output(command_output_enum, error_message, &writer_output);
-                    // 성공하는 args 도 섞여 있을 수 있기 때문에 표준 출력을 위해 StdOutNewLine 으로 변경
-                    command_output_enum = CommandOutput::StdOut;
-                // 2> 와 2>> 가 아닐 경우 에러를 표준 출력
-                } else {
-                    command_output(CommandOutput::StdOut, error_message, &writer_output);
-                }
-            }
-        }
+fn exit_status_code(exit_status: &std::process::ExitStatus) -> i32 {
+    if let Some(code) = exit_status.code() {
+        return code;
     }
 
-    // valid_command_args 요소중 "-" 로 시작하는 옵션 외에 있을 경우만 실행
-    let is_command_execute = valid_command_args.iter().any(|v| false == v.starts_with("-"));
+    // signal 로 종료된 경우
+    match std::os::unix::process::ExitStatusExt::signal(exit_status) {
+        Some(signal) => 128 + signal,
+        None => 1,
+    }
+}
 
-    // execute command
-    if is_command_execute {
-        match Command::new(check_command_executable_result.command).args(valid_command_args).output() {
-            Ok(output) => {
-                command_output(command_output_enum, str::from_utf8(&output.stdout).unwrap(), &writer_output);
-            },
-            Err(e) => {
-                println!("{}", e);
-            }
+fn command_exit(args: &str) -> i32 {
+    IS_EXIT_REQUESTED.store(true, Ordering::Relaxed);
+
+    let args = args.trim();
+    if args.is_empty() {
+        return LAST_EXIT_STATUS.load(Ordering::Relaxed);
+    }
+
+    match args.parse::<i32>() {
+        Ok(status) => status & 0xff,
+        Err(_) => {
+            println!("exit: {}: numeric argument required", args);
+            2
         }
     }
 }
 
 fn check_command_executable(command: &str) -> CommandExecutableResult {
+    let result = find_command_executable(command, true);
+
+    if result.is_builtin {
+        println!("{} is a shell builtin", command);
+    } else if CommandResult::NotFound == result.result {
+        println!("{}: not found", command);
+    }
+
+    result
+}
+
+// 출력 없이 builtin 인지, PATH 에 있는지 확인
+// is_hashed 면 찾은 경로를 hash table 에 기억하고 사용 횟수 증가 (실행할 때만)
+fn find_command_executable(command: &str, is_hashed: bool) -> CommandExecutableResult {
     let mut result: CommandExecutableResult = CommandExecutableResult::default();
 
     // struct 에 담기 때문에 owned
@@ -640,40 +1256,30 @@ fn check_command_executable(command: &str) -> CommandExecutableResult {
     result.result = CommandResult::NotFound;
 
     if COMMAND.contains(&command) {
-        println!("{} is a shell builtin", command);
+        result.is_builtin = true;
         return result;
     }
 
-    // get PATH
-    let Some(paths) = env::var_os("PATH") else {
-        println!("{}: not found", command);
-        return result;
+    // PATH 는 매번 scan 하지 않고 hash table 에서 찾음
+    let full_path = if is_hashed {
+        find_command_path(command)
+    } else {
+        get_hashed_command(command).map(|hashed_command| hashed_command.path).or_else(|| get_executable_path(command))
     };
-
-    for path in env::split_paths(&paths) {
-        let full = path.join(command);
-
-        // Check if a file with the command name exists.
-        if fs::metadata(&full).is_err() {
-            continue;
-        }
-
-        // Check if the file has execute permissions.
-        // full_display 를 result 에 담아야 되기 때문에 into_owned 로 소유
-        let full_display = full.to_string_lossy().into_owned();
-        let path_display = Path::new(&full_display);
-        if false == path_display.is_executable() {
-            continue;
-        }
-
-        result.full_path = full_display;
+    if let Some(full_path) = full_path {
+        result.full_path = full_path.to_string_lossy().into_owned();
         result.result = CommandResult::Success;
-        break;
     }
 
-    if CommandResult::NotFound == result.result {
-        println!("{}: not found", command);
+    result
+}
+
+// syntax highlighting 용, 입력한 command 를 실행할 수 있는지 (함수, builtin, PATH)
+fn is_command_executable(command: &str) -> bool {
+    if get_function(command).is_some() {
+        return true;
     }
 
-    return result;
-}
\ No newline at end of file
+    let result = find_command_executable(command, false);
+    result.is_builtin || CommandResult::Success == result.result
+}
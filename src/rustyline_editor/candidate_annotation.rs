@@ -0,0 +1,117 @@
+// 후보 목록을 보여줄 때 붙이는 종류와 짧은 설명
+//   종류 : builtin, function, 실행 파일 (PATH 디렉토리), directory, file, variable, option
+//   설명 : provider, complete -F 의 COMPREPLY, complete -C 의 출력에서 "후보\t설명" 형태로 받거나
+//          실행 파일은 whatis 결과 (한번 찾은 결과는 cache)
+
+use std::{collections::BTreeMap, path::PathBuf, sync::Mutex};
+
+use crate::rustyline_editor::completion::{CompletionWord, expand_candidate_path};
+use crate::rustyline_editor::completion_providers::help_options::run_command_with_timeout;
+use crate::shell_state::command_hash::get_executable_path;
+use crate::shell_state::functions::get_function;
+
+// command 이름 -> whatis 설명 (없으면 None 으로 cache 해서 다시 실행하지 않음)
+static WHATIS_CACHE: Mutex<BTreeMap<String, Option<String>>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CandidateKind {
+    Builtin,
+    Function,
+    // 실행 파일이 있는 PATH 디렉토리
+    Executable(PathBuf),
+    Directory,
+    File,
+    Variable,
+    Option,
+    // -W 단어처럼 종류를 알 수 없는 후보
+    Word,
+}
+
+impl CandidateKind {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Builtin => "builtin".to_string(),
+            Self::Function => "function".to_string(),
+            Self::Executable(dir) => format!("executable ({})", dir.display()),
+            Self::Directory => "directory".to_string(),
+            Self::File => "file".to_string(),
+            Self::Variable => "variable".to_string(),
+            Self::Option => "option".to_string(),
+            Self::Word => String::new(),
+        }
+    }
+}
+
+// "후보\t설명" 을 후보와 설명으로 나눔
+pub fn split_candidate_description(candidate: &str) -> (&str, Option<&str>) {
+    match candidate.split_once('\t') {
+        Some((text, description)) if false == description.trim().is_empty() => (text, Some(description.trim())),
+        Some((text, _)) => (text, None),
+        None => (candidate, None),
+    }
+}
+
+// 실행할 때와 같은 우선순위 (함수 > builtin > PATH)
+pub fn get_candidate_kind(candidate: &str, completion_word: &CompletionWord) -> CandidateKind {
+    if candidate.ends_with('/') {
+        return CandidateKind::Directory;
+    }
+
+    if completion_word.is_command_position && false == candidate.contains('/') {
+        if get_function(candidate).is_some() {
+            return CandidateKind::Function;
+        }
+        if crate::COMMAND.contains(&candidate) {
+            return CandidateKind::Builtin;
+        }
+        if let Some(path) = get_executable_path(candidate) {
+            return CandidateKind::Executable(path.parent().map(|dir| dir.to_path_buf()).unwrap_or_default());
+        }
+    }
+
+    if candidate.starts_with('$') {
+        return CandidateKind::Variable;
+    }
+    if candidate.starts_with('-') {
+        return CandidateKind::Option;
+    }
+    if expand_candidate_path(candidate).exists() {
+        return CandidateKind::File;
+    }
+
+    CandidateKind::Word
+}
+
+// whatis 로 command 설명을 한번에 찾음
+pub fn get_command_descriptions(names: &[&str]) -> BTreeMap<String, String> {
+    let uncached_names: Vec<&str> = {
+        let whatis_cache = WHATIS_CACHE.lock().unwrap();
+        names.iter().copied().filter(|name| false == whatis_cache.contains_key(*name)).collect()
+    };
+
+    if false == uncached_names.is_empty() {
+        // "ls (1)               - list directory contents"
+        let whatis_output = run_command_with_timeout("whatis", &uncached_names).unwrap_or_default();
+        let mut found: BTreeMap<String, String> = BTreeMap::new();
+        for line in whatis_output.lines() {
+            let Some((left, description)) = line.split_once(" - ") else {
+                continue;
+            };
+            let Some(name) = left.split_whitespace().next() else {
+                continue;
+            };
+            found.entry(name.to_string()).or_insert_with(|| description.trim().to_string());
+        }
+
+        let mut whatis_cache = WHATIS_CACHE.lock().unwrap();
+        for name in uncached_names {
+            whatis_cache.insert(name.to_string(), found.get(name).cloned());
+        }
+    }
+
+    let whatis_cache = WHATIS_CACHE.lock().unwrap();
+    names
+        .iter()
+        .filter_map(|name| whatis_cache.get(*name).cloned().flatten().map(|description| (name.to_string(), description)))
+        .collect()
+}
@@ -0,0 +1,274 @@
+// TAB completion 대상 단어 파싱과 후보 생성
+//   command 위치      : command 이름 (단어에 / 가 있으면 실행 파일과 디렉토리 path)
+//   인자 위치         : 파일/디렉토리 path (디렉토리는 뒤에 / 추가)
+//   $NAME             : 변수 이름
+//   ~/..., $VAR/...   : 앞부분을 확장한 경로에서 후보를 찾고, 입력한 형태는 그대로 유지
+
+use std::{env, fs, path::PathBuf};
+
+use is_executable::IsExecutable;
+
+use crate::shell_state::variables::{get_all_variables, get_variable};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionWord {
+    // cursor 앞의 현재 단어 (쿼터와 escape 를 제거한 값)
+    pub text: String,
+    // 단어가 쿼터로 열려 있으면 그 쿼터 문자
+    pub quote: Option<char>,
+    // command 이름 자리인지 여부
+    pub is_command_position: bool,
+    // 같은 command 의 앞쪽 단어들 (첫번째가 command 이름)
+    pub previous_words: Vec<String>,
+    // line 에서 현재 단어가 시작하는 byte 위치 (쿼터 포함)
+    pub start: usize,
+}
+
+// cursor 앞까지의 line 에서 현재 completion 대상 단어를 찾음
+pub fn parse_completion_word(line: &str) -> CompletionWord {
+    let mut text = String::new();
+    let mut quote: Option<char> = None;
+    let mut is_ignore_next = false;
+    let mut is_word_started = false;
+    let mut is_command_position = true;
+    let mut previous_words: Vec<String> = vec![];
+    let mut start = 0;
+
+    for (idx, char) in line.char_indices() {
+        if is_ignore_next {
+            text.push(char);
+            is_ignore_next = false;
+            continue;
+        }
+
+        match quote {
+            Some('\'') => {
+                if char == '\'' {
+                    quote = None;
+                } else {
+                    text.push(char);
+                }
+                continue;
+            }
+            Some(_) => {
+                if char == '"' {
+                    quote = None;
+                } else if char == '\\' {
+                    is_ignore_next = true;
+                } else {
+                    text.push(char);
+                }
+                continue;
+            }
+            None => {}
+        }
+
+        match char {
+            '\\' => {
+                is_word_started = true;
+                is_ignore_next = true;
+            }
+            '\'' | '"' => {
+                is_word_started = true;
+                quote = Some(char);
+            }
+            // 단어 구분
+            ' ' | '\t' => {
+                start = idx + 1;
+                if is_word_started {
+                    previous_words.push(std::mem::take(&mut text));
+                    is_word_started = false;
+                    is_command_position = false;
+                }
+            }
+            // operator 뒤는 새로운 command
+            ';' | '&' | '|' | '(' | ')' => {
+                start = idx + 1;
+                text.clear();
+                previous_words.clear();
+                is_word_started = false;
+                is_command_position = true;
+            }
+            // redirection 뒤의 단어는 파일
+            '<' | '>' => {
+                start = idx + 1;
+                text.clear();
+                is_word_started = false;
+                if previous_words.is_empty() {
+                    is_command_position = false;
+                }
+            }
+            _ => {
+                is_word_started = true;
+                text.push(char);
+            }
+        }
+    }
+
+    // { 와 ! 는 다음 단어가 command
+    if false == is_command_position && false == previous_words.is_empty() && previous_words.iter().all(|word| word == "{" || word == "!" || word == "((") {
+        is_command_position = true;
+        previous_words.clear();
+    }
+
+    CompletionWord {
+        text,
+        quote,
+        is_command_position,
+        previous_words,
+        start,
+    }
+}
+
+// 입력된 path 앞부분의 ~ 와 $VAR 를 실제 경로로 확장
+fn expand_path_prefix(dir_part: &str) -> PathBuf {
+    if (dir_part == "~" || dir_part.starts_with("~/"))
+        && let Some(home) = get_variable("HOME") {
+        return PathBuf::from(format!("{}{}", home, &dir_part[1..]));
+    }
+
+    if let Some(rest) = dir_part.strip_prefix('$') {
+        let (name, remain) = if let Some(braced) = rest.strip_prefix('{') {
+            match braced.split_once('}') {
+                Some((name, remain)) => (name, remain),
+                None => (braced, ""),
+            }
+        } else {
+            let name_end = rest.find(|c: char| false == (c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+            (&rest[..name_end], &rest[name_end..])
+        };
+
+        if let Some(value) = get_variable(name) {
+            return PathBuf::from(format!("{}{}", value, remain));
+        }
+    }
+
+    if dir_part.is_empty() {
+        return PathBuf::from(".");
+    }
+
+    PathBuf::from(dir_part)
+}
+
+// 후보 문자열이 가리키는 실제 path (~, $VAR 는 펼침)
+pub fn expand_candidate_path(candidate: &str) -> PathBuf {
+    match candidate.rfind('/') {
+        Some(slash_idx) => expand_path_prefix(&candidate[..slash_idx + 1]).join(&candidate[slash_idx + 1..]),
+        None => expand_path_prefix("").join(candidate),
+    }
+}
+
+// 단어를 완성할 수 있는 path 후보, 디렉토리는 / 로 끝남
+// is_executable_only 면 실행 가능한 파일과 디렉토리만
+pub fn get_path_candidates(word: &str, is_executable_only: bool) -> Vec<String> {
+    if word == "~" {
+        return vec!["~/".to_string()];
+    }
+
+    let (dir_part, file_prefix) = match word.rfind('/') {
+        Some(slash_idx) => (&word[..slash_idx + 1], &word[slash_idx + 1..]),
+        None => ("", word),
+    };
+
+    let lookup_dir = expand_path_prefix(dir_part);
+    let Ok(read_dir) = fs::read_dir(&lookup_dir) else {
+        return vec![];
+    };
+
+    let mut candidates = vec![];
+    for dir_entry in read_dir.flatten() {
+        let Some(file_name) = dir_entry.file_name().to_str().map(|name| name.to_string()) else {
+            continue;
+        };
+
+        if false == file_name.starts_with(file_prefix) {
+            continue;
+        }
+
+        // 숨김 파일은 . 으로 시작하는 prefix 를 입력했을 때만
+        if file_name.starts_with('.') && false == file_prefix.starts_with('.') {
+            continue;
+        }
+
+        // symlink 가 디렉토리를 가리키는 경우도 디렉토리로
+        let full_path = lookup_dir.join(&file_name);
+        let is_dir = full_path.is_dir();
+
+        if is_executable_only && false == is_dir && false == full_path.is_executable() {
+            continue;
+        }
+
+        let mut candidate = format!("{}{}", dir_part, file_name);
+        if is_dir {
+            candidate.push('/');
+        }
+        candidates.push(candidate);
+    }
+
+    candidates.sort();
+    candidates
+}
+
+// $NAME 후보 (shell 변수 + 환경변수)
+pub fn get_variable_candidates(word: &str) -> Vec<String> {
+    let Some(prefix) = word.strip_prefix('$') else {
+        return vec![];
+    };
+
+    let mut candidates: Vec<String> = get_all_variables()
+        .into_iter()
+        .map(|(name, _)| name)
+        .chain(env::vars().map(|(name, _)| name))
+        .filter(|name| name.starts_with(prefix))
+        .map(|name| format!("${}", name))
+        .collect();
+
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+// 현재 쿼터 상태에 맞게 삽입할 문자열을 escape
+pub fn escape_completion(text: &str, quote: Option<char>) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for char in text.chars() {
+        match quote {
+            // single quotes 안에서는 ' 를 '\'' 로
+            Some('\'') => {
+                if char == '\'' {
+                    result.push_str("'\\''");
+                } else {
+                    result.push(char);
+                }
+            }
+            Some(_) => {
+                if matches!(char, '"' | '\\' | '$' | '`') {
+                    result.push('\\');
+                }
+                result.push(char);
+            }
+            None => {
+                if matches!(char, ' ' | '\t' | '\'' | '"' | '\\' | '$' | '&' | ';' | '|' | '(' | ')' | '<' | '>' | '*' | '?' | '[' | ']' | '!' | '#' | '`' | '{' | '}') {
+                    result.push('\\');
+                }
+                result.push(char);
+            }
+        }
+    }
+
+    result
+}
+
+// 후보 목록을 보여줄 때 path 는 마지막 요소만 표시
+pub fn get_candidate_display_name(candidate: &str) -> &str {
+    let trimmed = candidate.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(slash_idx) => &candidate[slash_idx + 1..],
+        None => candidate,
+    }
+}
+
+pub fn is_path_word(word: &str) -> bool {
+    word.contains('/') || word.starts_with('~')
+}
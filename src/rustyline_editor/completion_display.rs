@@ -0,0 +1,320 @@
+// completion 후보 출력
+//   두번째 TAB 의 목록은 터미널 너비에 맞춰 column 정렬 (종류나 설명이 있으면 한 줄에 하나씩), 후보가 많으면 "Display all N possibilities? (y/n)" 확인
+//   set -o menucomplete 일 때의 선택 menu
+//     ↑ ↓ (Ctrl-P, Ctrl-N, TAB, Shift-TAB) : 이동
+//     문자 입력 / Backspace                  : fuzzy 로 후보 좁히기
+//     Enter                                  : 선택, ESC / Ctrl-C / Ctrl-G : 취소
+// rustyline 의 raw mode 안에서 실행되기 때문에 stdin 에서 key 를 직접 읽음
+
+use std::io::{self, Write};
+
+use crate::rustyline_editor::fuzzy::rank_fuzzy_candidates;
+
+// bash 의 completion-query-items 기본값
+const COMPLETION_QUERY_ITEMS: usize = 100;
+const MENU_MAX_ROWS: usize = 10;
+const COLUMN_GAP: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerminalSize {
+    pub columns: usize,
+    pub rows: usize,
+}
+
+pub fn get_terminal_size() -> TerminalSize {
+    let mut window_size: libc::winsize = unsafe { std::mem::zeroed() };
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut window_size) };
+
+    if result == 0 && window_size.ws_col > 0 {
+        return TerminalSize {
+            columns: window_size.ws_col as usize,
+            rows: window_size.ws_row.max(1) as usize,
+        };
+    }
+
+    // 터미널이 아니면 COLUMNS, LINES 변수
+    let get_size_variable = |name: &str, default: usize| {
+        crate::shell_state::variables::get_variable(name).and_then(|value| value.parse::<usize>().ok()).unwrap_or(default)
+    };
+    TerminalSize {
+        columns: get_size_variable("COLUMNS", 80),
+        rows: get_size_variable("LINES", 24),
+    }
+}
+
+// 위에서 아래로 채운 뒤 다음 column 으로 (ls 와 같은 순서)
+pub fn format_columns(items: &[&str], terminal_width: usize) -> Vec<String> {
+    if items.is_empty() {
+        return vec![];
+    }
+
+    let max_width = items.iter().map(|item| item.chars().count()).max().unwrap_or(0);
+    let column_width = max_width + COLUMN_GAP;
+    let column_count = ((terminal_width + COLUMN_GAP) / column_width).max(1);
+    let row_count = items.len().div_ceil(column_count);
+
+    let mut lines = vec![];
+    for row in 0..row_count {
+        let mut line = String::new();
+        let mut column = 0;
+        while let Some(item) = items.get(column * row_count + row) {
+            line.push_str(item);
+            // 마지막 column 뒤에는 공백을 붙이지 않음
+            if items.get((column + 1) * row_count + row).is_some() {
+                line.push_str(&" ".repeat(column_width - item.chars().count()));
+            }
+            column += 1;
+        }
+        lines.push(line);
+    }
+
+    lines
+}
+
+// 후보가 많으면 출력할지 먼저 물어봄
+pub fn confirm_display_all(count: usize) -> bool {
+    if count <= COMPLETION_QUERY_ITEMS {
+        return true;
+    }
+
+    let mut stdout = io::stdout();
+    write!(stdout, "\r\nDisplay all {} possibilities? (y/n)", count).ok();
+    stdout.flush().ok();
+
+    let is_display = loop {
+        match read_key() {
+            Some(MenuKey::Char('y' | 'Y' | ' ')) => break true,
+            Some(MenuKey::Char('n' | 'N')) | Some(MenuKey::Cancel) | Some(MenuKey::Backspace) | None => break false,
+            _ => continue,
+        }
+    };
+    if false == is_display {
+        write!(stdout, "\r\n").ok();
+        stdout.flush().ok();
+    }
+    is_display
+}
+
+// 후보 이름, 종류, 설명을 한 줄에 하나씩 맞춰서 (터미널 너비를 넘으면 자름)
+pub fn format_annotated_lines(items: &[AnnotatedCandidate], terminal_width: usize) -> Vec<String> {
+    let name_width = items.iter().map(|item| item.display_name.chars().count()).max().unwrap_or(0);
+    let kind_width = items.iter().map(|item| item.kind.chars().count()).max().unwrap_or(0);
+
+    items
+        .iter()
+        .map(|item| {
+            let mut line = format!("{:<name_width$}", item.display_name);
+            if kind_width > 0 {
+                line.push_str(&" ".repeat(COLUMN_GAP));
+                line.push_str(&format!("{:<kind_width$}", item.kind));
+            }
+            if let Some(description) = &item.description {
+                line.push_str(&" ".repeat(COLUMN_GAP));
+                line.push_str(description);
+            }
+            truncate_to_width(line.trim_end(), terminal_width)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotatedCandidate {
+    pub display_name: String,
+    // 비어 있으면 종류를 표시하지 않음
+    pub kind: String,
+    pub description: Option<String>,
+}
+
+// 종류나 설명이 있는 후보 목록 출력, 확인은 confirm_display_all 로 미리 받음
+pub fn print_annotated_candidate_list(items: &[AnnotatedCandidate]) {
+    let mut stdout = io::stdout();
+    write!(stdout, "\r\n").ok();
+
+    // 붙일 정보가 없으면 기존처럼 column 정렬
+    let is_plain = items.iter().all(|item| item.kind.is_empty() && item.description.is_none());
+    let lines = if is_plain {
+        let display_names: Vec<&str> = items.iter().map(|item| item.display_name.as_str()).collect();
+        format_columns(&display_names, get_terminal_size().columns)
+    } else {
+        format_annotated_lines(items, get_terminal_size().columns)
+    };
+    for line in lines {
+        write!(stdout, "{}\r\n", line).ok();
+    }
+    stdout.flush().ok();
+}
+
+// menu 와 history 검색 UI 에서 읽는 key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MenuKey {
+    Up,
+    Down,
+    Tab,
+    Enter,
+    Cancel,
+    Backspace,
+    Char(char),
+    // 위에 해당하지 않는 Ctrl-문자 (대문자)
+    Control(char),
+    Other,
+}
+
+fn read_byte(timeout_ms: Option<i32>) -> Option<u8> {
+    if let Some(timeout_ms) = timeout_ms {
+        let mut poll_fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+        if unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) } <= 0 {
+            return None;
+        }
+    }
+
+    let mut byte = 0u8;
+    let read_count = unsafe { libc::read(libc::STDIN_FILENO, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+    if read_count == 1 { Some(byte) } else { None }
+}
+
+pub fn read_key() -> Option<MenuKey> {
+    let first_byte = read_byte(None)?;
+
+    let key = match first_byte {
+        b'\r' | b'\n' => MenuKey::Enter,
+        // Ctrl-C, Ctrl-G
+        0x03 | 0x07 => MenuKey::Cancel,
+        0x7f | 0x08 => MenuKey::Backspace,
+        // Ctrl-P, Ctrl-N
+        0x10 => MenuKey::Up,
+        0x0e => MenuKey::Down,
+        b'\t' => MenuKey::Tab,
+        0x1b => {
+            // ESC 만 눌렀는지 escape sequence 인지 잠깐 기다려서 구분
+            let Some(second_byte) = read_byte(Some(50)) else {
+                return Some(MenuKey::Cancel);
+            };
+            if second_byte != b'[' && second_byte != b'O' {
+                return Some(MenuKey::Other);
+            }
+
+            // 파라미터(숫자, ;) 뒤의 마지막 문자까지 읽음
+            let mut final_byte = read_byte(Some(50))?;
+            while final_byte.is_ascii_digit() || final_byte == b';' {
+                final_byte = read_byte(Some(50))?;
+            }
+            match final_byte {
+                b'A' | b'Z' => MenuKey::Up,
+                b'B' => MenuKey::Down,
+                _ => MenuKey::Other,
+            }
+        }
+        byte if byte < 0x20 => MenuKey::Control((byte + 0x40) as char),
+        byte => {
+            // UTF-8 multi byte 문자
+            let char_len = match byte {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+            let mut bytes = vec![byte];
+            for _ in 1..char_len {
+                bytes.push(read_byte(Some(50))?);
+            }
+            match String::from_utf8(bytes).ok().and_then(|text| text.chars().next()) {
+                Some(char) => MenuKey::Char(char),
+                None => MenuKey::Other,
+            }
+        }
+    };
+
+    Some(key)
+}
+
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+// 선택한 후보를 반환, 취소하면 None
+// get_match_text 는 후보에서 fuzzy 매칭할 부분 (path 는 마지막 요소)
+pub fn select_from_menu(query: &str, candidates: &[String], get_match_text: impl Fn(&str) -> &str) -> Option<String> {
+    let mut stdout = io::stdout();
+    let terminal_size = get_terminal_size();
+
+    let mut query = query.to_string();
+    let mut filtered_candidates = candidates.to_vec();
+    let mut selected_idx = 0;
+    let mut scroll_offset = 0;
+
+    // query 줄 + 후보 줄
+    let visible_rows = MENU_MAX_ROWS.min(candidates.len()).min(terminal_size.rows.saturating_sub(2)).max(1);
+    let menu_height = visible_rows + 1;
+
+    // 화면 아래쪽이면 scroll 되도록 미리 줄을 확보하고 (ESC D 는 column 을 유지하면서 한 줄 아래로)
+    // prompt 위치로 돌아와서 cursor 위치 저장
+    write!(stdout, "{}\x1b[{}A\x1b7", "\x1bD".repeat(menu_height), menu_height).ok();
+
+    let selected = loop {
+        if selected_idx < scroll_offset {
+            scroll_offset = selected_idx;
+        } else if selected_idx >= scroll_offset + visible_rows {
+            scroll_offset = selected_idx + 1 - visible_rows;
+        }
+
+        write!(stdout, "\x1b8").ok();
+        let status = format!("{}/{}", if filtered_candidates.is_empty() { 0 } else { selected_idx + 1 }, filtered_candidates.len());
+        write!(stdout, "\r\n\x1b[K\x1b[2m> \x1b[0m{}  \x1b[2m{}\x1b[0m", truncate_to_width(&query, terminal_size.columns.saturating_sub(status.len() + 4)), status).ok();
+        for row in 0..visible_rows {
+            write!(stdout, "\r\n\x1b[K").ok();
+            let idx = scroll_offset + row;
+            let Some(candidate) = filtered_candidates.get(idx) else {
+                continue;
+            };
+            let text = truncate_to_width(candidate, terminal_size.columns.saturating_sub(3));
+            if idx == selected_idx {
+                write!(stdout, "\x1b[7m {} \x1b[0m", text).ok();
+            } else {
+                write!(stdout, " {}", text).ok();
+            }
+        }
+        write!(stdout, "\x1b8").ok();
+        stdout.flush().ok();
+
+        match read_key() {
+            Some(MenuKey::Up) => {
+                if false == filtered_candidates.is_empty() {
+                    selected_idx = (selected_idx + filtered_candidates.len() - 1) % filtered_candidates.len();
+                }
+            }
+            Some(MenuKey::Down | MenuKey::Tab) => {
+                if false == filtered_candidates.is_empty() {
+                    selected_idx = (selected_idx + 1) % filtered_candidates.len();
+                }
+            }
+            Some(MenuKey::Enter) => {
+                if let Some(candidate) = filtered_candidates.get(selected_idx) {
+                    break Some(candidate.to_owned());
+                }
+            }
+            Some(MenuKey::Cancel) | None => break None,
+            Some(MenuKey::Backspace) => {
+                query.pop();
+                filtered_candidates = rank_fuzzy_candidates(&query, candidates.to_vec(), &get_match_text);
+                selected_idx = 0;
+            }
+            Some(MenuKey::Char(char)) => {
+                query.push(char);
+                filtered_candidates = rank_fuzzy_candidates(&query, candidates.to_vec(), &get_match_text);
+                selected_idx = 0;
+            }
+            Some(MenuKey::Control(_) | MenuKey::Other) => {}
+        }
+    };
+
+    // menu 지우고 prompt 위치로
+    write!(stdout, "\x1b8\r\n\x1b[J\x1b8").ok();
+    stdout.flush().ok();
+
+    selected
+}
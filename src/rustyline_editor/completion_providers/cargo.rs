@@ -0,0 +1,291 @@
+// cargo completion
+//   cargo <subcommand>              : 기본 subcommand + PATH 의 cargo-* + Cargo.toml 과 같은 위치의 .cargo/config.toml alias
+//   --bin, --example, --test, --bench : Cargo.toml 의 target 과 src/bin, examples, tests, benches 의 파일
+//   --features, -F                  : [features] 의 key (a,b 처럼 , 뒤에 이어서 입력 가능)
+//   -p, --package                   : package 이름과 workspace member
+//   --option                        : cargo <subcommand> --help 의 option
+
+use std::{env, fs, path::{Path, PathBuf}};
+
+use crate::rustyline_editor::completion::CompletionWord;
+use crate::rustyline_editor::completion_providers::{CompletionProvider, filter_prefix, help_options::get_help_options};
+
+// (subcommand, 설명)
+const CARGO_SUBCOMMANDS: [(&str, &str); 33] = [
+    ("add", "Add dependencies to a Cargo.toml manifest file"),
+    ("bench", "Execute all benchmarks of a local package"),
+    ("build", "Compile a local package and all of its dependencies"),
+    ("check", "Check a local package and all of its dependencies for errors"),
+    ("clean", "Remove artifacts that cargo has generated in the past"),
+    ("clippy", "Checks a package to catch common mistakes"),
+    ("config", "Inspect configuration values"),
+    ("doc", "Build a package's documentation"),
+    ("fetch", "Fetch dependencies of a package from the network"),
+    ("fix", "Automatically fix lint warnings reported by rustc"),
+    ("fmt", "Formats all bin and lib files of the current crate"),
+    ("generate-lockfile", "Generate the lockfile for a package"),
+    ("help", "Displays help for a cargo subcommand"),
+    ("init", "Create a new cargo package in an existing directory"),
+    ("install", "Install a Rust binary"),
+    ("locate-project", "Print a JSON representation of a Cargo.toml file's location"),
+    ("login", "Log in to a registry"),
+    ("logout", "Remove an API token from the registry locally"),
+    ("metadata", "Output the resolved dependencies of a package"),
+    ("new", "Create a new cargo package"),
+    ("owner", "Manage the owners of a crate on the registry"),
+    ("package", "Assemble the local package into a distributable tarball"),
+    ("pkgid", "Print a fully qualified package specification"),
+    ("publish", "Upload a package to the registry"),
+    ("remove", "Remove dependencies from a Cargo.toml manifest file"),
+    ("report", "Generate and display various kinds of reports"),
+    ("run", "Run a binary or example of the local package"),
+    ("rustc", "Compile a package, and pass extra options to the compiler"),
+    ("rustdoc", "Build a package's documentation, using specified custom flags"),
+    ("search", "Search packages in the registry"),
+    ("test", "Execute all unit and integration tests of a local package"),
+    ("tree", "Display a tree visualization of a dependency graph"),
+    ("update", "Update dependencies as recorded in the local lock file"),
+];
+
+#[derive(Debug)]
+pub struct CargoProvider;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct CargoManifest {
+    pub package_name: Option<String>,
+    pub bins: Vec<String>,
+    pub examples: Vec<String>,
+    pub tests: Vec<String>,
+    pub benches: Vec<String>,
+    pub features: Vec<String>,
+    pub workspace_members: Vec<String>,
+}
+
+impl CompletionProvider for CargoProvider {
+    fn command_name(&self) -> &'static str {
+        "cargo"
+    }
+
+    fn get_candidates(&self, completion_word: &CompletionWord) -> Option<Vec<String>> {
+        let word = completion_word.text.as_str();
+        let args = &completion_word.previous_words[1..];
+
+        // cargo run -- 뒤는 실행할 program 의 인자
+        if args.iter().any(|arg| arg == "--") {
+            return None;
+        }
+
+        // +nightly 같은 toolchain 은 건너뜀
+        let subcommand = args.iter().find(|arg| false == arg.starts_with('-') && false == arg.starts_with('+'));
+        let Some(subcommand) = subcommand else {
+            if word.starts_with('-') {
+                return Some(get_help_options("cargo", &["--help"], word));
+            }
+            return Some(filter_prefix(get_subcommands(), word));
+        };
+
+        let previous_word = args.last().map(|word| word.as_str()).unwrap_or("");
+        let target_kind = match previous_word {
+            "--bin" | "--example" | "--test" | "--bench" | "--features" | "-F" | "-p" | "--package" => Some(previous_word),
+            _ => None,
+        };
+        if let Some(target_kind) = target_kind {
+            let manifest_path = find_cargo_manifest()?;
+            let manifest = read_cargo_manifest(&manifest_path);
+
+            let candidates = match target_kind {
+                "--bin" => manifest.bins,
+                "--example" => manifest.examples,
+                "--test" => manifest.tests,
+                "--bench" => manifest.benches,
+                "-p" | "--package" => manifest.package_name.into_iter().chain(manifest.workspace_members).collect(),
+                // 이미 입력한 feature 뒤에 이어서
+                _ => {
+                    let (selected, feature_prefix) = match word.rfind(',') {
+                        Some(comma_idx) => (&word[..comma_idx + 1], &word[comma_idx + 1..]),
+                        None => ("", word),
+                    };
+                    let features = filter_prefix(manifest.features, feature_prefix);
+                    return Some(features.into_iter().map(|feature| format!("{}{}", selected, feature)).collect());
+                }
+            };
+            return Some(filter_prefix(candidates, word));
+        }
+
+        if word.starts_with('-') {
+            return Some(get_help_options("cargo", &[subcommand.as_str(), "--help"], word));
+        }
+
+        match subcommand.as_str() {
+            "help" => Some(filter_prefix(get_subcommands(), word)),
+            _ => None,
+        }
+    }
+}
+
+fn get_subcommands() -> Vec<String> {
+    let mut subcommands: Vec<String> = CARGO_SUBCOMMANDS.iter().map(|(subcommand, description)| format!("{}\t{}", subcommand, description)).collect();
+
+    // cargo-expand 같은 외부 subcommand
+    subcommands.extend(crate::get_all_executable_command().into_iter().filter_map(|command| command.strip_prefix("cargo-").map(|name| format!("{}\texternal subcommand", name))));
+
+    if let Some(manifest_path) = find_cargo_manifest()
+        && let Some(manifest_dir) = manifest_path.parent() {
+        subcommands.extend(read_cargo_aliases(&manifest_dir.join(".cargo").join("config.toml")).into_iter().map(|alias| format!("{}\talias", alias)));
+    }
+
+    subcommands
+}
+
+// 현재 디렉토리부터 위로 올라가면서 Cargo.toml 찾기
+pub fn find_cargo_manifest() -> Option<PathBuf> {
+    let current_dir = env::current_dir().ok()?;
+    current_dir.ancestors().map(|dir| dir.join("Cargo.toml")).find(|path| path.is_file())
+}
+
+pub fn read_cargo_manifest(manifest_path: &Path) -> CargoManifest {
+    let contents = fs::read_to_string(manifest_path).unwrap_or_default();
+    let mut manifest = parse_cargo_manifest(&contents);
+
+    let Some(manifest_dir) = manifest_path.parent() else {
+        return manifest;
+    };
+
+    // Cargo 가 자동으로 찾는 target
+    if manifest_dir.join("src").join("main.rs").is_file()
+        && let Some(package_name) = &manifest.package_name {
+        manifest.bins.push(package_name.to_owned());
+    }
+    manifest.bins.extend(get_target_names(&manifest_dir.join("src").join("bin")));
+    manifest.examples.extend(get_target_names(&manifest_dir.join("examples")));
+    manifest.tests.extend(get_target_names(&manifest_dir.join("tests")));
+    manifest.benches.extend(get_target_names(&manifest_dir.join("benches")));
+
+    // member 디렉토리의 Cargo.toml 에서 package 이름
+    let mut member_names = vec![];
+    for member in &manifest.workspace_members {
+        let member_dirs: Vec<PathBuf> = match member.strip_suffix("/*") {
+            Some(parent) => fs::read_dir(manifest_dir.join(parent))
+                .map(|read_dir| read_dir.flatten().map(|dir_entry| dir_entry.path()).collect())
+                .unwrap_or_default(),
+            None => vec![manifest_dir.join(member)],
+        };
+
+        for member_dir in member_dirs {
+            if let Ok(member_contents) = fs::read_to_string(member_dir.join("Cargo.toml"))
+                && let Some(package_name) = parse_cargo_manifest(&member_contents).package_name {
+                member_names.push(package_name);
+            }
+        }
+    }
+    manifest.workspace_members = member_names;
+
+    manifest
+}
+
+// dir/name.rs 와 dir/name/main.rs
+fn get_target_names(dir: &Path) -> Vec<String> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut target_names = vec![];
+    for dir_entry in read_dir.flatten() {
+        let path = dir_entry.path();
+        let Some(file_name) = path.file_name().and_then(|file_name| file_name.to_str()) else {
+            continue;
+        };
+
+        if let Some(target_name) = file_name.strip_suffix(".rs")
+            && path.is_file() {
+            target_names.push(target_name.to_string());
+        } else if path.join("main.rs").is_file() {
+            target_names.push(file_name.to_string());
+        }
+    }
+
+    target_names
+}
+
+// toml 전체를 해석하지 않고 completion 에 필요한 부분만
+pub fn parse_cargo_manifest(contents: &str) -> CargoManifest {
+    let mut manifest = CargoManifest::default();
+    let mut section = String::new();
+    let mut is_members_array = false;
+
+    for line in contents.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        // members = [ 가 여러 줄에 걸쳐 있는 경우
+        if is_members_array {
+            manifest.workspace_members.extend(parse_quoted_strings(line));
+            if line.contains(']') {
+                is_members_array = false;
+            }
+            continue;
+        }
+
+        if line.starts_with('[') {
+            section = line.trim_matches(|c| c == '[' || c == ']').trim().to_string();
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().trim_matches('"');
+        let value = value.trim();
+
+        match section.as_str() {
+            "package" if key == "name" => manifest.package_name = parse_quoted_strings(value).into_iter().next(),
+            "bin" | "example" | "test" | "bench" if key == "name" => {
+                let Some(name) = parse_quoted_strings(value).into_iter().next() else {
+                    continue;
+                };
+                match section.as_str() {
+                    "bin" => manifest.bins.push(name),
+                    "example" => manifest.examples.push(name),
+                    "test" => manifest.tests.push(name),
+                    _ => manifest.benches.push(name),
+                }
+            }
+            "features" => manifest.features.push(key.to_string()),
+            "workspace" if key == "members" => {
+                manifest.workspace_members.extend(parse_quoted_strings(value));
+                is_members_array = false == value.contains(']');
+            }
+            _ => {}
+        }
+    }
+
+    manifest
+}
+
+// .cargo/config.toml 의 [alias] key
+fn read_cargo_aliases(config_path: &Path) -> Vec<String> {
+    let Ok(contents) = fs::read_to_string(config_path) else {
+        return vec![];
+    };
+
+    let mut aliases = vec![];
+    let mut is_alias_section = false;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            is_alias_section = line == "[alias]";
+            continue;
+        }
+        if is_alias_section && let Some((key, _)) = line.split_once('=') {
+            aliases.push(key.trim().to_string());
+        }
+    }
+
+    aliases
+}
+
+fn parse_quoted_strings(value: &str) -> Vec<String> {
+    value.split('"').skip(1).step_by(2).map(|quoted| quoted.to_string()).collect()
+}
@@ -0,0 +1,265 @@
+// git completion, 모든 정보는 .git 디렉토리에서 직접 읽음 (네트워크 사용 안함)
+//   git <subcommand>            : 기본 subcommand + .git/config, ~/.gitconfig 의 alias
+//   checkout, merge, log ...    : branch, tag, remote branch
+//   switch, branch              : local branch
+//   push, pull, fetch           : 첫번째 인자는 remote, 그 뒤는 local branch
+//   remote <subcommand> <name>  : remote 이름
+//   --option                    : git <subcommand> -h 의 option
+
+use std::{env, fs, path::{Path, PathBuf}};
+
+use crate::rustyline_editor::completion::CompletionWord;
+use crate::rustyline_editor::completion_providers::{CompletionProvider, filter_prefix, help_options::get_help_options};
+use crate::shell_state::variables::get_variable;
+
+// (subcommand, 설명)
+const GIT_SUBCOMMANDS: [(&str, &str); 36] = [
+    ("add", "Add file contents to the index"),
+    ("am", "Apply a series of patches from a mailbox"),
+    ("bisect", "Use binary search to find the commit that introduced a bug"),
+    ("blame", "Show what revision and author last modified each line of a file"),
+    ("branch", "List, create, or delete branches"),
+    ("checkout", "Switch branches or restore working tree files"),
+    ("cherry-pick", "Apply the changes introduced by some existing commits"),
+    ("clean", "Remove untracked files from the working tree"),
+    ("clone", "Clone a repository into a new directory"),
+    ("commit", "Record changes to the repository"),
+    ("config", "Get and set repository or global options"),
+    ("describe", "Give an object a human readable name based on an available ref"),
+    ("diff", "Show changes between commits, commit and working tree, etc"),
+    ("fetch", "Download objects and refs from another repository"),
+    ("grep", "Print lines matching a pattern"),
+    ("init", "Create an empty Git repository or reinitialize an existing one"),
+    ("log", "Show commit logs"),
+    ("merge", "Join two or more development histories together"),
+    ("mv", "Move or rename a file, a directory, or a symlink"),
+    ("notes", "Add or inspect object notes"),
+    ("pull", "Fetch from and integrate with another repository or a local branch"),
+    ("push", "Update remote refs along with associated objects"),
+    ("rebase", "Reapply commits on top of another base tip"),
+    ("reflog", "Manage reflog information"),
+    ("remote", "Manage set of tracked repositories"),
+    ("reset", "Reset current HEAD to the specified state"),
+    ("restore", "Restore working tree files"),
+    ("revert", "Revert some existing commits"),
+    ("rm", "Remove files from the working tree and from the index"),
+    ("show", "Show various types of objects"),
+    ("stash", "Stash the changes in a dirty working directory away"),
+    ("status", "Show the working tree status"),
+    ("submodule", "Initialize, update or inspect submodules"),
+    ("switch", "Switch branches"),
+    ("tag", "Create, list, delete or verify a tag object"),
+    ("worktree", "Manage multiple working trees"),
+];
+const GIT_REMOTE_SUBCOMMANDS: [&str; 7] = ["add", "get-url", "prune", "remove", "rename", "set-url", "show"];
+
+#[derive(Debug)]
+pub struct GitProvider;
+
+impl CompletionProvider for GitProvider {
+    fn command_name(&self) -> &'static str {
+        "git"
+    }
+
+    fn get_candidates(&self, completion_word: &CompletionWord) -> Option<Vec<String>> {
+        let word = completion_word.text.as_str();
+        let args = &completion_word.previous_words[1..];
+
+        // git -C dir, git -c key=value 의 값은 subcommand 가 아님
+        let mut subcommand_idx = None;
+        let mut idx = 0;
+        while idx < args.len() {
+            match args[idx].as_str() {
+                "-C" | "-c" | "--git-dir" | "--work-tree" => idx += 2,
+                arg if arg.starts_with('-') => idx += 1,
+                _ => {
+                    subcommand_idx = Some(idx);
+                    break;
+                }
+            }
+        }
+
+        // -C 뒤는 path
+        if args.last().is_some_and(|arg| arg == "-C") {
+            return None;
+        }
+
+        let Some(subcommand_idx) = subcommand_idx else {
+            if word.starts_with('-') {
+                return Some(get_help_options("git", &["--help"], word));
+            }
+            return Some(filter_prefix(get_subcommands(), word));
+        };
+
+        let subcommand = args[subcommand_idx].as_str();
+        // subcommand 뒤의 option 이 아닌 인자들
+        let operands: Vec<&String> = args[subcommand_idx + 1..].iter().filter(|arg| false == arg.starts_with('-')).collect();
+
+        if word.starts_with('-') {
+            return Some(get_help_options("git", &[subcommand, "-h"], word));
+        }
+
+        // add, rm, restore 처럼 path 를 받는 subcommand 는 기본 completion 으로
+        let git_dir = find_git_dir()?;
+        let candidates = match subcommand {
+            "help" => get_subcommands(),
+            "switch" | "branch" => get_local_branches(&git_dir),
+            "checkout" | "merge" | "rebase" | "log" | "diff" | "show" | "reset" | "cherry-pick" | "revert" | "describe" | "bisect" | "format-patch" => {
+                let mut refs = get_local_branches(&git_dir);
+                refs.extend(get_tags(&git_dir));
+                refs.extend(get_remote_branches(&git_dir));
+                refs
+            }
+            "tag" => get_tags(&git_dir),
+            "push" | "pull" | "fetch" => {
+                if operands.is_empty() {
+                    get_remotes(&git_dir)
+                } else {
+                    get_local_branches(&git_dir)
+                }
+            }
+            "remote" => match operands.first().map(|operand| operand.as_str()) {
+                None => GIT_REMOTE_SUBCOMMANDS.iter().map(|subcommand| subcommand.to_string()).collect(),
+                Some("add") => return None,
+                Some(_) if operands.len() == 1 => get_remotes(&git_dir),
+                Some(_) => vec![],
+            },
+            _ => return None,
+        };
+
+        Some(filter_prefix(candidates, word))
+    }
+}
+
+fn get_subcommands() -> Vec<String> {
+    let mut subcommands: Vec<String> = GIT_SUBCOMMANDS.iter().map(|(subcommand, description)| format!("{}\t{}", subcommand, description)).collect();
+
+    let mut aliases = vec![];
+    if let Some(home) = get_variable("HOME") {
+        aliases.extend(get_config_section_keys(&Path::new(&home).join(".gitconfig"), "alias"));
+    }
+    if let Some(git_dir) = find_git_dir() {
+        aliases.extend(get_config_section_keys(&get_common_dir(&git_dir).join("config"), "alias"));
+    }
+    subcommands.extend(aliases.into_iter().map(|alias| format!("{}\talias", alias)));
+
+    subcommands
+}
+
+// 현재 디렉토리부터 위로 올라가면서 .git 찾기, worktree 와 submodule 은 .git 파일에 "gitdir: path"
+pub fn find_git_dir() -> Option<PathBuf> {
+    let current_dir = env::current_dir().ok()?;
+
+    for dir in current_dir.ancestors() {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Some(dot_git);
+        }
+        if dot_git.is_file() {
+            let contents = fs::read_to_string(&dot_git).ok()?;
+            let git_dir = contents.trim().strip_prefix("gitdir:")?.trim();
+            return Some(dir.join(git_dir));
+        }
+    }
+
+    None
+}
+
+// worktree 의 refs 와 config 는 commondir 에 있음
+fn get_common_dir(git_dir: &Path) -> PathBuf {
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common_dir) => git_dir.join(common_dir.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+pub fn get_local_branches(git_dir: &Path) -> Vec<String> {
+    get_refs(git_dir, "refs/heads/")
+}
+
+pub fn get_tags(git_dir: &Path) -> Vec<String> {
+    get_refs(git_dir, "refs/tags/")
+}
+
+pub fn get_remote_branches(git_dir: &Path) -> Vec<String> {
+    get_refs(git_dir, "refs/remotes/").into_iter().filter(|remote_branch| false == remote_branch.ends_with("/HEAD")).collect()
+}
+
+pub fn get_remotes(git_dir: &Path) -> Vec<String> {
+    let config_path = get_common_dir(git_dir).join("config");
+    let Ok(contents) = fs::read_to_string(config_path) else {
+        return vec![];
+    };
+
+    // [remote "origin"]
+    contents
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("[remote \"")?.strip_suffix("\"]").map(|remote| remote.to_string()))
+        .collect()
+}
+
+// refs 디렉토리의 loose ref 와 packed-refs 에서 prefix 뒤쪽 이름
+fn get_refs(git_dir: &Path, prefix: &str) -> Vec<String> {
+    let common_dir = get_common_dir(git_dir);
+    let mut refs = vec![];
+
+    collect_loose_refs(&common_dir.join(prefix), "", &mut refs);
+
+    if let Ok(packed_refs) = fs::read_to_string(common_dir.join("packed-refs")) {
+        for line in packed_refs.lines() {
+            // # 주석과 ^ 로 시작하는 peeled tag 는 제외
+            let Some((_, ref_name)) = line.split_once(' ') else {
+                continue;
+            };
+            if let Some(name) = ref_name.strip_prefix(prefix) {
+                refs.push(name.to_string());
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+// feature/login 처럼 / 가 들어간 branch 는 하위 디렉토리
+fn collect_loose_refs(dir: &Path, name_prefix: &str, refs: &mut Vec<String>) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for dir_entry in read_dir.flatten() {
+        let Some(file_name) = dir_entry.file_name().to_str().map(|name| name.to_string()) else {
+            continue;
+        };
+        let name = format!("{}{}", name_prefix, file_name);
+
+        if dir_entry.path().is_dir() {
+            collect_loose_refs(&dir_entry.path(), &format!("{}/", name), refs);
+        } else {
+            refs.push(name);
+        }
+    }
+}
+
+// [section] 아래의 key 이름
+fn get_config_section_keys(config_path: &Path, section: &str) -> Vec<String> {
+    let Ok(contents) = fs::read_to_string(config_path) else {
+        return vec![];
+    };
+
+    let mut keys = vec![];
+    let mut is_section = false;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            is_section = line.trim_matches(|c| c == '[' || c == ']').trim() == section;
+            continue;
+        }
+        if is_section && let Some((key, _)) = line.split_once('=') {
+            keys.push(key.trim().to_string());
+        }
+    }
+
+    keys
+}
@@ -0,0 +1,115 @@
+// command 의 --help 출력에서 --long-option 을 찾아 completion 후보로 사용 (같은 줄에 설명이 있으면 "option\t설명")
+// 같은 command 에 대해 매번 실행하지 않도록 결과를 cache 하고, 멈춰 있는 command 는 시간 제한 후 종료
+
+use std::{collections::BTreeMap, io::Read, process::{Command, Stdio}, sync::Mutex, thread, time::{Duration, Instant}};
+
+use regex::Regex;
+
+use crate::rustyline_editor::completion_providers::filter_prefix;
+
+const HELP_TIMEOUT: Duration = Duration::from_secs(2);
+
+// "command arg..." -> option 목록
+static HELP_OPTIONS_CACHE: Mutex<BTreeMap<String, Vec<String>>> = Mutex::new(BTreeMap::new());
+
+// --name, --name=VALUE, --name[=VALUE] 형태 (값을 받는 option 은 = 까지 후보로)
+// "  -a, --all     do not ignore entries" 처럼 option 뒤에 공백 두 칸 이상을 두고 나오는 문장을 설명으로
+pub fn parse_help_options(help_output: &str) -> Vec<String> {
+    let option_regex = Regex::new(r"(?:^|[\s,\[(])(--[A-Za-z0-9][A-Za-z0-9_-]*)(\[?=)?").unwrap();
+    let description_regex = Regex::new(r"^\s*-.*?\S\s{2,}(\S.*)$").unwrap();
+
+    let mut options: Vec<String> = vec![];
+    for line in help_output.lines() {
+        // 설명 앞쪽의 option 만
+        let (option_part, description) = match description_regex.captures(line) {
+            Some(captures) => {
+                let description_start = captures.get(1).map(|description| description.start()).unwrap_or(line.len());
+                (&line[..description_start], captures.get(1).map(|description| description.as_str().trim()))
+            }
+            None => (line, None),
+        };
+
+        for captures in option_regex.captures_iter(option_part) {
+            let mut option = captures[1].to_string();
+            if captures.get(2).is_some_and(|assign| assign.as_str() == "=") {
+                option.push('=');
+            }
+            if let Some(description) = description {
+                option = format!("{}\t{}", option, description);
+            }
+            options.push(option);
+        }
+    }
+
+    // 같은 option 이 여러번 나오면 설명이 있는 것
+    options.sort_by(|a, b| get_option_name(a).cmp(get_option_name(b)).then(b.contains('\t').cmp(&a.contains('\t'))));
+    options.dedup_by(|a, b| get_option_name(a) == get_option_name(b));
+    options
+}
+
+fn get_option_name(option: &str) -> &str {
+    option.split('\t').next().unwrap_or(option)
+}
+
+pub fn get_help_options(command: &str, args: &[&str], word: &str) -> Vec<String> {
+    let cache_key = format!("{} {}", command, args.join(" "));
+
+    let cached_options = HELP_OPTIONS_CACHE.lock().unwrap().get(&cache_key).cloned();
+    let options = match cached_options {
+        Some(options) => options,
+        None => {
+            let options = run_command_with_timeout(command, args).map(|help_output| parse_help_options(&help_output)).unwrap_or_default();
+            HELP_OPTIONS_CACHE.lock().unwrap().insert(cache_key, options.to_owned());
+            options
+        }
+    };
+
+    filter_prefix(options, word)
+}
+
+// 멈춰 있는 command 는 HELP_TIMEOUT 후 종료, stdout 과 stderr 를 합쳐서 반환
+pub fn run_command_with_timeout(command: &str, args: &[&str]) -> Option<String> {
+    let mut child = Command::new(command)
+        .args(args)
+        // git help 처럼 pager 를 띄우지 않게
+        .env("PAGER", "cat")
+        .env("MANPAGER", "cat")
+        .env("GIT_PAGER", "cat")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    // 출력이 pipe buffer 보다 크면 child 가 멈추기 때문에 읽기는 별도 thread 에서
+    let mut stdout = child.stdout.take()?;
+    let mut stderr = child.stderr.take()?;
+    let stdout_reader = thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).ok();
+        output
+    });
+    let stderr_reader = thread::spawn(move || {
+        let mut output = String::new();
+        stderr.read_to_string(&mut output).ok();
+        output
+    });
+
+    let started_at = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if started_at.elapsed() < HELP_TIMEOUT => thread::sleep(Duration::from_millis(10)),
+            _ => {
+                child.kill().ok();
+                child.wait().ok();
+                break;
+            }
+        }
+    }
+
+    // usage 를 stderr 로 출력하는 command 도 있음
+    let mut help_output = stdout_reader.join().unwrap_or_default();
+    help_output.push_str(&stderr_reader.join().unwrap_or_default());
+    Some(help_output)
+}
@@ -0,0 +1,108 @@
+// make completion
+//   make <target>     : Makefile 의 target (-C dir, -f file 을 반영)
+//   make --option     : make --help 의 option
+
+use std::{fs, path::PathBuf};
+
+use crate::rustyline_editor::completion::CompletionWord;
+use crate::rustyline_editor::completion_providers::{CompletionProvider, filter_prefix, help_options::get_help_options};
+
+const MAKEFILE_NAMES: [&str; 3] = ["GNUmakefile", "makefile", "Makefile"];
+
+#[derive(Debug)]
+pub struct MakeProvider;
+
+impl CompletionProvider for MakeProvider {
+    fn command_name(&self) -> &'static str {
+        "make"
+    }
+
+    fn get_candidates(&self, completion_word: &CompletionWord) -> Option<Vec<String>> {
+        let word = completion_word.text.as_str();
+        let previous_word = completion_word.previous_words.last().map(|word| word.as_str()).unwrap_or("");
+
+        // -C, -f 뒤는 path
+        if matches!(previous_word, "-C" | "-f" | "--directory" | "--file" | "--makefile" | "-I" | "--include-dir") {
+            return None;
+        }
+
+        if word.starts_with('-') {
+            return Some(get_help_options("make", &["--help"], word));
+        }
+
+        let makefile_path = find_makefile(&completion_word.previous_words)?;
+        let contents = fs::read_to_string(makefile_path).ok()?;
+
+        Some(filter_prefix(parse_makefile_targets(&contents), word))
+    }
+}
+
+fn find_makefile(previous_words: &[String]) -> Option<PathBuf> {
+    let mut directory = PathBuf::from(".");
+    let mut makefile: Option<String> = None;
+
+    let mut words = previous_words.iter().skip(1);
+    while let Some(word) = words.next() {
+        match word.as_str() {
+            "-C" | "--directory" => directory = directory.join(words.next()?),
+            "-f" | "--file" | "--makefile" => makefile = words.next().cloned(),
+            _ => {
+                if let Some(value) = word.strip_prefix("--directory=") {
+                    directory = directory.join(value);
+                } else if let Some(value) = word.strip_prefix("--file=").or_else(|| word.strip_prefix("--makefile=")) {
+                    makefile = Some(value.to_string());
+                }
+            }
+        }
+    }
+
+    if let Some(makefile) = makefile {
+        return Some(directory.join(makefile));
+    }
+
+    MAKEFILE_NAMES.iter().map(|name| directory.join(name)).find(|path| path.is_file())
+}
+
+// "target1 target2: deps" 형태의 rule 에서 target 이름
+// .PHONY 같은 특수 target, %.o 같은 pattern rule, $(VAR) 를 쓰는 target, 변수 대입(:=, ::=) 은 제외
+pub fn parse_makefile_targets(contents: &str) -> Vec<String> {
+    let mut targets = vec![];
+    let mut is_define_block = false;
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("define ") || trimmed == "define" {
+            is_define_block = true;
+            continue;
+        }
+        if is_define_block {
+            if trimmed == "endef" {
+                is_define_block = false;
+            }
+            continue;
+        }
+
+        // recipe 와 주석
+        if line.starts_with('\t') || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let Some(colon_idx) = line.find(':') else {
+            continue;
+        };
+        let target_part = &line[..colon_idx];
+        let rest = &line[colon_idx + 1..];
+        if target_part.contains('=') || rest.starts_with('=') || rest.starts_with(":=") {
+            continue;
+        }
+
+        for target in target_part.split_whitespace() {
+            if target.starts_with('.') || target.contains('%') || target.contains('$') {
+                continue;
+            }
+            targets.push(target.to_string());
+        }
+    }
+
+    targets
+}
@@ -0,0 +1,36 @@
+// 자주 쓰는 command 의 subcommand, option, 인자 completion
+// complete builtin 으로 등록한 규칙이 없을 때 command 이름으로 provider 를 찾는다
+// provider 가 None 을 반환하면 기본 completion (--option 은 --help 파싱, 그 외에는 path) 으로 넘어감
+
+pub mod cargo;
+pub mod git;
+pub mod help_options;
+pub mod make;
+pub mod z;
+
+use std::fmt::Debug;
+
+use crate::rustyline_editor::completion::CompletionWord;
+
+pub trait CompletionProvider: Debug + Send + Sync {
+    fn command_name(&self) -> &'static str;
+    fn get_candidates(&self, completion_word: &CompletionWord) -> Option<Vec<String>>;
+}
+
+pub fn get_bundled_providers() -> Vec<Box<dyn CompletionProvider>> {
+    vec![
+        Box::new(cargo::CargoProvider),
+        Box::new(git::GitProvider),
+        Box::new(make::MakeProvider),
+        Box::new(z::ZProvider { command_name: "z" }),
+        Box::new(z::ZProvider { command_name: "j" }),
+    ]
+}
+
+// 후보 중 현재 단어로 시작하는 것만 정렬해서
+pub fn filter_prefix(candidates: impl IntoIterator<Item = String>, word: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = candidates.into_iter().filter(|candidate| candidate.starts_with(word)).collect();
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
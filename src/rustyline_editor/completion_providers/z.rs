@@ -0,0 +1,48 @@
+// z, j completion
+//   z <keyword>  : 지금까지 입력한 keyword 에 맞는 디렉토리를 점수가 높은 순으로 (선택하면 단어 전체가 경로로 바뀜)
+//   z -option    : z 의 option
+// / . ~ 로 시작하는 단어는 기본 path completion
+
+use crate::rustyline_editor::completion::{CompletionWord, is_path_word};
+use crate::rustyline_editor::completion_providers::{CompletionProvider, filter_prefix};
+use crate::shell_state::directory_frecency::{FrecencySort, find_matching_directories};
+
+const Z_OPTIONS: [&str; 5] = ["-e", "-l", "-r", "-t", "-x"];
+
+#[derive(Debug)]
+pub struct ZProvider {
+    // z 와 j 모두 등록
+    pub command_name: &'static str,
+}
+
+impl CompletionProvider for ZProvider {
+    fn command_name(&self) -> &'static str {
+        self.command_name
+    }
+
+    fn get_candidates(&self, completion_word: &CompletionWord) -> Option<Vec<String>> {
+        let word = completion_word.text.as_str();
+        if is_path_word(word) || word.starts_with('.') {
+            return None;
+        }
+        if word.starts_with('-') {
+            return Some(filter_prefix(Z_OPTIONS.iter().map(|option| option.to_string()), word));
+        }
+
+        // 앞에서 입력한 keyword 도 같이 매칭
+        let mut keywords: Vec<String> = completion_word.previous_words.iter().skip(1).filter(|word| false == word.starts_with('-')).cloned().collect();
+        keywords.push(word.to_string());
+        let frecency_sort = match completion_word.previous_words.iter().find(|word| word.starts_with('-')) {
+            Some(option) if option.contains('r') => FrecencySort::Rank,
+            Some(option) if option.contains('t') => FrecencySort::Recent,
+            _ => FrecencySort::Frecency,
+        };
+
+        // 목록에는 마지막 요소만 보이므로 설명에 전체 경로
+        let candidates = find_matching_directories(&keywords, frecency_sort)
+            .into_iter()
+            .map(|(record, _)| format!("{}/\t{}", record.path, record.path))
+            .collect();
+        Some(candidates)
+    }
+}
@@ -0,0 +1,86 @@
+// fuzzy completion 용 subsequence 매칭과 점수
+//   query 의 문자가 후보에 순서대로 모두 있으면 매칭
+//   연속으로 매칭되거나, 단어 시작(/ - _ . 뒤, 대문자)에서 매칭되거나, prefix 로 매칭되면 점수가 높음
+//   query 에 대문자가 있으면 대소문자 구분 (smart case)
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 24;
+const BONUS_WORD_START: i64 = 20;
+const BONUS_PREFIX: i64 = 48;
+const PENALTY_GAP: i64 = 2;
+const PENALTY_UNMATCHED: i64 = 1;
+
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let is_case_sensitive = query.chars().any(|c| c.is_uppercase());
+    let normalize = |c: char| if is_case_sensitive { c } else { c.to_ascii_lowercase() };
+
+    let query: Vec<char> = query.chars().map(normalize).collect();
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+
+    let mut score = 0;
+    let mut query_idx = 0;
+    let mut previous_match_idx: Option<usize> = None;
+
+    for (idx, candidate_char) in candidate_chars.iter().enumerate() {
+        if query_idx == query.len() {
+            break;
+        }
+        if normalize(*candidate_char) != query[query_idx] {
+            continue;
+        }
+
+        score += SCORE_MATCH;
+        match previous_match_idx {
+            Some(previous_idx) if previous_idx + 1 == idx => score += BONUS_CONSECUTIVE,
+            Some(previous_idx) => score -= PENALTY_GAP * (idx - previous_idx - 1) as i64,
+            None => {
+                if idx == 0 {
+                    score += BONUS_PREFIX;
+                } else {
+                    score -= PENALTY_GAP * idx as i64;
+                }
+            }
+        }
+        if is_word_start(&candidate_chars, idx) {
+            score += BONUS_WORD_START;
+        }
+
+        previous_match_idx = Some(idx);
+        query_idx += 1;
+    }
+
+    if query_idx < query.len() {
+        return None;
+    }
+
+    // 같은 조건이면 짧은 후보가 위로
+    score -= PENALTY_UNMATCHED * (candidate_chars.len() - query.len()) as i64;
+    Some(score)
+}
+
+fn is_word_start(chars: &[char], idx: usize) -> bool {
+    if idx == 0 {
+        return true;
+    }
+
+    let previous = chars[idx - 1];
+    matches!(previous, '/' | '-' | '_' | '.' | ' ') || (previous.is_lowercase() && chars[idx].is_uppercase())
+}
+
+// 점수가 높은 순서, 같으면 짧은 순서와 이름 순서
+pub fn rank_fuzzy_candidates(query: &str, candidates: Vec<String>, get_match_text: impl Fn(&str) -> &str) -> Vec<String> {
+    let mut scored: Vec<(i64, String)> = candidates
+        .into_iter()
+        .filter_map(|candidate| fuzzy_match(query, get_match_text(&candidate)).map(|score| (score, candidate)))
+        .collect();
+
+    scored.sort_by(|(score_a, candidate_a), (score_b, candidate_b)| {
+        score_b.cmp(score_a).then(candidate_a.len().cmp(&candidate_b.len())).then(candidate_a.cmp(candidate_b))
+    });
+
+    scored.into_iter().map(|(_, candidate)| candidate).collect()
+}
@@ -0,0 +1,287 @@
+// PS1 의 \g : git 저장소 안이면 branch 와 상태를 표시
+//   main*+ ↑1↓2
+//     *  : worktree 에 commit 되지 않은 변경 (index 와 다름)
+//     +  : staged 변경 (HEAD 와 index 가 다름)
+//     ↑N : upstream 보다 앞선 commit 개수, ↓N : upstream 에 있고 로컬에 없는 commit 개수
+//   (1a2b3c4)         : detached HEAD
+//   main|MERGING      : merge, rebase, cherry-pick 등 진행중
+// 큰 저장소에서 prompt 가 멈추지 않게 GIT_PROMPT_TIMEOUT 안에 끝나지 않은 검사는 ? 로 표시
+
+use std::{collections::{BTreeMap, BinaryHeap, HashMap}, env, fs, os::unix::fs::{MetadataExt, PermissionsExt}, path::Path, time::{Duration, Instant}};
+
+use crate::rustyline_editor::completion_providers::git::find_git_dir;
+use crate::rustyline_editor::git_repository::{GitRepository, IndexEntry, ObjectId, format_object_id, hash_blob, hash_blob_file};
+
+const GIT_PROMPT_TIMEOUT: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GitPromptStatus {
+    pub branch: Option<String>,
+    // detached HEAD 일 때 짧은 commit id
+    pub detached_commit: Option<String>,
+    pub operation: Option<&'static str>,
+    // None 이면 시간 안에 확인하지 못함
+    pub is_dirty: Option<bool>,
+    pub is_staged: Option<bool>,
+    pub ahead_behind: Option<(usize, usize)>,
+}
+
+pub fn get_git_prompt_segment() -> String {
+    let Some(git_dir) = find_git_dir() else {
+        return String::new();
+    };
+    // .git 디렉토리나 worktree 의 .git 파일이 있는 디렉토리
+    let Some(worktree_dir) = env::current_dir().ok().and_then(|current_dir| current_dir.ancestors().find(|dir| dir.join(".git").exists()).map(|dir| dir.to_path_buf())) else {
+        return String::new();
+    };
+
+    let deadline = Instant::now() + GIT_PROMPT_TIMEOUT;
+    let git_prompt_status = get_git_prompt_status(&GitRepository::open(&git_dir), &worktree_dir, deadline);
+    format_git_prompt_status(&git_prompt_status)
+}
+
+pub fn get_git_prompt_status(repository: &GitRepository, worktree_dir: &Path, deadline: Instant) -> GitPromptStatus {
+    let is_timed_out = || Instant::now() >= deadline;
+    let mut git_prompt_status = GitPromptStatus::default();
+
+    let head_ref = repository.read_head_ref();
+    let head_id = repository.resolve_head();
+    match &head_ref {
+        Some(head_ref) => git_prompt_status.branch = Some(head_ref.strip_prefix("refs/heads/").unwrap_or(head_ref).to_string()),
+        None => git_prompt_status.detached_commit = head_id.map(|head_id| format_object_id(&head_id)[..7].to_string()),
+    }
+    git_prompt_status.operation = get_operation(&repository.git_dir);
+
+    let index_entries = repository.read_index().unwrap_or_default();
+
+    // HEAD tree 와 index 비교, 아직 commit 이 없으면 index 에 무엇이든 있으면 staged
+    git_prompt_status.is_staged = match head_id.and_then(|head_id| repository.read_commit(&head_id)) {
+        Some(head_commit) => {
+            let mut head_entries = BTreeMap::new();
+            repository
+                .read_tree_recursive(&head_commit.tree, "", &mut head_entries, &is_timed_out)
+                .map(|_| is_index_different_from_tree(&index_entries, &head_entries))
+        }
+        None => Some(false == index_entries.is_empty()),
+    };
+
+    git_prompt_status.is_dirty = is_worktree_dirty(&index_entries, worktree_dir, &is_timed_out);
+
+    if let Some(head_ref) = &head_ref
+        && let Some(head_id) = head_id
+        && let Some(upstream_ref) = get_upstream_ref(&repository.read_config(), head_ref)
+        && let Some(upstream_id) = repository.resolve_ref(&upstream_ref) {
+        git_prompt_status.ahead_behind = count_ahead_behind(repository, head_id, upstream_id, &is_timed_out);
+    }
+
+    git_prompt_status
+}
+
+pub fn format_git_prompt_status(git_prompt_status: &GitPromptStatus) -> String {
+    let mut segment = match (&git_prompt_status.branch, &git_prompt_status.detached_commit) {
+        (Some(branch), _) => branch.to_owned(),
+        (None, Some(detached_commit)) => format!("({})", detached_commit),
+        (None, None) => return String::new(),
+    };
+
+    let get_flag = |state: Option<bool>, flag: &str| match state {
+        Some(true) => flag.to_string(),
+        Some(false) => String::new(),
+        None => "?".to_string(),
+    };
+    let dirty_flag = get_flag(git_prompt_status.is_dirty, "*");
+    let staged_flag = get_flag(git_prompt_status.is_staged, "+");
+    // 둘다 모르면 ? 한번만
+    if dirty_flag == "?" && staged_flag == "?" {
+        segment.push('?');
+    } else {
+        segment.push_str(&dirty_flag);
+        segment.push_str(&staged_flag);
+    }
+
+    if let Some(operation) = git_prompt_status.operation {
+        segment.push('|');
+        segment.push_str(operation);
+    }
+
+    match git_prompt_status.ahead_behind {
+        Some((0, 0)) | None => {}
+        Some((ahead, behind)) => {
+            segment.push(' ');
+            if ahead > 0 {
+                segment.push_str(&format!("↑{}", ahead));
+            }
+            if behind > 0 {
+                segment.push_str(&format!("↓{}", behind));
+            }
+        }
+    }
+
+    segment
+}
+
+fn get_operation(git_dir: &Path) -> Option<&'static str> {
+    if git_dir.join("rebase-merge").is_dir() || git_dir.join("rebase-apply").join("rebasing").is_file() {
+        return Some("REBASE");
+    }
+    if git_dir.join("rebase-apply").is_dir() {
+        return Some("AM");
+    }
+
+    let operations = [("MERGE_HEAD", "MERGING"), ("CHERRY_PICK_HEAD", "CHERRY-PICKING"), ("REVERT_HEAD", "REVERTING"), ("BISECT_LOG", "BISECTING")];
+    operations.iter().find(|(file_name, _)| git_dir.join(file_name).is_file()).map(|(_, operation)| *operation)
+}
+
+// [branch "main"] 의 remote, merge 로 upstream 의 remote tracking ref
+fn get_upstream_ref(config: &str, head_ref: &str) -> Option<String> {
+    let branch = head_ref.strip_prefix("refs/heads/")?;
+    let section = format!("[branch \"{}\"]", branch);
+
+    let mut is_branch_section = false;
+    let mut remote = None;
+    let mut merge = None;
+    for line in config.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            is_branch_section = line == section;
+            continue;
+        }
+        if false == is_branch_section {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            match key.trim() {
+                "remote" => remote = Some(value.trim().to_string()),
+                "merge" => merge = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    let merge_branch = merge?.strip_prefix("refs/heads/")?.to_string();
+    match remote?.as_str() {
+        // remote = . 는 로컬 branch 를 추적
+        "." => Some(format!("refs/heads/{}", merge_branch)),
+        remote => Some(format!("refs/remotes/{}/{}", remote, merge_branch)),
+    }
+}
+
+fn is_index_different_from_tree(index_entries: &[IndexEntry], tree_entries: &BTreeMap<String, (u32, ObjectId)>) -> bool {
+    // conflict 중인 entry 가 있으면 staged 로
+    if index_entries.iter().any(|index_entry| index_entry.stage != 0) {
+        return true;
+    }
+    if index_entries.len() != tree_entries.len() {
+        return true;
+    }
+
+    index_entries.iter().any(|index_entry| match tree_entries.get(&index_entry.path) {
+        Some((mode, object_id)) => *object_id != index_entry.object_id || *mode != index_entry.mode,
+        None => true,
+    })
+}
+
+// git status 처럼 stat 정보가 같으면 변경 없음, 다르면 내용의 hash 로 확인
+fn is_worktree_dirty(index_entries: &[IndexEntry], worktree_dir: &Path, is_timed_out: &dyn Fn() -> bool) -> Option<bool> {
+    for index_entry in index_entries {
+        if is_timed_out() {
+            return None;
+        }
+        // submodule(gitlink) 은 검사하지 않음
+        if index_entry.is_assumed_valid || index_entry.mode == 0o160000 {
+            continue;
+        }
+
+        let path = worktree_dir.join(&index_entry.path);
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            return Some(true);
+        };
+
+        // 크기가 다르면 내용을 읽지 않아도 변경
+        if metadata.size() as u32 != index_entry.size {
+            return Some(true);
+        }
+        if metadata.mtime() as u32 == index_entry.mtime_seconds
+            && metadata.mtime_nsec() as u32 == index_entry.mtime_nanoseconds {
+            continue;
+        }
+
+        let object_id = if metadata.file_type().is_symlink() {
+            fs::read_link(&path).map(|target| Some(hash_blob(target.to_string_lossy().as_bytes())))
+        } else {
+            hash_blob_file(&path, metadata.size(), is_timed_out)
+        };
+        match object_id {
+            Ok(Some(object_id)) if object_id == index_entry.object_id => {}
+            // 시간 안에 다 읽지 못함
+            Ok(None) => return None,
+            _ => return Some(true),
+        }
+
+        // 실행 권한만 바뀐 경우
+        let is_executable = metadata.permissions().mode() & 0o111 != 0;
+        if metadata.file_type().is_file() && is_executable != (index_entry.mode == 0o100755) {
+            return Some(true);
+        }
+    }
+
+    Some(false)
+}
+
+// commit 시간이 최근인 것부터 양쪽에서 거슬러 올라가면서 한쪽에서만 닿는 commit 개수
+fn count_ahead_behind(repository: &GitRepository, local_id: ObjectId, upstream_id: ObjectId, is_timed_out: &dyn Fn() -> bool) -> Option<(usize, usize)> {
+    const LOCAL: u8 = 1;
+    const UPSTREAM: u8 = 2;
+
+    let mut flags: HashMap<ObjectId, u8> = HashMap::new();
+    // 부모에게 전달한 flag, 나중에 다른쪽 flag 가 추가되면 다시 전달해야 함
+    let mut propagated_flags: HashMap<ObjectId, u8> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    let mut is_queued: HashMap<ObjectId, bool> = HashMap::new();
+
+    for (object_id, flag) in [(local_id, LOCAL), (upstream_id, UPSTREAM)] {
+        *flags.entry(object_id).or_insert(0) |= flag;
+        let commit = repository.read_commit(&object_id)?;
+        if false == is_queued.insert(object_id, true).unwrap_or(false) {
+            queue.push((commit.commit_time, object_id));
+        }
+    }
+
+    // 큐에 양쪽 모두에서 닿은 commit 만 남고, 그 flag 를 부모에게 전달할 필요가 없으면 끝
+    let is_remaining = |object_id: &ObjectId, flags: &HashMap<ObjectId, u8>, propagated_flags: &HashMap<ObjectId, u8>| {
+        flags[object_id] != LOCAL | UPSTREAM || propagated_flags.get(object_id).is_some_and(|propagated_flag| *propagated_flag != flags[object_id])
+    };
+    while queue.iter().any(|(_, object_id)| is_remaining(object_id, &flags, &propagated_flags)) {
+        if is_timed_out() {
+            return None;
+        }
+        let Some((_, object_id)) = queue.pop() else {
+            break;
+        };
+        is_queued.insert(object_id, false);
+
+        let flag = flags[&object_id];
+        propagated_flags.insert(object_id, flag);
+        let commit = repository.read_commit(&object_id)?;
+        for parent_id in commit.parents {
+            let parent_flag = flags.entry(parent_id).or_insert(0);
+            if *parent_flag | flag == *parent_flag {
+                continue;
+            }
+            *parent_flag |= flag;
+
+            if false == is_queued.get(&parent_id).copied().unwrap_or(false) {
+                let Some(parent_commit) = repository.read_commit(&parent_id) else {
+                    // shallow clone 의 끝
+                    continue;
+                };
+                queue.push((parent_commit.commit_time, parent_id));
+                is_queued.insert(parent_id, true);
+            }
+        }
+    }
+
+    let ahead = flags.values().filter(|flag| **flag == LOCAL).count();
+    let behind = flags.values().filter(|flag| **flag == UPSTREAM).count();
+    Some((ahead, behind))
+}
@@ -0,0 +1,557 @@
+// git 명령어를 실행하지 않고 .git 디렉토리를 직접 읽음
+//   ref      : loose ref 파일과 packed-refs
+//   object   : objects/xx/yyyy 의 loose object 와 objects/pack 의 pack (ofs/ref delta 적용)
+//   index    : version 2, 3, 4 의 index 파일 entry
+// pack 의 .idx 는 prompt 마다 다시 읽지 않도록 파싱한 결과를 수정 시각, 크기와 함께 저장해 두고 바뀐 경우만 다시 읽음
+
+use std::{collections::BTreeMap, fs::{self, File}, io::{self, BufReader, Read, Seek, SeekFrom}, path::{Path, PathBuf}, sync::{Arc, Mutex}, time::SystemTime};
+
+use flate2::read::ZlibDecoder;
+
+pub type ObjectId = [u8; 20];
+
+const OBJECT_COMMIT: u8 = 1;
+const OBJECT_TREE: u8 = 2;
+const OBJECT_BLOB: u8 = 3;
+const OBJECT_TAG: u8 = 4;
+const OBJECT_OFS_DELTA: u8 = 6;
+const OBJECT_REF_DELTA: u8 = 7;
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+// .idx 경로 별로 파싱한 index
+static PACK_INDEX_CACHE: Mutex<BTreeMap<PathBuf, CachedPackIndex>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitObject {
+    pub kind: &'static str,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitCommit {
+    pub tree: ObjectId,
+    pub parents: Vec<ObjectId>,
+    // committer 시간 (unix time)
+    pub commit_time: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexEntry {
+    pub path: String,
+    pub mode: u32,
+    pub object_id: ObjectId,
+    pub mtime_seconds: u32,
+    pub mtime_nanoseconds: u32,
+    pub size: u32,
+    // merge 중 conflict 가 나면 1 ~ 3
+    pub stage: u16,
+    // assume-unchanged, skip-worktree 는 worktree 와 비교하지 않음
+    pub is_assumed_valid: bool,
+}
+
+pub struct GitRepository {
+    pub git_dir: PathBuf,
+    // worktree 의 refs, objects 는 commondir 에 있음
+    pub common_dir: PathBuf,
+    pack_indexes: Vec<Arc<PackIndex>>,
+}
+
+struct CachedPackIndex {
+    modified: SystemTime,
+    len: u64,
+    pack_index: Arc<PackIndex>,
+}
+
+struct PackIndex {
+    pack_path: PathBuf,
+    object_ids: Vec<ObjectId>,
+    offsets: Vec<u64>,
+}
+
+impl GitRepository {
+    pub fn open(git_dir: &Path) -> Self {
+        let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+            Ok(common_dir) => git_dir.join(common_dir.trim()),
+            Err(_) => git_dir.to_path_buf(),
+        };
+        let pack_indexes = read_pack_indexes(&common_dir.join("objects").join("pack"));
+
+        Self {
+            git_dir: git_dir.to_path_buf(),
+            common_dir,
+            pack_indexes,
+        }
+    }
+
+    // HEAD 가 가리키는 ref 이름, detached 면 None
+    pub fn read_head_ref(&self) -> Option<String> {
+        let head = fs::read_to_string(self.git_dir.join("HEAD")).ok()?;
+        head.trim().strip_prefix("ref:").map(|ref_name| ref_name.trim().to_string())
+    }
+
+    pub fn resolve_head(&self) -> Option<ObjectId> {
+        let head = fs::read_to_string(self.git_dir.join("HEAD")).ok()?;
+        match head.trim().strip_prefix("ref:") {
+            Some(ref_name) => self.resolve_ref(ref_name.trim()),
+            None => parse_object_id(head.trim()),
+        }
+    }
+
+    // refs/heads/main 같은 전체 이름, 다른 ref 를 가리키는 symbolic ref 도 따라감
+    pub fn resolve_ref(&self, ref_name: &str) -> Option<ObjectId> {
+        self.resolve_ref_with_depth(ref_name, 0)
+    }
+
+    // symbolic ref 가 서로를 가리키는 경우의 무한 반복 방지
+    fn resolve_ref_with_depth(&self, ref_name: &str, depth: usize) -> Option<ObjectId> {
+        if depth > 5 {
+            return None;
+        }
+
+        // HEAD 같은 worktree 별 ref 는 git_dir 에, 나머지는 common_dir 에
+        let loose_ref = fs::read_to_string(self.git_dir.join(ref_name)).or_else(|_| fs::read_to_string(self.common_dir.join(ref_name)));
+        if let Ok(loose_ref) = loose_ref {
+            return match loose_ref.trim().strip_prefix("ref:") {
+                Some(target) => self.resolve_ref_with_depth(target.trim(), depth + 1),
+                None => parse_object_id(loose_ref.trim()),
+            };
+        }
+
+        let packed_refs = fs::read_to_string(self.common_dir.join("packed-refs")).ok()?;
+        packed_refs
+            .lines()
+            .filter(|line| false == line.starts_with('#') && false == line.starts_with('^'))
+            .find_map(|line| {
+                let (object_id, name) = line.split_once(' ')?;
+                if name.trim() == ref_name { parse_object_id(object_id) } else { None }
+            })
+    }
+
+    pub fn read_config(&self) -> String {
+        fs::read_to_string(self.common_dir.join("config")).unwrap_or_default()
+    }
+
+    pub fn read_object(&self, object_id: &ObjectId) -> Option<GitObject> {
+        if let Some(git_object) = self.read_loose_object(object_id) {
+            return Some(git_object);
+        }
+
+        for pack_index in &self.pack_indexes {
+            if let Ok(idx) = pack_index.object_ids.binary_search(object_id) {
+                let (kind, data) = self.read_pack_object(&pack_index.pack_path, pack_index.offsets[idx], 0)?;
+                return Some(GitObject { kind: get_object_kind_name(kind)?, data });
+            }
+        }
+
+        None
+    }
+
+    pub fn read_commit(&self, object_id: &ObjectId) -> Option<GitCommit> {
+        let git_object = self.read_object(object_id)?;
+        if git_object.kind != "commit" {
+            return None;
+        }
+        Some(parse_commit(&git_object.data))
+    }
+
+    // tree 를 펼쳐서 path -> (mode, object id)
+    pub fn read_tree_recursive(&self, tree_id: &ObjectId, prefix: &str, entries: &mut BTreeMap<String, (u32, ObjectId)>, is_timed_out: &dyn Fn() -> bool) -> Option<()> {
+        if is_timed_out() {
+            return None;
+        }
+
+        let git_object = self.read_object(tree_id)?;
+        let mut data = git_object.data.as_slice();
+
+        // "mode name\0" + 20 byte object id 의 반복
+        while false == data.is_empty() {
+            let space_idx = data.iter().position(|byte| *byte == b' ')?;
+            let nul_idx = data.iter().position(|byte| *byte == 0)?;
+            let mode = u32::from_str_radix(std::str::from_utf8(&data[..space_idx]).ok()?, 8).ok()?;
+            let name = String::from_utf8_lossy(&data[space_idx + 1..nul_idx]);
+            let object_id: ObjectId = data.get(nul_idx + 1..nul_idx + 21)?.try_into().ok()?;
+            data = &data[nul_idx + 21..];
+
+            let path = format!("{}{}", prefix, name);
+            if mode == 0o40000 {
+                self.read_tree_recursive(&object_id, &format!("{}/", path), entries, is_timed_out)?;
+            } else {
+                entries.insert(path, (mode, object_id));
+            }
+        }
+
+        Some(())
+    }
+
+    fn read_loose_object(&self, object_id: &ObjectId) -> Option<GitObject> {
+        let hex = format_object_id(object_id);
+        let file = File::open(self.common_dir.join("objects").join(&hex[..2]).join(&hex[2..])).ok()?;
+        let mut decompressed = vec![];
+        ZlibDecoder::new(file).read_to_end(&mut decompressed).ok()?;
+
+        // "commit 123\0" + 내용
+        let nul_idx = decompressed.iter().position(|byte| *byte == 0)?;
+        let header = std::str::from_utf8(&decompressed[..nul_idx]).ok()?;
+        let kind = match header.split(' ').next()? {
+            "commit" => "commit",
+            "tree" => "tree",
+            "blob" => "blob",
+            "tag" => "tag",
+            _ => return None,
+        };
+        Some(GitObject { kind, data: decompressed[nul_idx + 1..].to_vec() })
+    }
+
+    // delta 는 base object 를 먼저 읽어서 적용 (depth 는 무한 반복 방지)
+    fn read_pack_object(&self, pack_path: &Path, offset: u64, depth: usize) -> Option<(u8, Vec<u8>)> {
+        if depth > 64 {
+            return None;
+        }
+
+        let mut reader = BufReader::new(File::open(pack_path).ok()?);
+        reader.seek(SeekFrom::Start(offset)).ok()?;
+
+        // type 3 bit + size 가변 길이
+        let mut byte = read_u8(&mut reader)?;
+        let kind = (byte >> 4) & 0x07;
+        let mut size = (byte & 0x0f) as usize;
+        let mut shift = 4;
+        while byte & 0x80 != 0 {
+            byte = read_u8(&mut reader)?;
+            size |= ((byte & 0x7f) as usize) << shift;
+            shift += 7;
+        }
+
+        match kind {
+            OBJECT_OFS_DELTA => {
+                let mut byte = read_u8(&mut reader)?;
+                let mut base_distance = (byte & 0x7f) as u64;
+                while byte & 0x80 != 0 {
+                    byte = read_u8(&mut reader)?;
+                    base_distance = ((base_distance + 1) << 7) | (byte & 0x7f) as u64;
+                }
+                let delta = read_zlib(&mut reader, size)?;
+                let (base_kind, base_data) = self.read_pack_object(pack_path, offset.checked_sub(base_distance)?, depth + 1)?;
+                Some((base_kind, apply_delta(&base_data, &delta)?))
+            }
+            OBJECT_REF_DELTA => {
+                let mut base_id: ObjectId = [0; 20];
+                reader.read_exact(&mut base_id).ok()?;
+                let delta = read_zlib(&mut reader, size)?;
+                let base_object = self.read_object(&base_id)?;
+                let base_kind = match base_object.kind {
+                    "commit" => OBJECT_COMMIT,
+                    "tree" => OBJECT_TREE,
+                    "blob" => OBJECT_BLOB,
+                    _ => OBJECT_TAG,
+                };
+                Some((base_kind, apply_delta(&base_object.data, &delta)?))
+            }
+            _ => Some((kind, read_zlib(&mut reader, size)?)),
+        }
+    }
+
+    pub fn read_index(&self) -> Option<Vec<IndexEntry>> {
+        let data = fs::read(self.git_dir.join("index")).ok()?;
+        parse_index(&data)
+    }
+}
+
+fn read_pack_indexes(pack_dir: &Path) -> Vec<Arc<PackIndex>> {
+    let idx_paths: Vec<PathBuf> = match fs::read_dir(pack_dir) {
+        Ok(read_dir) => read_dir
+            .flatten()
+            .map(|dir_entry| dir_entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "idx"))
+            .collect(),
+        Err(_) => vec![],
+    };
+
+    let mut pack_index_cache = PACK_INDEX_CACHE.lock().unwrap();
+    // repack 등으로 지워진 .idx 는 cache 에서도 지움
+    pack_index_cache.retain(|idx_path, _| idx_path.parent() != Some(pack_dir) || idx_paths.contains(idx_path));
+
+    idx_paths
+        .into_iter()
+        .filter_map(|idx_path| {
+            let metadata = fs::metadata(&idx_path).ok()?;
+            let modified = metadata.modified().ok()?;
+            if let Some(cached_pack_index) = pack_index_cache.get(&idx_path)
+                && cached_pack_index.modified == modified
+                && cached_pack_index.len == metadata.len() {
+                return Some(cached_pack_index.pack_index.clone());
+            }
+
+            let data = fs::read(&idx_path).ok()?;
+            let (object_ids, offsets) = parse_pack_index(&data)?;
+            let pack_index = Arc::new(PackIndex { pack_path: idx_path.with_extension("pack"), object_ids, offsets });
+            pack_index_cache.insert(idx_path, CachedPackIndex { modified, len: metadata.len(), pack_index: pack_index.clone() });
+            Some(pack_index)
+        })
+        .collect()
+}
+
+// version 2 의 .idx : header, fanout[256], object id[n], crc[n], offset[n], 큰 offset[]
+fn parse_pack_index(data: &[u8]) -> Option<(Vec<ObjectId>, Vec<u64>)> {
+    if data.get(..8)? != b"\xfftOc\x00\x00\x00\x02" {
+        return None;
+    }
+
+    let object_count = read_be_u32(data, 8 + 255 * 4)? as usize;
+    let object_ids_start = 8 + 256 * 4;
+    let offsets_start = object_ids_start + object_count * 24;
+    let large_offsets_start = offsets_start + object_count * 4;
+
+    let mut object_ids = Vec::with_capacity(object_count);
+    let mut offsets = Vec::with_capacity(object_count);
+    for idx in 0..object_count {
+        let start = object_ids_start + idx * 20;
+        object_ids.push(data.get(start..start + 20)?.try_into().ok()?);
+
+        let offset = read_be_u32(data, offsets_start + idx * 4)?;
+        if offset & 0x8000_0000 == 0 {
+            offsets.push(offset as u64);
+        } else {
+            let large_idx = large_offsets_start + (offset & 0x7fff_ffff) as usize * 8;
+            offsets.push(u64::from_be_bytes(data.get(large_idx..large_idx + 8)?.try_into().ok()?));
+        }
+    }
+
+    Some((object_ids, offsets))
+}
+
+// delta : base 크기, 결과 크기, 그 뒤는 copy(base 의 일부) / insert(새 데이터) 명령
+fn apply_delta(base: &[u8], delta: &[u8]) -> Option<Vec<u8>> {
+    let mut idx = 0;
+    let read_size = |idx: &mut usize| -> Option<usize> {
+        let mut size = 0;
+        let mut shift = 0;
+        loop {
+            let byte = *delta.get(*idx)?;
+            *idx += 1;
+            size |= ((byte & 0x7f) as usize) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return Some(size);
+            }
+        }
+    };
+
+    let base_size = read_size(&mut idx)?;
+    if base_size != base.len() {
+        return None;
+    }
+    let result_size = read_size(&mut idx)?;
+    let mut result = Vec::with_capacity(result_size);
+
+    while idx < delta.len() {
+        let command = delta[idx];
+        idx += 1;
+
+        if command & 0x80 != 0 {
+            let mut copy_offset = 0usize;
+            let mut copy_size = 0usize;
+            for bit in 0..4 {
+                if command & (1 << bit) != 0 {
+                    copy_offset |= (*delta.get(idx)? as usize) << (bit * 8);
+                    idx += 1;
+                }
+            }
+            for bit in 0..3 {
+                if command & (0x10 << bit) != 0 {
+                    copy_size |= (*delta.get(idx)? as usize) << (bit * 8);
+                    idx += 1;
+                }
+            }
+            if copy_size == 0 {
+                copy_size = 0x10000;
+            }
+            result.extend_from_slice(base.get(copy_offset..copy_offset + copy_size)?);
+        } else if command != 0 {
+            let insert_size = command as usize;
+            result.extend_from_slice(delta.get(idx..idx + insert_size)?);
+            idx += insert_size;
+        } else {
+            return None;
+        }
+    }
+
+    if result.len() != result_size {
+        return None;
+    }
+    Some(result)
+}
+
+// header 의 signature, version, entry 개수 뒤에 entry 가 이어짐
+fn parse_index(data: &[u8]) -> Option<Vec<IndexEntry>> {
+    if data.get(..4)? != b"DIRC" {
+        return None;
+    }
+    let version = read_be_u32(data, 4)?;
+    if false == (2..=4).contains(&version) {
+        return None;
+    }
+    let entry_count = read_be_u32(data, 8)? as usize;
+
+    let mut entries = Vec::with_capacity(entry_count);
+    let mut idx = 12;
+    let mut previous_path = String::new();
+
+    for _ in 0..entry_count {
+        let entry_start = idx;
+        let mtime_seconds = read_be_u32(data, idx + 8)?;
+        let mtime_nanoseconds = read_be_u32(data, idx + 12)?;
+        let mode = read_be_u32(data, idx + 24)?;
+        let size = read_be_u32(data, idx + 36)?;
+        let object_id: ObjectId = data.get(idx + 40..idx + 60)?.try_into().ok()?;
+        let flags = u16::from_be_bytes(data.get(idx + 60..idx + 62)?.try_into().ok()?);
+        idx += 62;
+
+        let mut is_assumed_valid = flags & 0x8000 != 0;
+        // version 3 이상의 extended flag (skip-worktree, intent-to-add)
+        if flags & 0x4000 != 0 {
+            let extended_flags = u16::from_be_bytes(data.get(idx..idx + 2)?.try_into().ok()?);
+            is_assumed_valid |= extended_flags & 0x4000 != 0;
+            idx += 2;
+        }
+
+        let path = if version == 4 {
+            // 이전 path 의 뒤쪽 N byte 를 지우고 이어붙임
+            let mut strip_len = 0usize;
+            loop {
+                let byte = *data.get(idx)?;
+                idx += 1;
+                strip_len = (strip_len << 7) | (byte & 0x7f) as usize;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                strip_len += 1;
+            }
+            let nul_idx = idx + data.get(idx..)?.iter().position(|byte| *byte == 0)?;
+            let mut path = previous_path.get(..previous_path.len().checked_sub(strip_len)?)?.to_string();
+            path.push_str(&String::from_utf8_lossy(&data[idx..nul_idx]));
+            idx = nul_idx + 1;
+            path
+        } else {
+            let nul_idx = idx + data.get(idx..)?.iter().position(|byte| *byte == 0)?;
+            let path = String::from_utf8_lossy(&data[idx..nul_idx]).into_owned();
+            // entry 크기가 8 의 배수가 되도록 NUL 로 채워져 있음
+            idx = entry_start + (nul_idx + 1 - entry_start).div_ceil(8) * 8;
+            path
+        };
+
+        previous_path = path.to_owned();
+        entries.push(IndexEntry {
+            path,
+            mode,
+            object_id,
+            mtime_seconds,
+            mtime_nanoseconds,
+            size,
+            stage: (flags >> 12) & 0x03,
+            is_assumed_valid,
+        });
+    }
+
+    Some(entries)
+}
+
+fn parse_commit(data: &[u8]) -> GitCommit {
+    let mut commit = GitCommit { tree: [0; 20], parents: vec![], commit_time: 0 };
+
+    // header 는 첫번째 빈 줄까지
+    for line in String::from_utf8_lossy(data).lines() {
+        if line.is_empty() {
+            break;
+        }
+        if let Some(tree) = line.strip_prefix("tree ").and_then(parse_object_id) {
+            commit.tree = tree;
+        } else if let Some(parent) = line.strip_prefix("parent ").and_then(parse_object_id) {
+            commit.parents.push(parent);
+        } else if let Some(committer) = line.strip_prefix("committer ") {
+            // "name <email> 1700000000 +0900"
+            commit.commit_time = committer.rsplit(' ').nth(1).and_then(|time| time.parse().ok()).unwrap_or(0);
+        }
+    }
+
+    commit
+}
+
+pub fn parse_object_id(hex: &str) -> Option<ObjectId> {
+    if hex.len() != 40 {
+        return None;
+    }
+    let mut object_id = [0u8; 20];
+    for (idx, byte) in object_id.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(idx * 2..idx * 2 + 2)?, 16).ok()?;
+    }
+    Some(object_id)
+}
+
+pub fn format_object_id(object_id: &ObjectId) -> String {
+    object_id.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// git 과 같은 방식의 blob object id ("blob 크기\0" + 내용 의 sha1)
+pub fn hash_blob(contents: &[u8]) -> ObjectId {
+    let mut hasher = sha1_smol::Sha1::new();
+    hasher.update(format!("blob {}\0", contents.len()).as_bytes());
+    hasher.update(contents);
+    hasher.digest().bytes()
+}
+
+// 큰 파일도 prompt 를 멈추지 않게 나눠 읽으면서 blob object id 계산, 시간 안에 끝나지 않으면 Ok(None)
+pub fn hash_blob_file(path: &Path, size: u64, is_timed_out: &dyn Fn() -> bool) -> io::Result<Option<ObjectId>> {
+    let mut file = File::open(path)?;
+    let mut hasher = sha1_smol::Sha1::new();
+    hasher.update(format!("blob {}\0", size).as_bytes());
+
+    let mut buf = vec![0u8; HASH_CHUNK_SIZE];
+    let mut read_size = 0u64;
+    loop {
+        if is_timed_out() {
+            return Ok(None);
+        }
+        let len = file.read(&mut buf)?;
+        if len == 0 {
+            break;
+        }
+        hasher.update(&buf[..len]);
+        read_size += len as u64;
+    }
+
+    // 읽는 중에 크기가 바뀌었으면 header 가 틀림
+    if read_size != size {
+        return Err(io::Error::other("file size changed"));
+    }
+    Ok(Some(hasher.digest().bytes()))
+}
+
+fn get_object_kind_name(kind: u8) -> Option<&'static str> {
+    match kind {
+        OBJECT_COMMIT => Some("commit"),
+        OBJECT_TREE => Some("tree"),
+        OBJECT_BLOB => Some("blob"),
+        OBJECT_TAG => Some("tag"),
+        _ => None,
+    }
+}
+
+fn read_zlib(reader: &mut impl Read, size: usize) -> Option<Vec<u8>> {
+    let mut decompressed = Vec::with_capacity(size);
+    ZlibDecoder::new(reader).take(size as u64).read_to_end(&mut decompressed).ok()?;
+    if decompressed.len() != size {
+        return None;
+    }
+    Some(decompressed)
+}
+
+fn read_u8(reader: &mut impl Read) -> Option<u8> {
+    let mut byte = [0u8; 1];
+    reader.read_exact(&mut byte).ok()?;
+    Some(byte[0])
+}
+
+fn read_be_u32(data: &[u8], idx: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(idx..idx + 4)?.try_into().ok()?))
+}
@@ -0,0 +1,248 @@
+// Ctrl-R 의 history 검색 UI
+//   입력중인 line 으로 시작해서, 입력한 문자열이 포함된 command 를 최근 것부터 (같은 command 는 한번만)
+//   소문자만 입력하면 대소문자 구분 없이 매칭
+//     ↑ ↓ (Ctrl-P, Ctrl-N, Ctrl-R) : 이동
+//     TAB                            : 필터 변경 (all → cwd → session → failed → succeeded)
+//     Enter                          : 선택한 command 를 line 에 넣고 편집 (실행하지 않음)
+//     ESC / Ctrl-C / Ctrl-G          : 취소
+//   목록 아래에 선택한 command 의 디렉토리, 시작 시각, 걸린 시간, exit status, session 표시
+// 실행 정보가 있는 history database(HISTDB) 의 기록에서 검색 (다른 shell 과 이전 session 포함)
+// HISTDB 를 쓰지 않거나 읽을 수 없으면 현재 history 에서 검색
+
+use std::io::{self, Write};
+
+use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, RepeatCount};
+
+use crate::rustyline_editor::completion_display::{MenuKey, get_terminal_size, read_key, truncate_to_width};
+use crate::rustyline_editor::prompt::format_timestamp;
+use crate::rustyline_editor::tab_handler::get_replace_line_cmd;
+use crate::shell_builtin::cd_command::get_logical_current_dir;
+use crate::shell_state::history::{HistoryEntry, get_history_entries, get_session_id};
+use crate::shell_state::history_database::{get_history_database_path, read_history_records};
+
+const SEARCH_MAX_ROWS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryFilter {
+    All,
+    // 현재 디렉토리에서 실행한 command
+    Directory,
+    // 이 shell 에서 실행한 command
+    Session,
+    Failed,
+    Succeeded,
+}
+
+impl HistoryFilter {
+    fn label(&self) -> &'static str {
+        match self {
+            HistoryFilter::All => "all",
+            HistoryFilter::Directory => "cwd",
+            HistoryFilter::Session => "session",
+            HistoryFilter::Failed => "failed",
+            HistoryFilter::Succeeded => "succeeded",
+        }
+    }
+
+    fn next(&self) -> HistoryFilter {
+        match self {
+            HistoryFilter::All => HistoryFilter::Directory,
+            HistoryFilter::Directory => HistoryFilter::Session,
+            HistoryFilter::Session => HistoryFilter::Failed,
+            HistoryFilter::Failed => HistoryFilter::Succeeded,
+            HistoryFilter::Succeeded => HistoryFilter::All,
+        }
+    }
+
+    // 실행 정보가 없는 entry 는 all 에서만 보임
+    fn is_match(&self, history_entry: &HistoryEntry, current_dir: &str) -> bool {
+        let Some(metadata) = &history_entry.metadata else {
+            return *self == HistoryFilter::All;
+        };
+        match self {
+            HistoryFilter::All => true,
+            HistoryFilter::Directory => metadata.cwd == current_dir,
+            HistoryFilter::Session => metadata.session_id == get_session_id(),
+            HistoryFilter::Failed => metadata.exit_status.is_some_and(|exit_status| exit_status != 0),
+            HistoryFilter::Succeeded => metadata.exit_status == Some(0),
+        }
+    }
+}
+
+pub struct HistorySearchHandler;
+
+impl ConditionalEventHandler for HistorySearchHandler {
+    fn handle(&self, _evt: &Event, _n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        let line = ctx.line();
+        match search_history(line) {
+            Some(selected) => Some(get_replace_line_cmd(line, ctx.pos(), &selected, selected.len())),
+            None => Some(Cmd::Repaint),
+        }
+    }
+}
+
+// query 가 포함된 entry, 최근 것부터 같은 command 는 한번만
+pub fn filter_history_entries(history_entries: &[HistoryEntry], query: &str, history_filter: HistoryFilter, current_dir: &str) -> Vec<HistoryEntry> {
+    let is_ignore_case = false == query.chars().any(|char| char.is_uppercase());
+    let query = if is_ignore_case { query.to_lowercase() } else { query.to_string() };
+
+    let mut filtered_entries: Vec<HistoryEntry> = vec![];
+    for history_entry in history_entries.iter().rev() {
+        if false == history_filter.is_match(history_entry, current_dir) {
+            continue;
+        }
+        let is_contained = if is_ignore_case { history_entry.line.to_lowercase().contains(&query) } else { history_entry.line.contains(&query) };
+        if false == is_contained || filtered_entries.iter().any(|entry| entry.line == history_entry.line) {
+            continue;
+        }
+        filtered_entries.push(history_entry.to_owned());
+    }
+
+    filtered_entries
+}
+
+// 선택한 command, 취소하면 None
+fn search_history(query: &str) -> Option<String> {
+    let mut stdout = io::stdout();
+    let terminal_size = get_terminal_size();
+    let history_entries = get_search_entries();
+    // 기록된 cwd 와 같은 논리 경로
+    let current_dir = get_logical_current_dir().to_string_lossy().into_owned();
+
+    let mut query = query.to_string();
+    let mut history_filter = HistoryFilter::All;
+    let mut filtered_entries = filter_history_entries(&history_entries, &query, history_filter, &current_dir);
+    let mut selected_idx = 0;
+    let mut scroll_offset = 0;
+
+    // query 줄 + 목록 + 선택한 entry 정보 줄
+    let visible_rows = SEARCH_MAX_ROWS.min(history_entries.len()).min(terminal_size.rows.saturating_sub(3)).max(1);
+    let search_height = visible_rows + 2;
+
+    // completion menu 와 같이 아래쪽 줄을 확보하고 prompt 위치 저장
+    write!(stdout, "{}\x1b[{}A\x1b7", "\x1bD".repeat(search_height), search_height).ok();
+
+    let selected = loop {
+        if selected_idx < scroll_offset {
+            scroll_offset = selected_idx;
+        } else if selected_idx >= scroll_offset + visible_rows {
+            scroll_offset = selected_idx + 1 - visible_rows;
+        }
+
+        write!(stdout, "\x1b8").ok();
+        let status = format!("[{}] {}/{}", history_filter.label(), if filtered_entries.is_empty() { 0 } else { selected_idx + 1 }, filtered_entries.len());
+        write!(stdout, "\r\n\x1b[K\x1b[2mhistory> \x1b[0m{}  \x1b[2m{}\x1b[0m", truncate_to_width(&query, terminal_size.columns.saturating_sub(status.len() + 11)), status).ok();
+        for row in 0..visible_rows {
+            write!(stdout, "\r\n\x1b[K").ok();
+            let idx = scroll_offset + row;
+            let Some(history_entry) = filtered_entries.get(idx) else {
+                continue;
+            };
+            let text = format_entry_row(history_entry, terminal_size.columns.saturating_sub(2));
+            if idx == selected_idx {
+                write!(stdout, "\x1b[7m {}\x1b[0m", text).ok();
+            } else {
+                write!(stdout, " {}", text).ok();
+            }
+        }
+        let detail = filtered_entries.get(selected_idx).map(format_entry_detail).unwrap_or_default();
+        write!(stdout, "\r\n\x1b[K\x1b[2m{}\x1b[0m", truncate_to_width(&detail, terminal_size.columns.saturating_sub(1))).ok();
+        write!(stdout, "\x1b8").ok();
+        stdout.flush().ok();
+
+        let mut is_query_changed = false;
+        match read_key() {
+            Some(MenuKey::Up) => selected_idx = selected_idx.saturating_sub(1),
+            Some(MenuKey::Down | MenuKey::Control('R')) => {
+                if selected_idx + 1 < filtered_entries.len() {
+                    selected_idx += 1;
+                }
+            }
+            Some(MenuKey::Tab) => {
+                history_filter = history_filter.next();
+                is_query_changed = true;
+            }
+            Some(MenuKey::Enter) => {
+                if let Some(history_entry) = filtered_entries.get(selected_idx) {
+                    break Some(history_entry.line.to_owned());
+                }
+            }
+            Some(MenuKey::Cancel) | None => break None,
+            Some(MenuKey::Backspace) => {
+                query.pop();
+                is_query_changed = true;
+            }
+            Some(MenuKey::Char(char)) => {
+                query.push(char);
+                is_query_changed = true;
+            }
+            Some(MenuKey::Control(_) | MenuKey::Other) => {}
+        }
+
+        if is_query_changed {
+            filtered_entries = filter_history_entries(&history_entries, &query, history_filter, &current_dir);
+            selected_idx = 0;
+        }
+    };
+
+    // 검색 UI 지우고 prompt 위치로
+    write!(stdout, "\x1b8\r\n\x1b[J\x1b8").ok();
+    stdout.flush().ok();
+
+    selected
+}
+
+// 기록 파일의 순서(오래된 것부터)
+fn get_search_entries() -> Vec<HistoryEntry> {
+    let history_records = get_history_database_path().and_then(|history_database_path| read_history_records(&history_database_path).ok());
+    match history_records {
+        Some(history_records) if false == history_records.is_empty() => history_records,
+        _ => get_history_entries(),
+    }
+}
+
+// 시작 시각, 실패했으면 exit status, command (여러 줄은 ↵ 로 이어서)
+fn format_entry_row(history_entry: &HistoryEntry, width: usize) -> String {
+    let (time, exit_status) = match &history_entry.metadata {
+        Some(metadata) => (
+            format_timestamp(metadata.start_time, "%m-%d %H:%M"),
+            match metadata.exit_status {
+                Some(0) | None => String::new(),
+                Some(exit_status) => exit_status.to_string(),
+            },
+        ),
+        None => (String::new(), String::new()),
+    };
+
+    let row = format!("{:<11} {:>3}  {}", time, exit_status, history_entry.line.replace('\n', " ↵ "));
+    truncate_to_width(&row, width)
+}
+
+fn format_entry_detail(history_entry: &HistoryEntry) -> String {
+    let Some(metadata) = &history_entry.metadata else {
+        return "(no metadata)".to_string();
+    };
+
+    let mut details = vec![
+        metadata.cwd.to_owned(),
+        format_timestamp(metadata.start_time, "%Y-%m-%d %H:%M:%S"),
+    ];
+    if let Some(duration_ms) = metadata.duration_ms {
+        details.push(format_duration(duration_ms));
+    }
+    match metadata.exit_status {
+        Some(exit_status) => details.push(format!("status {}", exit_status)),
+        None => details.push("running".to_string()),
+    }
+    details.push(format!("session {}", metadata.session_id));
+
+    details.join("  ")
+}
+
+pub fn format_duration(duration_ms: u64) -> String {
+    match duration_ms {
+        0..1000 => format!("{}ms", duration_ms),
+        1000..60_000 => format!("{:.1}s", duration_ms as f64 / 1000.0),
+        _ => format!("{}m{}s", duration_ms / 60_000, duration_ms % 60_000 / 1000),
+    }
+}
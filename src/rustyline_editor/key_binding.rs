@@ -0,0 +1,490 @@
+// bind builtin 과 inputrc 파일의 key binding 을 rustyline Editor 에 반영
+//   "\C-x\C-r": function-name   : editor 함수 (bind -l 목록)
+//   "\C-xg": "text"             : 입력할 문자열 (macro)
+//   Control-a: function-name    : key 이름 (Control-, Meta-, C-, M-, DEL, ESC, RET, SPC, TAB ...)
+//   set editing-mode vi         : inputrc 변수, editing-mode 외에는 무시
+//   $if 같은 조건부 directive 는 무시하고 안쪽 줄을 모두 적용
+// key sequence 의 escape : \C-x \M-x \e \\ \" \' \a \b \d \f \n \r \t \v \nnn \xHH
+// \e[A 같은 방향키 등의 escape sequence 는 rustyline 의 key 로 바꿔서 bind
+// inputrc 파일은 INPUTRC 변수, 없으면 ~/.shell_inputrc
+
+use std::{fs, io::{self, Write}, path::{Path, PathBuf}, sync::Mutex};
+
+use rustyline::{Anchor, At, Cmd, ConditionalEventHandler, EditMode, Editor, Event, EventContext, EventHandler, KeyCode, KeyEvent, Modifiers, Movement, RepeatCount, Word, config::Configurer};
+
+use crate::rustyline_editor::history_search::HistorySearchHandler;
+use crate::rustyline_editor::shell_helper::{HintCompletionHandler, ShellHelper};
+use crate::rustyline_editor::tab_handler::{MyTabHandler, get_replace_line_cmd};
+use crate::shell_state::history::SharedHistory;
+use crate::shell_state::key_bindings::{KeyBinding, KeyBindingAction, set_key_binding, take_changed_key_bindings};
+use crate::shell_state::options::{find_shell_option, is_shell_option_enabled, set_shell_option};
+use crate::shell_state::variables::{get_variable, set_variable};
+
+const DEFAULT_INPUTRC_FILE_NAME: &str = ".shell_inputrc";
+
+// readline 과 같은 이름의 editor 함수, complete 는 MyTabHandler, reverse-search-history 는 HistorySearchHandler 를 사용
+const EDITOR_FUNCTIONS: [(&str, Cmd); 38] = [
+    ("abort", Cmd::Abort),
+    ("accept-line", Cmd::AcceptLine),
+    // 흐리게 표시된 autosuggestion 을 입력
+    ("accept-suggestion", Cmd::CompleteHint),
+    ("backward-char", Cmd::Move(Movement::BackwardChar(1))),
+    ("backward-delete-char", Cmd::Kill(Movement::BackwardChar(1))),
+    ("backward-kill-line", Cmd::Kill(Movement::BeginningOfLine)),
+    ("backward-kill-word", Cmd::Kill(Movement::BackwardWord(1, Word::Emacs))),
+    ("backward-word", Cmd::Move(Movement::BackwardWord(1, Word::Emacs))),
+    ("beginning-of-history", Cmd::BeginningOfHistory),
+    ("beginning-of-line", Cmd::Move(Movement::BeginningOfLine)),
+    ("capitalize-word", Cmd::CapitalizeWord),
+    ("clear-screen", Cmd::ClearScreen),
+    ("complete", Cmd::Complete),
+    ("delete-char", Cmd::Kill(Movement::ForwardChar(1))),
+    ("downcase-word", Cmd::DowncaseWord),
+    ("end-of-history", Cmd::EndOfHistory),
+    ("end-of-line", Cmd::Move(Movement::EndOfLine)),
+    ("forward-char", Cmd::Move(Movement::ForwardChar(1))),
+    ("forward-search-history", Cmd::ForwardSearchHistory),
+    ("forward-word", Cmd::Move(Movement::ForwardWord(1, At::AfterEnd, Word::Emacs))),
+    ("history-search-backward", Cmd::HistorySearchBackward),
+    ("history-search-forward", Cmd::HistorySearchForward),
+    ("kill-line", Cmd::Kill(Movement::EndOfLine)),
+    ("kill-whole-line", Cmd::Kill(Movement::WholeLine)),
+    ("kill-word", Cmd::Kill(Movement::ForwardWord(1, At::AfterEnd, Word::Emacs))),
+    ("next-history", Cmd::NextHistory),
+    ("previous-history", Cmd::PreviousHistory),
+    ("quoted-insert", Cmd::QuotedInsert),
+    ("redraw-current-line", Cmd::Repaint),
+    ("reverse-search-history", Cmd::ReverseSearchHistory),
+    ("transpose-chars", Cmd::TransposeChars),
+    ("transpose-words", Cmd::TransposeWords(1)),
+    ("undo", Cmd::Undo(1)),
+    ("unix-line-discard", Cmd::Kill(Movement::BeginningOfLine)),
+    ("unix-word-rubout", Cmd::Kill(Movement::BackwardWord(1, Word::Big))),
+    ("upcase-word", Cmd::UpcaseWord),
+    ("yank", Cmd::Yank(1, Anchor::Before)),
+    ("yank-pop", Cmd::YankPop),
+];
+
+// Editor 에 마지막으로 bind 한 key sequence, 다시 반영할 때 먼저 unbind
+static APPLIED_KEY_SEQUENCES: Mutex<Vec<Vec<KeyEvent>>> = Mutex::new(vec![]);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputrcLine {
+    // 빈 줄, 주석, 조건부 directive
+    Empty,
+    Variable(String, String),
+    Binding(KeyBinding),
+}
+
+pub fn get_editor_function_names() -> Vec<&'static str> {
+    EDITOR_FUNCTIONS.iter().map(|(name, _)| *name).collect()
+}
+
+// inputrc 한 줄 혹은 bind 의 인자, is_shell_command 면 : 뒤를 shell command 로 (bind -x)
+pub fn parse_inputrc_line(line: &str, is_shell_command: bool) -> Result<InputrcLine, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') || line.starts_with('$') {
+        return Ok(InputrcLine::Empty);
+    }
+
+    if let Some(rest) = line.strip_prefix("set ") {
+        let mut parts = rest.split_whitespace();
+        let (Some(name), Some(value)) = (parts.next(), parts.next()) else {
+            return Err(format!("{}: missing variable value", line));
+        };
+        return Ok(InputrcLine::Variable(name.to_string(), value.to_string()));
+    }
+
+    let (key_sequence, keys, rest) = if line.starts_with('"') {
+        let close_idx = find_closing_quote(line).ok_or_else(|| format!("{}: no closing `\"' in key binding", line))?;
+        let keys = parse_key_sequence(&line[1..close_idx])?;
+        (line[..=close_idx].to_string(), keys, &line[close_idx + 1..])
+    } else {
+        let Some(colon_idx) = line.find(':') else {
+            return Err(format!("{}: missing colon separator", line));
+        };
+        let key_name = line[..colon_idx].trim();
+        (key_name.to_string(), parse_key_name(key_name)?, &line[colon_idx..])
+    };
+
+    let Some(value) = rest.trim_start().strip_prefix(':') else {
+        return Err(format!("{}: missing colon separator", line));
+    };
+    let value = value.trim();
+
+    let action = if is_shell_command {
+        KeyBindingAction::ShellCommand(strip_quotes(value).to_string())
+    } else if value.starts_with('"') || value.starts_with('\'') {
+        KeyBindingAction::Macro(strip_quotes(value).to_string())
+    } else if EDITOR_FUNCTIONS.iter().any(|(name, _)| *name == value) {
+        KeyBindingAction::Function(value.to_string())
+    } else {
+        return Err(format!("{}: unknown function name", value));
+    };
+
+    Ok(InputrcLine::Binding(KeyBinding { key_sequence, keys, action }))
+}
+
+// 한 줄씩 적용하고 잘못된 줄은 출력만 하고 넘어감
+pub fn read_inputrc_file(path: &Path) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    for (line_idx, line) in contents.lines().enumerate() {
+        let result = parse_inputrc_line(line, false).and_then(|inputrc_line| match inputrc_line {
+            InputrcLine::Empty => Ok(()),
+            InputrcLine::Variable(name, value) => set_inputrc_variable(&name, &value),
+            InputrcLine::Binding(key_binding) => {
+                set_key_binding(key_binding);
+                Ok(())
+            }
+        });
+        if let Err(e) = result {
+            println!("{}: line {}: {}", path.display(), line_idx + 1, e);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn set_inputrc_variable(name: &str, value: &str) -> Result<(), String> {
+    match name {
+        "editing-mode" => {
+            let Some(option) = find_shell_option(value).filter(|_| value == "vi" || value == "emacs") else {
+                return Err(format!("{}: invalid editing mode", value));
+            };
+            set_shell_option(option, true);
+            Ok(())
+        }
+        // 지원하지 않는 readline 변수는 무시
+        _ => Ok(()),
+    }
+}
+
+pub fn get_inputrc_file_path() -> Option<PathBuf> {
+    if let Some(inputrc) = get_variable("INPUTRC") {
+        return Some(PathBuf::from(inputrc));
+    }
+
+    get_variable("HOME").map(|home| Path::new(&home).join(DEFAULT_INPUTRC_FILE_NAME))
+}
+
+// shell 시작 시 inputrc 로드
+pub fn load_inputrc() {
+    let Some(inputrc_file_path) = get_inputrc_file_path() else {
+        return;
+    };
+
+    if false == inputrc_file_path.exists() {
+        return;
+    }
+
+    if let Err(e) = read_inputrc_file(&inputrc_file_path) {
+        eprintln!("inputrc load error. {}", e);
+    }
+}
+
+// set -o vi/emacs 와 바뀐 key binding 을 Editor 에 반영
+pub fn apply_key_bindings(readline_editor: &mut Editor<ShellHelper, SharedHistory>) {
+    let edit_mode = if is_shell_option_enabled("vi") { EditMode::Vi } else { EditMode::Emacs };
+    if readline_editor.config_mut().edit_mode() != edit_mode {
+        readline_editor.set_edit_mode(edit_mode);
+    }
+
+    let Some(key_bindings) = take_changed_key_bindings() else {
+        return;
+    };
+
+    let mut applied_key_sequences = APPLIED_KEY_SEQUENCES.lock().unwrap();
+    for keys in applied_key_sequences.drain(..) {
+        readline_editor.unbind_sequence(Event::KeySeq(keys));
+    }
+
+    // 기본 binding 뒤에 사용자 binding 을 적용해서 덮어쓸 수 있게
+    let default_key_bindings = [
+        (KeyEvent(KeyCode::Tab, Modifiers::NONE), EventHandler::Conditional(Box::new(MyTabHandler::new(crate::COMMAND)))),
+        (KeyEvent(KeyCode::End, Modifiers::NONE), EventHandler::Conditional(Box::new(HintCompletionHandler))),
+        (KeyEvent(KeyCode::Char('R'), Modifiers::CTRL), EventHandler::Conditional(Box::new(HistorySearchHandler))),
+    ];
+    for (key, event_handler) in default_key_bindings {
+        readline_editor.bind_sequence(key, event_handler);
+        applied_key_sequences.push(vec![key]);
+    }
+
+    for key_binding in key_bindings {
+        readline_editor.bind_sequence(Event::KeySeq(key_binding.keys.to_owned()), get_event_handler(&key_binding.action));
+        applied_key_sequences.push(key_binding.keys);
+    }
+}
+
+fn get_event_handler(action: &KeyBindingAction) -> EventHandler {
+    match action {
+        KeyBindingAction::Function(name) if name == "complete" => EventHandler::Conditional(Box::new(MyTabHandler::new(crate::COMMAND))),
+        KeyBindingAction::Function(name) if name == "reverse-search-history" => EventHandler::Conditional(Box::new(HistorySearchHandler)),
+        KeyBindingAction::Function(name) => {
+            let cmd = EDITOR_FUNCTIONS.iter().find(|(function_name, _)| function_name == name).map(|(_, cmd)| cmd.to_owned());
+            EventHandler::Simple(cmd.unwrap_or(Cmd::Noop))
+        }
+        KeyBindingAction::Macro(text) => {
+            let text: String = parse_escaped_chars(text).into_iter().collect();
+            EventHandler::Simple(Cmd::Insert(1, text))
+        }
+        KeyBindingAction::ShellCommand(command) => EventHandler::Conditional(Box::new(ShellCommandHandler { command: command.to_owned() })),
+    }
+}
+
+// bind -x : READLINE_LINE, READLINE_POINT 로 입력중인 line 을 넘기고, command 가 바꾼 값을 다시 line 에 반영
+struct ShellCommandHandler {
+    command: String,
+}
+
+impl ConditionalEventHandler for ShellCommandHandler {
+    fn handle(&self, _evt: &Event, _n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        let line = ctx.line();
+        let pos = ctx.pos();
+        set_variable("READLINE_LINE", line);
+        set_variable("READLINE_POINT", &line[..pos].chars().count().to_string());
+
+        // command 의 출력이 입력중인 line 을 덮어쓰지 않게 다음 줄에서 실행
+        print!("\r\n");
+        io::stdout().flush().ok();
+        crate::run_command_line(&self.command);
+
+        let new_line = get_variable("READLINE_LINE").unwrap_or_default();
+        let point = get_variable("READLINE_POINT").and_then(|point| point.parse::<usize>().ok()).unwrap_or(usize::MAX);
+        let point = new_line.char_indices().nth(point).map(|(idx, _)| idx).unwrap_or(new_line.len());
+
+        if new_line == line {
+            return Some(Cmd::Repaint);
+        }
+        Some(get_replace_line_cmd(line, pos, &new_line, point))
+    }
+}
+
+pub fn parse_key_sequence(key_sequence: &str) -> Result<Vec<KeyEvent>, String> {
+    let keys = chars_to_keys(&parse_escaped_chars(key_sequence))?;
+    if keys.is_empty() {
+        return Err("empty key sequence".to_string());
+    }
+    Ok(keys)
+}
+
+// Control-x, Meta-x, C-M-x, TAB 처럼 이름으로 지정한 key
+fn parse_key_name(key_name: &str) -> Result<Vec<KeyEvent>, String> {
+    let mut rest = key_name;
+    let mut is_control = false;
+    let mut is_meta = false;
+    loop {
+        if let Some(next) = rest.strip_prefix("Control-").or_else(|| rest.strip_prefix("C-")) {
+            is_control = true;
+            rest = next;
+        } else if let Some(next) = rest.strip_prefix("Meta-").or_else(|| rest.strip_prefix("M-")) {
+            is_meta = true;
+            rest = next;
+        } else {
+            break;
+        }
+    }
+
+    let char = match rest.to_ascii_uppercase().as_str() {
+        "DEL" | "RUBOUT" => '\x7f',
+        "ESC" | "ESCAPE" => '\x1b',
+        "LFD" | "NEWLINE" => '\n',
+        "RET" | "RETURN" => '\r',
+        "SPC" | "SPACE" => ' ',
+        "TAB" => '\t',
+        _ => {
+            let mut chars = rest.chars();
+            match (chars.next(), chars.next()) {
+                (Some(char), None) => char,
+                _ => return Err(format!("{}: unknown key name", key_name)),
+            }
+        }
+    };
+
+    let mut chars = vec![];
+    if is_meta {
+        chars.push('\x1b');
+    }
+    chars.push(if is_control { get_control_char(char) } else { char });
+    chars_to_keys(&chars)
+}
+
+fn find_closing_quote(line: &str) -> Option<usize> {
+    let mut is_escaped = false;
+    for (idx, char) in line.char_indices().skip(1) {
+        match char {
+            _ if is_escaped => is_escaped = false,
+            '\\' => is_escaped = true,
+            '"' => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn strip_quotes(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return &value[1..value.len() - 1];
+        }
+    }
+    value
+}
+
+fn get_control_char(char: char) -> char {
+    if char == '?' {
+        return '\x7f';
+    }
+    ((char.to_ascii_uppercase() as u8) & 0x1f) as char
+}
+
+fn parse_escaped_chars(text: &str) -> Vec<char> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = vec![];
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let char = chars[idx];
+        if char != '\\' {
+            result.push(char);
+            idx += 1;
+            continue;
+        }
+
+        let Some(escape) = chars.get(idx + 1).copied() else {
+            result.push('\\');
+            break;
+        };
+        idx += 2;
+
+        match escape {
+            'C' | 'M' if chars.get(idx) == Some(&'-') => {
+                idx += 1;
+                if escape == 'M' {
+                    // meta 는 ESC 를 앞에 붙인 것과 같음, 뒤의 문자는 다음 차례에 처리
+                    result.push('\x1b');
+                    continue;
+                }
+                let Some(target) = chars.get(idx).copied() else {
+                    break;
+                };
+                result.push(get_control_char(target));
+                idx += 1;
+            }
+            'e' => result.push('\x1b'),
+            'a' => result.push('\x07'),
+            'b' => result.push('\x08'),
+            'd' => result.push('\x7f'),
+            'f' => result.push('\x0c'),
+            'n' => result.push('\n'),
+            'r' => result.push('\r'),
+            't' => result.push('\t'),
+            'v' => result.push('\x0b'),
+            '0'..='7' => {
+                let mut octal = escape.to_string();
+                while octal.len() < 3 && let Some(digit @ '0'..='7') = chars.get(idx).copied() {
+                    octal.push(digit);
+                    idx += 1;
+                }
+                if let Some(octal_char) = u32::from_str_radix(&octal, 8).ok().and_then(char::from_u32) {
+                    result.push(octal_char);
+                }
+            }
+            'x' => {
+                let mut hex = String::new();
+                while hex.len() < 2 && let Some(digit) = chars.get(idx).copied().filter(|c| c.is_ascii_hexdigit()) {
+                    hex.push(digit);
+                    idx += 1;
+                }
+                if let Some(hex_char) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    result.push(hex_char);
+                }
+            }
+            // \\ \" \' 등은 문자 그대로
+            _ => result.push(escape),
+        }
+    }
+
+    result
+}
+
+// ESC 로 시작하는 sequence 는 rustyline 이 읽는 key 로 바꿈 (ESC + 문자는 Meta)
+fn chars_to_keys(chars: &[char]) -> Result<Vec<KeyEvent>, String> {
+    let mut keys = vec![];
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        if chars[idx] != '\x1b' {
+            keys.push(KeyEvent::normalize(KeyEvent::new(chars[idx], Modifiers::NONE)));
+            idx += 1;
+            continue;
+        }
+
+        match chars.get(idx + 1).copied() {
+            Some(intro @ ('[' | 'O')) => {
+                let (key, len) = parse_escape_sequence(&chars[idx + 2..], intro)?;
+                keys.push(key);
+                idx += len + 2;
+            }
+            Some(char) => {
+                keys.push(KeyEvent::normalize(KeyEvent::new(char, Modifiers::ALT)));
+                idx += 2;
+            }
+            None => {
+                keys.push(KeyEvent(KeyCode::Esc, Modifiers::NONE));
+                idx += 1;
+            }
+        }
+    }
+
+    Ok(keys)
+}
+
+// ESC [ 혹은 ESC O 뒤의 parameter 와 final byte, (key, 사용한 문자 개수)
+fn parse_escape_sequence(chars: &[char], intro: char) -> Result<(KeyEvent, usize), String> {
+    let Some(final_offset) = chars.iter().position(|c| false == (c.is_ascii_digit() || *c == ';')) else {
+        return Err("incomplete escape sequence".to_string());
+    };
+    let params: String = chars[..final_offset].iter().collect();
+    let mut params = params.split(';').map(|param| param.parse::<u8>().unwrap_or(1));
+    let number = params.next().unwrap_or(1);
+    // 1;5C 같은 xterm modifier : 1 + (shift 1, alt 2, ctrl 4)
+    let modifier_bits = params.next().map(|modifier| modifier.saturating_sub(1)).unwrap_or(0);
+    let mut modifiers = Modifiers::NONE;
+    if modifier_bits & 1 != 0 {
+        modifiers |= Modifiers::SHIFT;
+    }
+    if modifier_bits & 2 != 0 {
+        modifiers |= Modifiers::ALT;
+    }
+    if modifier_bits & 4 != 0 {
+        modifiers |= Modifiers::CTRL;
+    }
+
+    let key_code = match (chars[final_offset], number) {
+        ('A', _) => KeyCode::Up,
+        ('B', _) => KeyCode::Down,
+        ('C', _) => KeyCode::Right,
+        ('D', _) => KeyCode::Left,
+        ('H', _) | ('~', 1 | 7) => KeyCode::Home,
+        ('F', _) | ('~', 4 | 8) => KeyCode::End,
+        ('Z', _) if intro == '[' => KeyCode::BackTab,
+        ('P', _) => KeyCode::F(1),
+        ('Q', _) => KeyCode::F(2),
+        ('R', _) => KeyCode::F(3),
+        ('S', _) => KeyCode::F(4),
+        ('~', 2) => KeyCode::Insert,
+        ('~', 3) => KeyCode::Delete,
+        ('~', 5) => KeyCode::PageUp,
+        ('~', 6) => KeyCode::PageDown,
+        ('~', 15) => KeyCode::F(5),
+        ('~', number @ 17..=21) => KeyCode::F(number - 11),
+        ('~', number @ 23..=24) => KeyCode::F(number - 12),
+        _ => {
+            let sequence: String = chars[..=final_offset].iter().collect();
+            return Err(format!("\\e{}{}: unsupported escape sequence", intro, sequence));
+        }
+    };
+
+    Ok((KeyEvent(key_code, modifiers), final_offset + 1))
+}
@@ -1 +1,14 @@
+pub mod candidate_annotation;
+pub mod completion;
+pub mod completion_display;
+pub mod completion_providers;
+pub mod fuzzy;
+pub mod git_prompt;
+pub mod git_repository;
+pub mod history_search;
+pub mod key_binding;
+pub mod programmable_completion;
+pub mod prompt;
+pub mod shell_helper;
+pub mod syntax_highlight;
 pub mod tab_handler;
\ No newline at end of file
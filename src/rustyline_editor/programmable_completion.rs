@@ -0,0 +1,216 @@
+// complete builtin 으로 등록한 CompletionSpec 으로 후보 생성
+//   -W  : 단어 목록을 확장한 뒤 현재 단어로 시작하는 것
+//   -F  : COMP_WORDS, COMP_CWORD, COMP_LINE, COMP_POINT 를 설정하고 함수(command, 현재 단어, 이전 단어) 호출 후 COMPREPLY
+//   -C  : command, 현재 단어, 이전 단어를 인자로 외부 command 실행 후 stdout 의 각 줄
+//   -A  : directory, file, variable, job, user, hostname, command, builtin, function
+//   -X  : 후보 필터 (디렉토리는 계속 이어서 입력할 수 있게 남겨둠)
+//   -o  : default, dirnames 는 후보가 없을 때 파일/디렉토리로, filenames 는 디렉토리 뒤에 /
+// -F 의 COMPREPLY 와 -C 의 출력은 "후보\t설명" 형태로 설명을 붙일 수 있음 (목록을 출력할 때 표시)
+
+use std::{fs, path::Path, process::{Command, Stdio}};
+
+use crate::rustyline_editor::candidate_annotation::split_candidate_description;
+use crate::rustyline_editor::completion::{CompletionWord, get_path_candidates, get_variable_candidates};
+use crate::shell_parser::expansion::expand_command_line;
+use crate::shell_parser::pattern::glob_match;
+use crate::shell_state::completion_specs::{CompletionAction, CompletionSpec};
+use crate::shell_state::functions::{get_function, get_function_names};
+use crate::shell_state::variables::{get_array_variable, set_array_variable, set_variable};
+
+pub struct CompletionRequest<'a> {
+    // 전체 line 과 cursor 위치 (COMP_LINE, COMP_POINT)
+    pub line: &'a str,
+    pub pos: usize,
+    pub completion_word: &'a CompletionWord,
+}
+
+impl CompletionRequest<'_> {
+    fn command_name(&self) -> &str {
+        self.completion_word.previous_words.first().map(|word| word.as_str()).unwrap_or("")
+    }
+
+    fn previous_word(&self) -> &str {
+        self.completion_word.previous_words.last().map(|word| word.as_str()).unwrap_or("")
+    }
+}
+
+pub fn get_spec_candidates(completion_spec: &CompletionSpec, completion_request: &CompletionRequest) -> Vec<String> {
+    let word = completion_request.completion_word.text.as_str();
+    let mut candidates: Vec<String> = vec![];
+
+    for action in &completion_spec.actions {
+        candidates.extend(get_action_candidates(*action, word));
+    }
+
+    if let Some(word_list) = &completion_spec.word_list {
+        candidates.extend(get_word_list_candidates(word_list, word));
+    }
+
+    if let Some(function_name) = &completion_spec.function {
+        candidates.extend(get_function_candidates(function_name, completion_request));
+    }
+
+    if let Some(command) = &completion_spec.command {
+        candidates.extend(get_command_candidates(command, completion_request));
+    }
+
+    if let Some(filter_pattern) = &completion_spec.filter_pattern {
+        candidates = filter_candidates(candidates, filter_pattern);
+    }
+
+    if completion_spec.has_option("filenames") {
+        for candidate in candidates.iter_mut() {
+            if false == candidate.ends_with('/') && Path::new(candidate.as_str()).is_dir() {
+                candidate.push('/');
+            }
+        }
+    }
+
+    // 후보가 없으면 기본 completion 으로
+    if candidates.is_empty() {
+        if completion_spec.has_option("dirnames") {
+            candidates = get_directory_candidates(word);
+        } else if completion_spec.has_option("default") {
+            candidates = get_path_candidates(word, false);
+        }
+    }
+
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+pub fn get_action_candidates(action: CompletionAction, word: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = match action {
+        CompletionAction::Builtin => crate::COMMAND.iter().map(|command| command.to_string()).collect(),
+        CompletionAction::Command => {
+            let mut commands = crate::get_all_executable_command();
+            commands.extend(get_function_names());
+            commands
+        }
+        CompletionAction::Directory => return get_directory_candidates(word),
+        CompletionAction::File => return get_path_candidates(word, false),
+        CompletionAction::Function => get_function_names(),
+        CompletionAction::Hostname => get_host_names(),
+        // job control 이 없어서 항상 비어있음
+        CompletionAction::Job => vec![],
+        CompletionAction::User => get_user_names(),
+        CompletionAction::Variable => {
+            return get_variable_candidates(&format!("${}", word))
+                .into_iter()
+                .map(|candidate| candidate[1..].to_string())
+                .collect();
+        }
+    };
+
+    candidates.retain(|candidate| candidate.starts_with(word));
+    candidates
+}
+
+fn get_directory_candidates(word: &str) -> Vec<String> {
+    get_path_candidates(word, false).into_iter().filter(|candidate| candidate.ends_with('/')).collect()
+}
+
+fn get_word_list_candidates(word_list: &str, word: &str) -> Vec<String> {
+    let word_list = match expand_command_line(word_list) {
+        Ok(expanded) => expanded,
+        Err(_) => return vec![],
+    };
+
+    crate::special_char_args_builder(&word_list).into_iter().filter(|candidate| candidate.starts_with(word)).collect()
+}
+
+fn get_function_candidates(function_name: &str, completion_request: &CompletionRequest) -> Vec<String> {
+    let Some(function_body) = get_function(function_name) else {
+        return vec![];
+    };
+
+    let completion_word = completion_request.completion_word;
+    let mut comp_words = completion_word.previous_words.to_owned();
+    comp_words.push(completion_word.text.to_owned());
+
+    set_array_variable("COMP_WORDS", comp_words);
+    set_variable("COMP_CWORD", &completion_word.previous_words.len().to_string());
+    set_variable("COMP_LINE", completion_request.line);
+    set_variable("COMP_POINT", &completion_request.pos.to_string());
+    set_array_variable("COMPREPLY", vec![]);
+
+    let args = vec![
+        completion_request.command_name().to_string(),
+        completion_word.text.to_owned(),
+        completion_request.previous_word().to_string(),
+    ];
+    crate::call_function(&function_body, args);
+
+    get_array_variable("COMPREPLY").unwrap_or_default()
+}
+
+fn get_command_candidates(command: &str, completion_request: &CompletionRequest) -> Vec<String> {
+    let output = Command::new(command)
+        .arg(completion_request.command_name())
+        .arg(&completion_request.completion_word.text)
+        .arg(completion_request.previous_word())
+        .env("COMP_LINE", completion_request.line)
+        .env("COMP_POINT", completion_request.pos.to_string())
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output();
+
+    let Ok(output) = output else {
+        return vec![];
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| false == line.is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
+
+// -X pattern 에 매칭되는 후보 제외, !pattern 이면 매칭되지 않는 후보 제외
+fn filter_candidates(candidates: Vec<String>, filter_pattern: &str) -> Vec<String> {
+    let (pattern, is_negated) = match filter_pattern.strip_prefix('!') {
+        Some(pattern) => (pattern, true),
+        None => (filter_pattern, false),
+    };
+
+    candidates
+        .into_iter()
+        .filter(|candidate| {
+            // 설명은 빼고 후보만 매칭
+            let (text, _) = split_candidate_description(candidate);
+            if text.ends_with('/') {
+                return true;
+            }
+            glob_match(pattern, text) == is_negated
+        })
+        .collect()
+}
+
+fn get_user_names() -> Vec<String> {
+    let Ok(contents) = fs::read_to_string("/etc/passwd") else {
+        return vec![];
+    };
+
+    contents
+        .lines()
+        .filter(|line| false == line.starts_with('#'))
+        .filter_map(|line| line.split(':').next())
+        .filter(|name| false == name.is_empty())
+        .map(|name| name.to_string())
+        .collect()
+}
+
+// /etc/hosts 의 ip 뒤쪽 이름들
+fn get_host_names() -> Vec<String> {
+    let Ok(contents) = fs::read_to_string("/etc/hosts") else {
+        return vec![];
+    };
+
+    let mut host_names = vec![];
+    for line in contents.lines() {
+        let line = line.split('#').next().unwrap_or("");
+        host_names.extend(line.split_whitespace().skip(1).map(|name| name.to_string()));
+    }
+    host_names
+}
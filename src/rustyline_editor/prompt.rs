@@ -0,0 +1,251 @@
+// PS1, PS2, PS4 prompt 확장 (매 prompt 마다 다시 평가)
+//   \u user, \h host (첫번째 . 앞), \H host, \w 현재 디렉토리 (HOME 은 ~), \W 현재 디렉토리 이름
+//   \$ root 면 #, 아니면 $   \? 마지막 exit status   \j job 개수   \! history 번호   \s shell 이름
+//   \t HH:MM:SS, \T hh:MM:SS, \@ hh:MM AM, \A HH:MM, \d "Tue May 26", \D{format} strftime
+//   \g git branch 와 상태 (git_prompt)
+//   \n 줄바꿈, \e ESC, \a BEL, \\ 역슬래시, \nnn 8진수 문자
+//   \[ \] 화면에 표시되지 않는 부분 (색상 등)
+//   $NAME, ${NAME}, $? 변수 확장과 $(command), `command` 명령어 치환
+
+use std::{env, ffi::CString};
+
+use crate::rustyline_editor::git_prompt::get_git_prompt_segment;
+use crate::shell_builtin::cd_command::get_logical_current_dir;
+use crate::shell_parser::expansion::expand_dollar;
+use crate::shell_state::history::get_history_entries;
+use crate::shell_state::variables::get_variable;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExpandedPrompt {
+    // readline 에 넘길 prompt
+    pub text: String,
+    // \[ \] 안에서 CSI(ESC [) 가 아닌 sequence (터미널 제목 설정 같은 OSC)
+    // rustyline 은 CSI 만 너비 계산에서 제외하기 때문에 prompt 에 넣지 않고 먼저 출력
+    pub invisible: String,
+}
+
+// name 변수가 없으면 default 를 사용
+pub fn get_prompt(name: &str, default: &str) -> ExpandedPrompt {
+    let prompt = get_variable(name).unwrap_or_else(|| default.to_string());
+    expand_prompt(&prompt)
+}
+
+pub fn expand_prompt(prompt: &str) -> ExpandedPrompt {
+    let chars: Vec<char> = prompt.chars().collect();
+    let mut expanded_prompt = ExpandedPrompt::default();
+    // \[ 시작 위치의 text 길이, \] 에서 CSI 가 아닌 부분을 invisible 로 옮김
+    let mut non_printing_start = None;
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let char = chars[idx];
+
+        if char == '$' || char == '`' {
+            if let Some((value, next_idx)) = expand_prompt_substitution(&chars, idx) {
+                expanded_prompt.text.push_str(&value);
+                idx = next_idx;
+                continue;
+            }
+            expanded_prompt.text.push(char);
+            idx += 1;
+            continue;
+        }
+
+        if char != '\\' {
+            expanded_prompt.text.push(char);
+            idx += 1;
+            continue;
+        }
+
+        let Some(escape) = chars.get(idx + 1).copied() else {
+            expanded_prompt.text.push('\\');
+            break;
+        };
+        idx += 2;
+
+        match escape {
+            'u' => expanded_prompt.text.push_str(&get_user_name()),
+            'h' => expanded_prompt.text.push_str(get_host_name().split('.').next().unwrap_or("")),
+            'H' => expanded_prompt.text.push_str(&get_host_name()),
+            'w' => expanded_prompt.text.push_str(&get_working_directory(false)),
+            'W' => expanded_prompt.text.push_str(&get_working_directory(true)),
+            '$' => expanded_prompt.text.push(if unsafe { libc::geteuid() } == 0 { '#' } else { '$' }),
+            '?' => expanded_prompt.text.push_str(&get_variable("?").unwrap_or_default()),
+            // job control 이 없어서 항상 0
+            'j' => expanded_prompt.text.push('0'),
+            '!' => expanded_prompt.text.push_str(&(get_history_entries().len() + 1).to_string()),
+            'g' => expanded_prompt.text.push_str(&get_git_prompt_segment()),
+            's' => {
+                let shell_name = env::args().next().unwrap_or_default();
+                expanded_prompt.text.push_str(shell_name.rsplit('/').next().unwrap_or(""));
+            }
+            't' => expanded_prompt.text.push_str(&format_local_time("%H:%M:%S")),
+            'T' => expanded_prompt.text.push_str(&format_local_time("%I:%M:%S")),
+            '@' => expanded_prompt.text.push_str(&format_local_time("%I:%M %p")),
+            'A' => expanded_prompt.text.push_str(&format_local_time("%H:%M")),
+            'd' => expanded_prompt.text.push_str(&format_local_time("%a %b %d")),
+            'D' if chars.get(idx) == Some(&'{') => {
+                let Some(close_offset) = chars[idx..].iter().position(|c| *c == '}') else {
+                    expanded_prompt.text.push_str("\\D");
+                    continue;
+                };
+                let format: String = chars[idx + 1..idx + close_offset].iter().collect();
+                // \D{} 는 locale 의 시간 형식
+                let format = if format.is_empty() { "%X".to_string() } else { format };
+                expanded_prompt.text.push_str(&format_local_time(&format));
+                idx += close_offset + 1;
+            }
+            'n' => expanded_prompt.text.push('\n'),
+            'e' => expanded_prompt.text.push('\x1b'),
+            'a' => expanded_prompt.text.push('\x07'),
+            '\\' => expanded_prompt.text.push('\\'),
+            '0'..='7' => {
+                // \033 처럼 최대 3자리
+                let mut octal = escape.to_string();
+                while octal.len() < 3 && let Some(digit @ '0'..='7') = chars.get(idx).copied() {
+                    octal.push(digit);
+                    idx += 1;
+                }
+                if let Some(octal_char) = u32::from_str_radix(&octal, 8).ok().and_then(char::from_u32) {
+                    expanded_prompt.text.push(octal_char);
+                }
+            }
+            '[' => non_printing_start = Some(expanded_prompt.text.len()),
+            ']' => {
+                if let Some(start) = non_printing_start.take() {
+                    let non_printing = expanded_prompt.text.split_off(start);
+                    let (csi, invisible) = split_csi_sequences(&non_printing);
+                    expanded_prompt.text.push_str(&csi);
+                    expanded_prompt.invisible.push_str(&invisible);
+                }
+            }
+            _ => {
+                expanded_prompt.text.push('\\');
+                expanded_prompt.text.push(escape);
+            }
+        }
+    }
+
+    expanded_prompt
+}
+
+// $(command), `command`, $NAME 확장, 확장 대상이 아니면 None
+fn expand_prompt_substitution(chars: &[char], idx: usize) -> Option<(String, usize)> {
+    let (command, next_idx) = if chars[idx] == '`' {
+        let close_offset = chars[idx + 1..].iter().position(|c| *c == '`')?;
+        (chars[idx + 1..idx + 1 + close_offset].iter().collect::<String>(), idx + 2 + close_offset)
+    } else if chars.get(idx + 1) == Some(&'(') && chars.get(idx + 2) != Some(&'(') {
+        let close_idx = find_substitution_end(chars, idx + 2)?;
+        (chars[idx + 2..close_idx].iter().collect::<String>(), close_idx + 1)
+    } else {
+        return expand_dollar(chars, idx).ok().flatten();
+    };
+
+    Some((crate::capture_command_output(&command), next_idx))
+}
+
+// $( 다음부터 짝이 맞는 ) 의 인덱스
+fn find_substitution_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+
+    for (idx, char) in chars.iter().enumerate().skip(start) {
+        match (quote, char) {
+            (Some(quote_char), _) if *char == quote_char => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(*char),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => return Some(idx),
+            (None, ')') => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// ESC [ ... 로 시작하는 CSI sequence 와 나머지로 나눔
+fn split_csi_sequences(non_printing: &str) -> (String, String) {
+    let mut csi = String::new();
+    let mut invisible = String::new();
+    let mut chars = non_printing.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if char == '\x1b' && chars.peek() == Some(&'[') {
+            csi.push(char);
+            // 0x40 ~ 0x7e 의 final byte 까지
+            for csi_char in chars.by_ref() {
+                csi.push(csi_char);
+                if csi_char != '[' && ('\x40'..='\x7e').contains(&csi_char) {
+                    break;
+                }
+            }
+            continue;
+        }
+        invisible.push(char);
+    }
+
+    (csi, invisible)
+}
+
+fn get_user_name() -> String {
+    if let Some(user) = get_variable("USER") {
+        return user;
+    }
+
+    let passwd = unsafe { libc::getpwuid(libc::geteuid()) };
+    if passwd.is_null() {
+        return String::new();
+    }
+    unsafe { std::ffi::CStr::from_ptr((*passwd).pw_name) }.to_string_lossy().into_owned()
+}
+
+fn get_host_name() -> String {
+    let mut buffer = [0u8; 256];
+    if unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len()) } != 0 {
+        return String::new();
+    }
+    let len = buffer.iter().position(|byte| *byte == 0).unwrap_or(buffer.len());
+    String::from_utf8_lossy(&buffer[..len]).into_owned()
+}
+
+// symlink 를 따라 들어온 경로 그대로, HOME 아래는 ~ 로 줄여서, is_basename 이면 마지막 요소만
+fn get_working_directory(is_basename: bool) -> String {
+    let current_dir = get_logical_current_dir().to_string_lossy().into_owned();
+    let home = get_variable("HOME").unwrap_or_default();
+
+    if false == home.is_empty() && current_dir == home.trim_end_matches('/') {
+        return "~".to_string();
+    }
+    if is_basename {
+        if current_dir == "/" {
+            return current_dir;
+        }
+        return current_dir.rsplit('/').next().unwrap_or("").to_string();
+    }
+    if false == home.is_empty()
+        && let Some(rest) = current_dir.strip_prefix(&format!("{}/", home.trim_end_matches('/'))) {
+        return format!("~/{}", rest);
+    }
+
+    current_dir
+}
+
+fn format_local_time(format: &str) -> String {
+    format_timestamp(unsafe { libc::time(std::ptr::null_mut()) }, format)
+}
+
+// unix time 을 지역 시간의 strftime 형식으로
+pub fn format_timestamp(timestamp: i64, format: &str) -> String {
+    let Ok(format) = CString::new(format) else {
+        return String::new();
+    };
+
+    let mut buffer = [0u8; 256];
+    let len = unsafe {
+        let mut local_time: libc::tm = std::mem::zeroed();
+        libc::localtime_r(&timestamp, &mut local_time);
+        libc::strftime(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len(), format.as_ptr(), &local_time)
+    };
+    String::from_utf8_lossy(&buffer[..len]).into_owned()
+}
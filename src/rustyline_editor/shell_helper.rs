@@ -0,0 +1,112 @@
+// rustyline Editor 의 Helper
+// TAB 은 MyTabHandler 가 처리하고, Completer 는 menu 에서 선택한 후보로 단어를 교체할 때만 사용
+// (Cmd::Replace 는 cursor 를 교체한 문자열 앞에 남겨서, 교체 후 cursor 를 뒤로 옮기는 Cmd::Complete 를 사용)
+// Highlighter 는 syntax_highlight 로 입력 중인 line 에 색을 넣음
+// Hinter 는 cursor 가 line 끝에 있을 때 history (set -o autosuggestpath 면 path 도) 에서 찾은 나머지를 흐리게 표시
+//   → 나 End 로 받아들임
+// Validator 는 쿼터나 if 등이 닫히지 않았으면 Enter 에 실행하지 않고 개행을 넣어서 다음 줄을 이어서 입력 받음
+//   (rustyline 은 이어지는 줄에 PS2 prompt 를 표시하지 못함)
+
+use std::{borrow::Cow, cell::Cell};
+
+use rustyline::{Cmd, ConditionalEventHandler, Context, Event, EventContext, Helper, RepeatCount, completion::Completer, highlight::{CmdKind, Highlighter}, hint::Hinter, validate::{ValidationContext, ValidationResult, Validator}};
+
+use crate::rustyline_editor::completion::{escape_completion, get_path_candidates, is_path_word, parse_completion_word};
+use crate::rustyline_editor::syntax_highlight::{highlight_line, is_color_enabled};
+use crate::rustyline_editor::tab_handler::take_menu_completion;
+use crate::shell_parser::input_completeness::{InputCompleteness, check_input_completeness};
+use crate::shell_state::history::find_history_entry_by_prefix;
+use crate::shell_state::options::is_shell_option_enabled;
+
+#[derive(Debug, Default)]
+pub struct ShellHelper {
+    // Enter 후의 마지막 출력에서는 괄호 강조를 지움 (highlight_char 에서 설정)
+    is_matching_bracket: Cell<bool>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(&self, _line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        match take_menu_completion() {
+            Some((start, replacement)) => Ok((start, vec![replacement])),
+            None => Ok((pos, vec![])),
+        }
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        // 색이 없으면 입력한 부분과 구분되지 않아서 표시하지 않음
+        if pos < line.len() || line.trim().is_empty() || false == is_shell_option_enabled("autosuggest") || false == is_color_enabled() {
+            return None;
+        }
+
+        if let Some(entry) = find_history_entry_by_prefix(line) {
+            return Some(entry[line.len()..].to_string());
+        }
+
+        if is_shell_option_enabled("autosuggestpath") {
+            return get_path_hint(line);
+        }
+
+        None
+    }
+}
+
+// 현재 단어로 시작하는 첫번째 path 의 나머지 부분
+fn get_path_hint(line: &str) -> Option<String> {
+    let completion_word = parse_completion_word(line);
+    if completion_word.text.is_empty() || (completion_word.is_command_position && false == is_path_word(&completion_word.text)) {
+        return None;
+    }
+
+    get_path_candidates(&completion_word.text, false)
+        .into_iter()
+        .find(|candidate| candidate.len() > completion_word.text.len() && candidate.starts_with(&completion_word.text))
+        .map(|candidate| escape_completion(&candidate[completion_word.text.len()..], completion_word.quote))
+}
+
+impl Highlighter for ShellHelper {
+    fn highlight<'l>(&self, line: &'l str, pos: usize) -> Cow<'l, str> {
+        if false == is_color_enabled() {
+            return Cow::Borrowed(line);
+        }
+        Cow::Owned(highlight_line(line, pos, self.is_matching_bracket.get()))
+    }
+
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        Cow::Owned(format!("\x1b[90m{}\x1b[0m", hint))
+    }
+
+    // 입력한 문자에 따라 command 색이 바뀌기 때문에 항상 다시 그림
+    fn highlight_char(&self, _line: &str, _pos: usize, kind: CmdKind) -> bool {
+        self.is_matching_bracket.set(kind != CmdKind::ForcedRefresh);
+        is_color_enabled()
+    }
+}
+
+impl Validator for ShellHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        match check_input_completeness(ctx.input()) {
+            InputCompleteness::Complete => Ok(ValidationResult::Valid(None)),
+            InputCompleteness::Incomplete => Ok(ValidationResult::Incomplete),
+        }
+    }
+}
+
+impl Helper for ShellHelper {}
+
+// End 는 hint 가 있고 cursor 가 line 끝이면 hint 를 받아들이고, 아니면 기본 동작 (→ 는 rustyline 이 처리)
+pub struct HintCompletionHandler;
+
+impl ConditionalEventHandler for HintCompletionHandler {
+    fn handle(&self, _evt: &Event, _n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        if ctx.has_hint() && ctx.pos() == ctx.line().len() {
+            return Some(Cmd::CompleteHint);
+        }
+        None
+    }
+}
@@ -0,0 +1,410 @@
+// 입력 중인 line 의 syntax highlighting
+//   command  : 실행할 수 있으면 초록, 없으면 빨강 (함수, builtin, PATH 순서로 확인)
+//   keyword  : if, for, while 같은 예약어
+//   string   : '...', "..."
+//   variable : $NAME, ${...}, $(...), $((...)), `...`
+//   operator : ; && || | & ( ) { }
+//   redirect : > >> < 2> 2>&1 &>
+//   comment  : # 부터 줄 끝까지
+// cursor 위치의 괄호나 쿼터는 짝이 되는 문자와 함께 강조
+// NO_COLOR 가 설정되어 있거나 stdout 이 터미널이 아니면 색을 넣지 않음
+
+use crate::shell_state::variables::{get_variable, is_valid_variable_name};
+
+const SHELL_KEYWORDS: [&str; 19] = ["if", "then", "else", "elif", "fi", "for", "in", "while", "until", "do", "done", "case", "esac", "function", "select", "time", "!", "[[", "]]"];
+// 다음 단어가 command 가 아닌 keyword (for NAME in ...)
+const ARGUMENT_KEYWORDS: [&str; 5] = ["for", "in", "case", "select", "function"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HighlightStyle {
+    ValidCommand,
+    InvalidCommand,
+    Keyword,
+    String,
+    Variable,
+    Operator,
+    Redirect,
+    Comment,
+}
+
+impl HighlightStyle {
+    fn ansi_code(&self) -> &'static str {
+        match self {
+            Self::ValidCommand => "32",
+            Self::InvalidCommand => "31",
+            Self::Keyword => "34",
+            Self::String => "33",
+            Self::Variable => "36",
+            Self::Operator => "35",
+            Self::Redirect => "1;35",
+            Self::Comment => "90",
+        }
+    }
+}
+
+const MATCHING_BRACKET_CODE: &str = "1;4";
+
+pub fn is_color_enabled() -> bool {
+    if get_variable("NO_COLOR").is_some_and(|no_color| false == no_color.is_empty()) {
+        return false;
+    }
+    if get_variable("TERM").is_some_and(|term| term == "dumb") {
+        return false;
+    }
+    unsafe { libc::isatty(libc::STDOUT_FILENO) == 1 }
+}
+
+// char 단위의 style 과 짝이 맞는 괄호, 쿼터의 (여는 위치, 닫는 위치)
+#[derive(Debug, Default)]
+struct LineHighlight {
+    styles: Vec<Option<HighlightStyle>>,
+    pairs: Vec<(usize, usize)>,
+}
+
+impl LineHighlight {
+    fn set_style(&mut self, start: usize, end: usize, style: HighlightStyle) {
+        for char_style in &mut self.styles[start..end] {
+            *char_style = Some(style);
+        }
+    }
+}
+
+// pos 는 byte 위치, is_matching_bracket 이 false 면 괄호 강조 안함 (Enter 후의 마지막 출력)
+pub fn highlight_line(line: &str, pos: usize, is_matching_bracket: bool) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let line_highlight = analyze_line(&chars);
+
+    // cursor 위치 또는 바로 앞의 문자가 괄호나 쿼터면 짝과 함께
+    let mut matching_idxs = vec![];
+    if is_matching_bracket {
+        let cursor_idx = line[..pos.min(line.len())].chars().count();
+        let find_pair = |idx: usize| line_highlight.pairs.iter().find(|(open_idx, close_idx)| *open_idx == idx || *close_idx == idx).copied();
+        let pair = find_pair(cursor_idx).or_else(|| cursor_idx.checked_sub(1).and_then(find_pair));
+        if let Some((open_idx, close_idx)) = pair {
+            matching_idxs = vec![open_idx, close_idx];
+        }
+    }
+
+    let mut highlighted = String::with_capacity(line.len() * 2);
+    let mut current_code: Option<String> = None;
+    for (idx, char) in chars.iter().enumerate() {
+        let mut codes = vec![];
+        if let Some(style) = line_highlight.styles[idx] {
+            codes.push(style.ansi_code());
+        }
+        if matching_idxs.contains(&idx) {
+            codes.push(MATCHING_BRACKET_CODE);
+        }
+        let code = if codes.is_empty() { None } else { Some(codes.join(";")) };
+
+        if code != current_code {
+            if current_code.is_some() {
+                highlighted.push_str("\x1b[0m");
+            }
+            if let Some(code) = &code {
+                highlighted.push_str(&format!("\x1b[{}m", code));
+            }
+            current_code = code;
+        }
+        highlighted.push(*char);
+    }
+    if current_code.is_some() {
+        highlighted.push_str("\x1b[0m");
+    }
+
+    highlighted
+}
+
+fn analyze_line(chars: &[char]) -> LineHighlight {
+    let mut line_highlight = LineHighlight {
+        styles: vec![None; chars.len()],
+        pairs: vec![],
+    };
+    // ( { 의 위치
+    let mut bracket_stack: Vec<usize> = vec![];
+    let mut is_command_position = true;
+    let mut is_redirect_target = false;
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let char = chars[idx];
+
+        if char.is_whitespace() {
+            if char == '\n' {
+                is_command_position = true;
+            }
+            idx += 1;
+            continue;
+        }
+
+        // 단어의 시작에서만 주석
+        if char == '#' {
+            line_highlight.set_style(idx, chars.len(), HighlightStyle::Comment);
+            break;
+        }
+
+        if let Some(operator_len) = get_operator_len(chars, idx) {
+            line_highlight.set_style(idx, idx + operator_len, HighlightStyle::Operator);
+            match char {
+                '(' | '{' => bracket_stack.push(idx),
+                ')' | '}' => {
+                    if let Some(open_idx) = bracket_stack.pop() {
+                        line_highlight.pairs.push((open_idx, idx));
+                    }
+                }
+                _ => {}
+            }
+            // f() 의 () 뒤에 오는 { 도 command 위치
+            is_command_position = true;
+            is_redirect_target = false;
+            idx += operator_len;
+            continue;
+        }
+
+        if let Some(redirect_len) = get_redirect_len(chars, idx) {
+            line_highlight.set_style(idx, idx + redirect_len, HighlightStyle::Redirect);
+            // 2>&1 처럼 fd 로 redirect 하면 대상 단어가 없음
+            let redirect = &chars[idx..idx + redirect_len];
+            is_redirect_target = redirect.len() < 2 || redirect[redirect.len() - 2] != '&';
+            idx += redirect_len;
+            continue;
+        }
+
+        // 단어
+        let word_start = idx;
+        let word_end = scan_word(chars, idx, &mut line_highlight);
+        let word: String = chars[word_start..word_end].iter().collect();
+        idx = word_end;
+
+        if is_redirect_target {
+            is_redirect_target = false;
+            continue;
+        }
+        if false == is_command_position {
+            continue;
+        }
+
+        // NAME=value 뒤에는 command 가 올 수 있음
+        if let Some((name, _)) = word.split_once('=')
+            && is_valid_variable_name(name) {
+            line_highlight.set_style(word_start, word_start + name.chars().count(), HighlightStyle::Variable);
+            continue;
+        }
+
+        if SHELL_KEYWORDS.contains(&word.as_str()) {
+            line_highlight.set_style(word_start, word_end, HighlightStyle::Keyword);
+            is_command_position = false == ARGUMENT_KEYWORDS.contains(&word.as_str());
+            continue;
+        }
+
+        is_command_position = false;
+        // $EDITOR 처럼 확장해야 알 수 있는 command 는 그대로
+        if word.contains('$') || word.contains('`') {
+            continue;
+        }
+        let style = if crate::is_command_executable(&unquote_word(&word)) {
+            HighlightStyle::ValidCommand
+        } else {
+            HighlightStyle::InvalidCommand
+        };
+        // 쿼터와 변수 style 은 덮어쓰지 않음
+        for char_style in &mut line_highlight.styles[word_start..word_end] {
+            if char_style.is_none() {
+                *char_style = Some(style);
+            }
+        }
+    }
+
+    line_highlight
+}
+
+// ; && || | & ( ) 와 단어 시작의 { }
+fn get_operator_len(chars: &[char], idx: usize) -> Option<usize> {
+    let char = chars[idx];
+    let next_char = chars.get(idx + 1).copied();
+
+    match (char, next_char) {
+        ('&', Some('&')) | ('|', Some('|')) | (';', Some(';')) => Some(2),
+        // &> 는 redirect
+        ('&', Some('>')) => None,
+        (';' | '|' | '&' | '(' | ')', _) => Some(1),
+        // { 와 } 는 단독 단어일 때만 (a{b} 는 단어의 일부)
+        ('{' | '}', None) => Some(1),
+        ('{' | '}', Some(next_char)) if next_char.is_whitespace() || next_char == ';' => Some(1),
+        _ => None,
+    }
+}
+
+// >, >>, <, <<, <<<, 2>, 2>>, 2>&1, >&2, &>
+fn get_redirect_len(chars: &[char], idx: usize) -> Option<usize> {
+    let mut end = idx;
+    if chars[end] == '&' && chars.get(end + 1) == Some(&'>') {
+        end += 1;
+    } else {
+        while chars.get(end).is_some_and(|char| char.is_ascii_digit()) {
+            end += 1;
+        }
+    }
+
+    let redirect_char = *chars.get(end)?;
+    if redirect_char != '>' && redirect_char != '<' {
+        return None;
+    }
+    end += 1;
+    while chars.get(end) == Some(&redirect_char) && end - idx < 3 {
+        end += 1;
+    }
+
+    // >&2, 2>&1
+    if chars.get(end) == Some(&'&') && chars.get(end + 1).is_some_and(|char| char.is_ascii_digit() || *char == '-') {
+        end += 2;
+    }
+
+    Some(end - idx)
+}
+
+// 단어 끝 인덱스, 안쪽의 쿼터와 변수에 style 을 붙임
+fn scan_word(chars: &[char], start: usize, line_highlight: &mut LineHighlight) -> usize {
+    let mut idx = start;
+
+    while idx < chars.len() {
+        let char = chars[idx];
+        if char.is_whitespace() || matches!(char, ';' | '|' | '&' | '(' | ')' | '<' | '>') {
+            break;
+        }
+
+        match char {
+            '\\' => idx += 2,
+            '\'' => {
+                let close_idx = chars[idx + 1..].iter().position(|c| *c == '\'').map(|offset| idx + 1 + offset);
+                let end = close_idx.map(|close_idx| close_idx + 1).unwrap_or(chars.len());
+                line_highlight.set_style(idx, end, HighlightStyle::String);
+                if let Some(close_idx) = close_idx {
+                    line_highlight.pairs.push((idx, close_idx));
+                }
+                idx = end;
+            }
+            '"' => idx = scan_double_quote(chars, idx, line_highlight),
+            '$' | '`' => idx = scan_expansion(chars, idx, line_highlight),
+            _ => idx += 1,
+        }
+    }
+
+    idx.min(chars.len())
+}
+
+fn scan_double_quote(chars: &[char], start: usize, line_highlight: &mut LineHighlight) -> usize {
+    let mut idx = start + 1;
+    let mut close_idx = None;
+
+    while idx < chars.len() {
+        match chars[idx] {
+            '\\' => idx += 2,
+            '"' => {
+                close_idx = Some(idx);
+                break;
+            }
+            '$' | '`' => {
+                // 변수 style 이 덮어쓰지 않게 나중에 string style 을 빈 곳에만
+                idx = scan_expansion(chars, idx, line_highlight);
+            }
+            _ => idx += 1,
+        }
+    }
+
+    let end = close_idx.map(|close_idx| close_idx + 1).unwrap_or(chars.len());
+    for char_style in &mut line_highlight.styles[start..end] {
+        if char_style.is_none() {
+            *char_style = Some(HighlightStyle::String);
+        }
+    }
+    if let Some(close_idx) = close_idx {
+        line_highlight.pairs.push((start, close_idx));
+    }
+    end
+}
+
+// $NAME, ${...}, $(...), $((...)), `...` 의 끝 인덱스
+fn scan_expansion(chars: &[char], start: usize, line_highlight: &mut LineHighlight) -> usize {
+    let (end, close_pairs) = match (chars[start], chars.get(start + 1).copied()) {
+        ('`', _) => match chars[start + 1..].iter().position(|c| *c == '`') {
+            Some(offset) => (start + offset + 2, vec![(start, start + offset + 1)]),
+            None => (chars.len(), vec![]),
+        },
+        ('$', Some('(')) => match find_close_paren(chars, start + 1) {
+            Some(close_idx) => {
+                let mut close_pairs = vec![(start + 1, close_idx)];
+                // $(( )) 는 안쪽 괄호도
+                if chars.get(start + 2) == Some(&'(') && close_idx > start + 2 && chars[close_idx - 1] == ')' {
+                    close_pairs.push((start + 2, close_idx - 1));
+                }
+                (close_idx + 1, close_pairs)
+            }
+            None => (chars.len(), vec![]),
+        },
+        ('$', Some('{')) => match chars[start + 2..].iter().position(|c| *c == '}') {
+            Some(offset) => (start + offset + 3, vec![(start + 1, start + offset + 2)]),
+            None => (chars.len(), vec![]),
+        },
+        ('$', Some('?' | '#' | '@' | '*' | '$' | '!' | '0'..='9')) => (start + 2, vec![]),
+        ('$', _) => {
+            let name_len = chars[start + 1..].iter().take_while(|c| c.is_ascii_alphanumeric() || **c == '_').count();
+            // $ 뒤에 이름이 없으면 그냥 문자
+            if name_len == 0 {
+                return start + 1;
+            }
+            (start + 1 + name_len, vec![])
+        }
+        _ => (start + 1, vec![]),
+    };
+
+    line_highlight.set_style(start, end, HighlightStyle::Variable);
+    line_highlight.pairs.extend(close_pairs);
+    end
+}
+
+// ( 의 인덱스부터 짝이 맞는 ) 의 인덱스
+fn find_close_paren(chars: &[char], open_idx: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut idx = open_idx;
+
+    while idx < chars.len() {
+        let char = chars[idx];
+        match (quote, char) {
+            (_, '\\') => idx += 1,
+            (Some(quote_char), _) if char == quote_char => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(char),
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    None
+}
+
+// 쿼터와 \ 를 벗겨낸 command 이름
+fn unquote_word(word: &str) -> String {
+    let mut unquoted = String::with_capacity(word.len());
+    let mut quote = None;
+    let mut chars = word.chars();
+
+    while let Some(char) = chars.next() {
+        match (quote, char) {
+            (None, '\\') => unquoted.extend(chars.next()),
+            (None, '\'' | '"') => quote = Some(char),
+            (Some(quote_char), _) if char == quote_char => quote = None,
+            _ => unquoted.push(char),
+        }
+    }
+
+    unquoted
+}
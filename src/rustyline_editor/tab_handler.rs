@@ -1,21 +1,215 @@
-use std::{io::{self, Write}, sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}}};
+use std::{collections::BTreeMap, io::{self, Write}, sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}}};
 
-use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, RepeatCount};
+use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, Movement, RepeatCount};
+
+use crate::rustyline_editor::candidate_annotation::{CandidateKind, get_candidate_kind, get_command_descriptions, split_candidate_description};
+use crate::rustyline_editor::completion::{CompletionWord, escape_completion, get_candidate_display_name, get_path_candidates, get_variable_candidates, is_path_word, parse_completion_word};
+use crate::rustyline_editor::completion_display::{AnnotatedCandidate, confirm_display_all, print_annotated_candidate_list, select_from_menu};
+use crate::rustyline_editor::completion_providers::{CompletionProvider, get_bundled_providers, help_options::get_help_options};
+use crate::rustyline_editor::fuzzy::rank_fuzzy_candidates;
+use crate::rustyline_editor::programmable_completion::{CompletionRequest, get_spec_candidates};
+use crate::shell_state::command_hash::{get_executable_command_names, get_executable_path};
+use crate::shell_state::completion_specs::get_completion_spec;
+use crate::shell_state::options::is_shell_option_enabled;
+
+// menu 에서 선택한 후보 (교체 시작 byte 위치, 교체할 문자열), ShellHelper 의 Completer 가 가져감
+// bind -x 나 history 검색으로 바뀐 line 도 cursor 위치를 맞추기 위해 같은 방식으로 교체 (get_replace_line_cmd)
+static MENU_COMPLETION: Mutex<Option<(usize, String)>> = Mutex::new(None);
+
+pub fn set_menu_completion(start: usize, replacement: String) {
+    *MENU_COMPLETION.lock().unwrap() = Some((start, replacement));
+}
+
+pub fn take_menu_completion() -> Option<(usize, String)> {
+    MENU_COMPLETION.lock().unwrap().take()
+}
+
+// 입력중인 line 을 new_line 으로 바꾸고 cursor 를 new_pos 로 옮기는 Cmd
+// Cmd::Replace 는 cursor 를 맨 앞에 남기기 때문에 cursor 뒤쪽이 그대로면 completion 처럼 앞쪽만 교체
+pub fn get_replace_line_cmd(line: &str, pos: usize, new_line: &str, new_pos: usize) -> Cmd {
+    if new_line[new_pos..] == line[pos..] {
+        set_menu_completion(0, new_line[..new_pos].to_string());
+        return Cmd::Complete;
+    }
+    Cmd::Replace(Movement::WholeBuffer, Some(new_line.to_string()))
+}
+
+// handle 에서 사용하는 EventContext 의 일부, test 에서는 가짜 context 로 대체
+pub trait CompletionContext {
+    fn line(&self) -> &str;
+    fn pos(&self) -> usize;
+}
+
+impl CompletionContext for EventContext<'_> {
+    fn line(&self) -> &str {
+        EventContext::line(self)
+    }
+
+    fn pos(&self) -> usize {
+        EventContext::pos(self)
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct MyTabHandler {
     commands: Vec<String>,
+    providers: Vec<Box<dyn CompletionProvider>>,
     last_was_tab: AtomicBool, // complete 가 &self(불변 참조) 여서 AtomicBool 로 (EventHandler 가 Send + Sync 여서 Cell 사용 불가능)
     filtered_commands: Mutex<Vec<String>>, // complete 가 &self(불변 참조) 여서 Mutex 로 (EventHandler 가 Send + Sync 여서 RefCell 사용 불가능)
+    last_tab_line: Mutex<String>, // 이전 탭을 눌렀을 때의 line, 그 사이에 입력이 바뀌었으면 다시 필터
+    is_no_space: AtomicBool, // complete -o nospace 로 등록된 command 면 후보 뒤에 공백을 붙이지 않음
+    candidate_descriptions: Mutex<BTreeMap<String, String>>, // "후보\t설명" 으로 받은 설명, 목록을 출력할 때 사용
 }
 
 impl MyTabHandler {
     pub fn new(commands: impl IntoIterator<Item = impl Into<String>>) -> Self {
         Self {
             commands: commands.into_iter().map(Into::into).collect(),
+            providers: get_bundled_providers(),
             last_was_tab: AtomicBool::new(false),
-            filtered_commands: vec![].into()
+            filtered_commands: vec![].into(),
+            last_tab_line: String::new().into(),
+            is_no_space: AtomicBool::new(false),
+            candidate_descriptions: BTreeMap::new().into(),
+        }
+    }
+
+    // 후보에 붙은 설명은 따로 보관하고 후보 문자열만 반환
+    fn get_candidates(&self, completion_word: &CompletionWord, line: &str, pos: usize) -> Vec<String> {
+        let mut candidate_descriptions = self.candidate_descriptions.lock().unwrap();
+        candidate_descriptions.clear();
+
+        let mut candidates = vec![];
+        for candidate in self.collect_candidates(completion_word, line, pos) {
+            let (text, description) = split_candidate_description(&candidate);
+            if let Some(description) = description {
+                candidate_descriptions.entry(text.to_string()).or_insert_with(|| description.to_string());
+            }
+            candidates.push(text.to_string());
         }
+        candidates.dedup();
+        candidates
+    }
+
+    // command 위치면 command 이름
+    // 인자 위치면 complete 로 등록된 규칙, command 별 provider, --help 의 option, 파일/디렉토리 path 순서로 후보를 만든다
+    fn collect_candidates(&self, completion_word: &CompletionWord, line: &str, pos: usize) -> Vec<String> {
+        let word = &completion_word.text;
+        self.is_no_space.store(false, Ordering::Relaxed);
+
+        if word.starts_with('$') && false == word.contains('/') {
+            return get_variable_candidates(word);
+        }
+
+        if completion_word.is_command_position {
+            // ./run.sh, ~/bin/tool 처럼 경로로 입력하는 command
+            if is_path_word(word) {
+                return get_path_candidates(word, true);
+            }
+
+            // PATH 의 실행 파일은 새로 설치되거나 PATH 가 바뀔 수 있어서 매번 cache 에서
+            let mut commands: Vec<String> = self.commands.iter()
+                .map(|command| command.as_str())
+                .chain(get_executable_command_names().iter().map(|command| command.as_str()))
+                .filter(|command| command.starts_with(word.as_str()))
+                // owned
+                .map(|f| f.to_owned())
+                .collect();
+            commands.sort();
+            commands.dedup();
+            return commands;
+        }
+
+        if let Some(command_name) = completion_word.previous_words.first()
+            && let Some(completion_spec) = get_completion_spec(command_name) {
+            self.is_no_space.store(completion_spec.has_option("nospace"), Ordering::Relaxed);
+
+            let completion_request = CompletionRequest {
+                line,
+                pos,
+                completion_word,
+            };
+            return get_spec_candidates(&completion_spec, &completion_request);
+        }
+
+        if let Some(command_name) = completion_word.previous_words.first() {
+            let command_name = command_name.rsplit('/').next().unwrap_or(command_name);
+            let provider = self.providers.iter().find(|provider| provider.command_name() == command_name);
+            if let Some(provider) = provider
+                && let Some(candidates) = provider.get_candidates(completion_word) {
+                return candidates;
+            }
+
+            // PATH 에 있는 command 만 실행
+            if word.starts_with("--") && get_executable_path(command_name).is_some() {
+                return get_help_options(command_name, &["--help"], word);
+            }
+        }
+
+        get_path_candidates(word, false)
+    }
+
+    // set -o menucomplete : path 는 마지막 요소, $NAME 은 이름 부분을 fuzzy 로 매칭하고 여러개면 선택 menu
+    fn handle_menu_completion(&self, ctx: &impl CompletionContext, completion_word: &CompletionWord) -> Cmd {
+        let line = &ctx.line()[..ctx.pos()];
+        let word = completion_word.text.as_str();
+
+        let base_len = if word.starts_with('$') && false == word.contains('/') {
+            1
+        } else {
+            word.rfind('/').map(|slash_idx| slash_idx + 1).unwrap_or(0)
+        };
+        let (base, query) = word.split_at(base_len);
+
+        // 입력한 prefix 로 거르지 않은 후보에서 fuzzy 매칭
+        let base_word = CompletionWord {
+            text: base.to_string(),
+            ..completion_word.clone()
+        };
+        let candidates = self.get_candidates(&base_word, ctx.line(), ctx.pos());
+        let candidates = rank_fuzzy_candidates(query, candidates, |candidate| strip_completion_base(candidate, base));
+
+        let selected = match candidates.len() {
+            0 => {
+                // bell 울림
+                print!("\x07");
+                io::stdout().flush().ok();
+                return Cmd::Noop;
+            }
+            1 => candidates[0].to_owned(),
+            _ => match select_from_menu(query, &candidates, |candidate| strip_completion_base(candidate, base)) {
+                Some(selected) => selected,
+                None => return Cmd::Repaint,
+            },
+        };
+
+        // 입력된 단어에서 fuzzy 매칭한 부분만 교체 (앞쪽 경로나 $ 는 입력한 그대로)
+        let raw_word = &line[completion_word.start..];
+        let raw_tail = if base_len == 0 {
+            raw_word
+        } else if base == "$" {
+            raw_word.split_once('$').map(|(_, tail)| tail).unwrap_or(raw_word)
+        } else {
+            raw_word.rsplit_once('/').map(|(_, tail)| tail).unwrap_or(raw_word)
+        };
+
+        let mut replacement = String::new();
+        if let Some(quote) = completion_word.quote
+            && raw_tail.starts_with(quote) {
+            replacement.push(quote);
+        }
+        replacement.push_str(&escape_completion(strip_completion_base(&selected, base), completion_word.quote));
+
+        let is_continued = selected.ends_with('/') || selected.ends_with('=');
+        if false == is_continued && false == self.is_no_space.load(Ordering::Relaxed) {
+            if let Some(quote) = completion_word.quote {
+                replacement.push(quote);
+            }
+            replacement.push(' ');
+        }
+
+        set_menu_completion(line.len() - raw_tail.len(), replacement);
+        Cmd::Complete
     }
 
     pub fn get_longest_common_prefix(strs: &Vec<String>) -> String {
@@ -33,8 +227,9 @@ impl MyTabHandler {
                 if prefix.is_empty() {
                     return String::new();
                 }
-                // 4. 매칭되지 않으면 prefix를 마지막에서 한 글자씩 줄임
-                prefix = &prefix[..prefix.len() - 1];
+                // 4. 매칭되지 않으면 prefix를 마지막에서 한 글자씩 줄임 (멀티바이트 문자 경계 기준)
+                let last_char_idx = prefix.char_indices().last().map(|(idx, _)| idx).unwrap_or(0);
+                prefix = &prefix[..last_char_idx];
             }
         }
 
@@ -42,6 +237,37 @@ impl MyTabHandler {
     }
 }
 
+impl MyTabHandler {
+    // path 는 마지막 요소만 표시하고 종류와 설명을 붙임
+    fn annotate_candidates(&self, candidates: &[String], completion_word: &CompletionWord) -> Vec<AnnotatedCandidate> {
+        let candidate_kinds: Vec<CandidateKind> = candidates.iter().map(|candidate| get_candidate_kind(candidate, completion_word)).collect();
+
+        // 설명이 없는 실행 파일만 whatis 로
+        let candidate_descriptions = self.candidate_descriptions.lock().unwrap();
+        let executable_names: Vec<&str> = candidates
+            .iter()
+            .zip(&candidate_kinds)
+            .filter(|(candidate, kind)| matches!(kind, CandidateKind::Executable(_)) && false == candidate_descriptions.contains_key(candidate.as_str()))
+            .map(|(candidate, _)| candidate.as_str())
+            .collect();
+        let command_descriptions = get_command_descriptions(&executable_names);
+
+        candidates
+            .iter()
+            .zip(candidate_kinds)
+            .map(|(candidate, kind)| AnnotatedCandidate {
+                display_name: get_candidate_display_name(candidate).to_string(),
+                kind: kind.label(),
+                description: candidate_descriptions.get(candidate).or_else(|| command_descriptions.get(candidate)).cloned(),
+            })
+            .collect()
+    }
+}
+
+fn strip_completion_base<'a>(candidate: &'a str, base: &str) -> &'a str {
+    candidate.strip_prefix(base).unwrap_or(candidate)
+}
+
 impl ConditionalEventHandler for MyTabHandler {
     fn handle(
         &self,
@@ -50,12 +276,25 @@ impl ConditionalEventHandler for MyTabHandler {
         _positive: bool,
         ctx: &EventContext,
     ) -> Option<Cmd> {
-        
-        let line = ctx.line();
+        self.handle_context(ctx)
+    }
+}
+
+impl MyTabHandler {
+    pub fn handle_context(&self, ctx: &impl CompletionContext) -> Option<Cmd> {
+        // cursor 앞쪽에서 completion 대상 단어를 찾음
+        let line = &ctx.line()[..ctx.pos()];
+        let completion_word = parse_completion_word(line);
+        let word = completion_word.text.as_str();
         let filtered_commands:Vec<String>;
 
-        // 이전에 탭을 눌렀었으면 이미 필터를 한번 했었음
-        if self.last_was_tab.load(Ordering::Relaxed) {
+        if is_shell_option_enabled("menucomplete") {
+            return Some(self.handle_menu_completion(ctx, &completion_word));
+        }
+
+        // 같은 line 에서 이전에 탭을 눌렀으면 이미 필터를 한번 했었음
+        let is_same_line = *self.last_tab_line.lock().unwrap() == line;
+        if self.last_was_tab.load(Ordering::Relaxed) && is_same_line {
             let filtered_commands_lock = self.filtered_commands.lock().unwrap();
 
             filtered_commands = filtered_commands_lock
@@ -63,15 +302,13 @@ impl ConditionalEventHandler for MyTabHandler {
                 .map(|f| f.to_owned())
                 .collect();
 
-        // 이전에 탭을 누르지 않았을 경우에는 전체 commands 를 바탕으로 필터
+        // 이전에 탭을 누르지 않았을 경우에는 현재 단어를 바탕으로 필터
         } else {
-            filtered_commands = self.commands.iter()
-                .filter(|command| command.starts_with(line))
-                // owned
-                .map(|f| f.to_owned())
-                .collect();
+            self.last_was_tab.store(false, Ordering::Relaxed);
+            filtered_commands = self.get_candidates(&completion_word, ctx.line(), ctx.pos());
         }
         self.filtered_commands.lock().unwrap().clear();
+        *self.last_tab_line.lock().unwrap() = line.to_string();
 
         if filtered_commands.len() <= 0 {
             // bell 울림
@@ -84,8 +321,26 @@ impl ConditionalEventHandler for MyTabHandler {
             let Some(first_filtered_command) = filtered_commands.first() else {
                 return None;
             };
-            let result = &first_filtered_command[line.len()..].to_string();
-            let result = format!("{} ", result);
+            // z 처럼 입력한 단어로 시작하지 않는 후보는 단어 전체를 교체
+            let Some(rest) = first_filtered_command.strip_prefix(word) else {
+                let mut replacement = String::new();
+                if let Some(quote) = completion_word.quote {
+                    replacement.push(quote);
+                }
+                replacement.push_str(&escape_completion(first_filtered_command, completion_word.quote));
+                set_menu_completion(completion_word.start, replacement);
+                return Some(Cmd::Complete);
+            };
+            let mut result = escape_completion(rest, completion_word.quote);
+
+            // 디렉토리와 --option= 은 계속 이어서 입력할 수 있게 그대로, 그 외에는 쿼터를 닫고 공백 추가
+            let is_continued = first_filtered_command.ends_with('/') || first_filtered_command.ends_with('=');
+            if false == is_continued && false == self.is_no_space.load(Ordering::Relaxed) {
+                if let Some(quote) = completion_word.quote {
+                    result.push(quote);
+                }
+                result.push(' ');
+            }
 
             // line 에서 추가할 부분만 뒤쪽에 추가
             return Some(Cmd::Insert(1, result));
@@ -94,7 +349,11 @@ impl ConditionalEventHandler for MyTabHandler {
             // 이전에 탭을 한번 눌렀을 경우
             if self.last_was_tab.load(Ordering::Relaxed) {
                 self.last_was_tab.store(false, Ordering::Relaxed);
-                println!("\r\n{}", filtered_commands.join("  "));
+
+                // whatis 를 실행하기 전에 먼저 확인
+                if confirm_display_all(filtered_commands.len()) {
+                    print_annotated_candidate_list(&self.annotate_candidates(&filtered_commands, &completion_word));
+                }
 
                 // 이전 line 유지
                 return Some(Cmd::Repaint);
@@ -108,8 +367,8 @@ impl ConditionalEventHandler for MyTabHandler {
                 let mut filtered_commands_lock = self.filtered_commands.lock().unwrap();
                 filtered_commands_lock.extend(filtered_commands);
 
-                // 일치하는 prefix 가 없거나, 현재 입력 된거랑 common prefix 가 같을 경우에
-                if longest_common_prefix.is_empty() || line == longest_common_prefix {
+                // 일치하는 prefix 가 없거나, 현재 입력 된거랑 common prefix 가 같거나, 입력한 단어로 시작하지 않을 경우에
+                if longest_common_prefix.is_empty() || word == longest_common_prefix || false == longest_common_prefix.starts_with(word) {
                     // 이전 탭 눌렀음 true 로 변경
                     self.last_was_tab.store(true, Ordering::Relaxed);
 
@@ -119,7 +378,10 @@ impl ConditionalEventHandler for MyTabHandler {
                     return Some(Cmd::Noop);
                 } else {
                     // line 에서 추가할 부분만 뒤쪽에 추가
-                    let result = longest_common_prefix[line.len()..].to_string();
+                    let Some(rest) = longest_common_prefix.strip_prefix(word) else {
+                        return Some(Cmd::Noop);
+                    };
+                    let result = escape_completion(rest, completion_word.quote);
                     return Some(Cmd::Insert(1, result));
                 }
             }
@@ -127,3 +389,175 @@ impl ConditionalEventHandler for MyTabHandler {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use rustyline::Cmd;
+
+    use super::{CompletionContext, MyTabHandler};
+    use crate::shell_state::completion_specs::{CompletionAction, CompletionSpec, set_completion_spec};
+
+    struct FakeEventContext {
+        line: String,
+        pos: usize,
+    }
+
+    impl FakeEventContext {
+        fn new(line: &str) -> Self {
+            Self {
+                line: line.to_string(),
+                pos: line.len(),
+            }
+        }
+    }
+
+    impl CompletionContext for FakeEventContext {
+        fn line(&self) -> &str {
+            &self.line
+        }
+
+        fn pos(&self) -> usize {
+            self.pos
+        }
+    }
+
+    // test 가 병렬로 실행되므로 command 이름과 디렉토리는 test 마다 다르게
+    fn create_test_dir(name: &str) -> PathBuf {
+        let test_dir = env::temp_dir().join(format!("tab_handler_{}_{}", name, std::process::id()));
+        fs::remove_dir_all(&test_dir).ok();
+        fs::create_dir_all(&test_dir).unwrap();
+        test_dir
+    }
+
+    #[test]
+    fn word_list_single_match_inserts_rest_and_space() {
+        set_completion_spec("svc_single", CompletionSpec {
+            word_list: Some("start stop status".to_string()),
+            ..Default::default()
+        });
+        let tab_handler = MyTabHandler::new(["svc_single"]);
+
+        let cmd = tab_handler.handle_context(&FakeEventContext::new("svc_single sto"));
+
+        assert_eq!(cmd, Some(Cmd::Insert(1, "p ".to_string())));
+    }
+
+    #[test]
+    fn word_list_common_prefix_then_listing() {
+        set_completion_spec("svc_prefix", CompletionSpec {
+            word_list: Some("start stop status".to_string()),
+            ..Default::default()
+        });
+        let tab_handler = MyTabHandler::new(["svc_prefix"]);
+        let ctx = FakeEventContext::new("svc_prefix st");
+
+        // start, status, stop 의 공통 prefix 가 입력과 같으면 bell, 다시 누르면 목록 출력
+        assert_eq!(tab_handler.handle_context(&ctx), Some(Cmd::Noop));
+        assert_eq!(tab_handler.handle_context(&ctx), Some(Cmd::Repaint));
+
+        let cmd = tab_handler.handle_context(&FakeEventContext::new("svc_prefix s"));
+        assert_eq!(cmd, Some(Cmd::Insert(1, "t".to_string())));
+    }
+
+    #[test]
+    fn nospace_option_skips_trailing_space() {
+        set_completion_spec("nospace_cmd", CompletionSpec {
+            word_list: Some("--output=".to_string()),
+            options: vec!["nospace".to_string()],
+            ..Default::default()
+        });
+        let tab_handler = MyTabHandler::new(["nospace_cmd"]);
+
+        let cmd = tab_handler.handle_context(&FakeEventContext::new("nospace_cmd --out"));
+
+        assert_eq!(cmd, Some(Cmd::Insert(1, "put=".to_string())));
+    }
+
+    #[test]
+    fn function_spec_reads_compreply() {
+        crate::run_command_line(r#"_fn_spec_completion() { COMPREPLY=("$2"-from-function); }"#);
+        set_completion_spec("fn_spec_cmd", CompletionSpec {
+            function: Some("_fn_spec_completion".to_string()),
+            ..Default::default()
+        });
+        let tab_handler = MyTabHandler::new(["fn_spec_cmd"]);
+
+        let cmd = tab_handler.handle_context(&FakeEventContext::new("fn_spec_cmd value"));
+
+        assert_eq!(cmd, Some(Cmd::Insert(1, "-from-function ".to_string())));
+    }
+
+    #[test]
+    fn file_action_with_filter_pattern() {
+        let test_dir = create_test_dir("filter");
+        fs::write(test_dir.join("notes.txt"), "").unwrap();
+        fs::write(test_dir.join("notes.rs"), "").unwrap();
+        set_completion_spec("txt_only", CompletionSpec {
+            actions: vec![CompletionAction::File],
+            filter_pattern: Some("!*.txt".to_string()),
+            ..Default::default()
+        });
+        let tab_handler = MyTabHandler::new(["txt_only"]);
+
+        let cmd = tab_handler.handle_context(&FakeEventContext::new(&format!("txt_only {}/no", test_dir.display())));
+
+        assert_eq!(cmd, Some(Cmd::Insert(1, "tes.txt ".to_string())));
+        fs::remove_dir_all(&test_dir).ok();
+    }
+
+    #[test]
+    fn directory_action_keeps_slash_without_space() {
+        let test_dir = create_test_dir("directory");
+        fs::create_dir(test_dir.join("only_dir")).unwrap();
+        fs::write(test_dir.join("only_file"), "").unwrap();
+        set_completion_spec("dir_only", CompletionSpec {
+            actions: vec![CompletionAction::Directory],
+            ..Default::default()
+        });
+        let tab_handler = MyTabHandler::new(["dir_only"]);
+
+        let cmd = tab_handler.handle_context(&FakeEventContext::new(&format!("dir_only {}/only", test_dir.display())));
+
+        assert_eq!(cmd, Some(Cmd::Insert(1, "_dir/".to_string())));
+        fs::remove_dir_all(&test_dir).ok();
+    }
+
+    #[test]
+    fn command_without_spec_falls_back_to_path() {
+        let test_dir = create_test_dir("fallback");
+        fs::write(test_dir.join("fallback file"), "").unwrap();
+        let tab_handler = MyTabHandler::new(["no_spec_cmd"]);
+
+        let cmd = tab_handler.handle_context(&FakeEventContext::new(&format!("no_spec_cmd {}/fall", test_dir.display())));
+
+        assert_eq!(cmd, Some(Cmd::Insert(1, "back\\ file ".to_string())));
+        fs::remove_dir_all(&test_dir).ok();
+    }
+
+    #[test]
+    fn command_position_ignores_spec() {
+        set_completion_spec("spec_position", CompletionSpec {
+            word_list: Some("ignored".to_string()),
+            ..Default::default()
+        });
+        let tab_handler = MyTabHandler::new(["spec_position"]);
+
+        let cmd = tab_handler.handle_context(&FakeEventContext::new("spec_pos"));
+
+        assert_eq!(cmd, Some(Cmd::Insert(1, "ition ".to_string())));
+    }
+
+    #[test]
+    fn no_candidate_rings_bell() {
+        set_completion_spec("empty_spec", CompletionSpec {
+            word_list: Some("alpha".to_string()),
+            ..Default::default()
+        });
+        let tab_handler = MyTabHandler::new(["empty_spec"]);
+
+        let cmd = tab_handler.handle_context(&FakeEventContext::new("empty_spec zz"));
+
+        assert_eq!(cmd, Some(Cmd::Noop));
+    }
+}
@@ -0,0 +1,100 @@
+// bind builtin
+//   bind '"\C-x\C-e": function'   : key sequence 에 editor 함수 연결
+//   bind '"\C-xg": "text"'        : key sequence 에 입력할 문자열 연결
+//   bind 'set editing-mode vi'    : inputrc 변수 설정
+//   bind -x '"\C-g": command'     : key sequence 에 shell command 연결 (READLINE_LINE, READLINE_POINT 로 line 을 읽고 바꿈)
+//   bind -r keyseq                : binding 삭제
+//   bind -f file                  : inputrc 파일 읽기
+//   bind -l                       : editor 함수 이름 목록
+//   bind -p                       : 함수, 문자열 binding 을 다시 입력할 수 있는 형태로 출력
+//   bind -X                       : shell command binding 출력
+
+use std::path::Path;
+
+use crate::rustyline_editor::key_binding::{InputrcLine, get_editor_function_names, parse_inputrc_line, parse_key_sequence, read_inputrc_file, set_inputrc_variable};
+use crate::shell_state::key_bindings::{KeyBindingAction, get_key_bindings, remove_key_binding, set_key_binding};
+
+pub fn command_bind(args: &[String]) -> i32 {
+    let mut status = 0;
+
+    let mut idx = 0;
+    while idx < args.len() && args[idx].starts_with('-') && args[idx].len() > 1 {
+        let arg = &args[idx];
+        idx += 1;
+        if arg == "--" {
+            break;
+        }
+
+        for flag in arg[1..].chars() {
+            match flag {
+                'l' => {
+                    for name in get_editor_function_names() {
+                        println!("{}", name);
+                    }
+                }
+                'p' => print_key_bindings(false),
+                'X' => print_key_bindings(true),
+                'x' | 'r' | 'f' => {
+                    let Some(value) = args.get(idx) else {
+                        eprintln!("bind: -{}: option requires an argument", flag);
+                        return 2;
+                    };
+                    idx += 1;
+
+                    let result = match flag {
+                        'x' => bind_line(value, true),
+                        'r' => unbind_key_sequence(value),
+                        _ => read_inputrc_file(Path::new(value)),
+                    };
+                    if let Err(e) = result {
+                        eprintln!("bind: {}", e);
+                        status = 1;
+                    }
+                }
+                _ => {
+                    eprintln!("bind: -{}: invalid option", flag);
+                    eprintln!("bind: usage: bind [-lpX] [-f filename] [-r keyseq] [-x keyseq:shell-command] [keyseq:readline-function or readline-command]");
+                    return 2;
+                }
+            }
+        }
+    }
+
+    for arg in &args[idx..] {
+        if let Err(e) = bind_line(arg, false) {
+            eprintln!("bind: {}", e);
+            status = 1;
+        }
+    }
+
+    status
+}
+
+fn bind_line(line: &str, is_shell_command: bool) -> Result<(), String> {
+    match parse_inputrc_line(line, is_shell_command)? {
+        InputrcLine::Empty => Ok(()),
+        InputrcLine::Variable(name, value) => set_inputrc_variable(&name, &value),
+        InputrcLine::Binding(key_binding) => {
+            set_key_binding(key_binding);
+            Ok(())
+        }
+    }
+}
+
+// 쿼터 없이 \C-a 처럼 입력해도 됨, 없는 binding 은 무시
+fn unbind_key_sequence(key_sequence: &str) -> Result<(), String> {
+    let key_sequence = key_sequence.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')).unwrap_or(key_sequence);
+    remove_key_binding(&parse_key_sequence(key_sequence)?);
+    Ok(())
+}
+
+fn print_key_bindings(is_shell_command: bool) {
+    for key_binding in get_key_bindings() {
+        match (&key_binding.action, is_shell_command) {
+            (KeyBindingAction::Function(name), false) => println!("{}: {}", key_binding.key_sequence, name),
+            (KeyBindingAction::Macro(text), false) => println!("{}: \"{}\"", key_binding.key_sequence, text),
+            (KeyBindingAction::ShellCommand(command), true) => println!("{}: \"{}\"", key_binding.key_sequence, command),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,224 @@
+// cd, pwd builtin
+//   cd [-L|-P] [dir]  : dir 이 없으면 HOME, - 면 OLDPWD 로 이동하고 이동한 디렉토리 출력
+//   pwd [-L|-P]       : 현재 디렉토리 출력
+// -L (기본값) 은 symlink 를 따라 들어온 경로 그대로(PWD) 사용하고 .. 는 경로에서 앞 요소를 지움
+// -P 는 symlink 를 풀어낸 실제 경로 사용
+// / 나 . .. 로 시작하지 않는 dir 은 CDPATH 의 디렉토리(: 로 구분, 빈 값은 현재 디렉토리)에서 먼저 찾고
+// CDPATH 로 찾았으면 이동한 디렉토리 출력
+// 에러는 stderr 로 출력
+
+use std::{env, io, path::{Component, Path, PathBuf}};
+
+use crate::shell_state::directory_frecency::add_directory_visit;
+use crate::shell_state::variables::{get_variable, set_variable};
+
+pub fn command_cd(args: &[String]) -> i32 {
+    let mut is_physical = false;
+
+    let mut idx = 0;
+    while idx < args.len() && args[idx].starts_with('-') && args[idx].len() > 1 {
+        let arg = &args[idx];
+        if arg == "--" {
+            idx += 1;
+            break;
+        }
+
+        for flag in arg[1..].chars() {
+            match flag {
+                'L' => is_physical = false,
+                'P' => is_physical = true,
+                _ => {
+                    eprintln!("cd: -{}: invalid option", flag);
+                    eprintln!("cd: usage: cd [-L|-P] [dir]");
+                    return 2;
+                }
+            }
+        }
+        idx += 1;
+    }
+
+    let operands = &args[idx..];
+    if operands.len() > 1 {
+        eprintln!("cd: too many arguments");
+        return 1;
+    }
+
+    let mut is_print_dir = false;
+    let dir = match operands.first().map(|operand| operand.as_str()) {
+        None => match get_variable("HOME").filter(|home| false == home.is_empty()) {
+            Some(home) => home,
+            None => {
+                eprintln!("cd: HOME not set");
+                return 1;
+            }
+        },
+        Some("-") => match get_variable("OLDPWD").filter(|old_pwd| false == old_pwd.is_empty()) {
+            Some(old_pwd) => {
+                is_print_dir = true;
+                old_pwd
+            }
+            None => {
+                eprintln!("cd: OLDPWD not set");
+                return 1;
+            }
+        },
+        // 빈 문자열은 이동하지 않음
+        Some("") => return 0,
+        Some(operand) => operand.to_string(),
+    };
+
+    let dir = match find_cdpath_dir(&dir) {
+        Some(cdpath_dir) => {
+            is_print_dir = true;
+            cdpath_dir
+        }
+        None => dir,
+    };
+
+    match change_directory(&dir, is_physical) {
+        Ok(current_dir) => {
+            if is_print_dir {
+                println!("{}", current_dir);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("cd: {}", e);
+            1
+        }
+    }
+}
+
+pub fn command_pwd(args: &[String]) -> i32 {
+    let mut is_physical = false;
+
+    for arg in args {
+        if false == arg.starts_with('-') || arg.len() == 1 {
+            continue;
+        }
+        for flag in arg[1..].chars() {
+            match flag {
+                'L' => is_physical = false,
+                'P' => is_physical = true,
+                _ => {
+                    eprintln!("pwd: -{}: invalid option", flag);
+                    eprintln!("pwd: usage: pwd [-LP]");
+                    return 2;
+                }
+            }
+        }
+    }
+
+    if false == is_physical {
+        println!("{}", get_logical_current_dir().display());
+        return 0;
+    }
+
+    match env::current_dir() {
+        Ok(current_dir) => {
+            println!("{}", current_dir.display());
+            0
+        }
+        Err(e) => {
+            eprintln!("pwd: error retrieving current directory: {}", format_io_error(&e));
+            1
+        }
+    }
+}
+
+// 디렉토리를 바꾸고 PWD, OLDPWD 갱신, z 를 위해 방문 기록, 바뀐 PWD 를 반환
+pub fn change_directory(dir: &str, is_physical: bool) -> Result<String, String> {
+    let old_pwd = get_logical_current_dir();
+
+    let current_dir = if is_physical {
+        env::set_current_dir(dir).map_err(|e| format!("{}: {}", dir, format_io_error(&e)))?;
+        env::current_dir().map_err(|e| format!("{}: {}", dir, format_io_error(&e)))?
+    } else {
+        let logical_dir = normalize_logical_path(&old_pwd.join(dir));
+        match env::set_current_dir(&logical_dir) {
+            Ok(_) => logical_dir,
+            // a/.. 의 a 가 없는 경우 등은 실제 경로로 다시 시도
+            Err(_) => {
+                env::set_current_dir(dir).map_err(|e| format!("{}: {}", dir, format_io_error(&e)))?;
+                env::current_dir().map_err(|e| format!("{}: {}", dir, format_io_error(&e)))?
+            }
+        }
+    };
+
+    let current_dir = current_dir.to_string_lossy().into_owned();
+    set_directory_variable("OLDPWD", &old_pwd.to_string_lossy());
+    set_directory_variable("PWD", &current_dir);
+    add_directory_visit(&current_dir);
+
+    Ok(current_dir)
+}
+
+// PWD 가 현재 디렉토리를 가리키고 있으면 PWD, 아니면 실제 경로
+pub fn get_logical_current_dir() -> PathBuf {
+    let physical_dir = env::current_dir().unwrap_or_default();
+
+    if let Some(pwd) = get_variable("PWD").map(PathBuf::from)
+        && pwd.is_absolute()
+        && pwd.canonicalize().is_ok_and(|pwd| pwd == physical_dir) {
+        return pwd;
+    }
+
+    physical_dir
+}
+
+fn find_cdpath_dir(dir: &str) -> Option<String> {
+    let first_component = dir.split('/').next().unwrap_or("");
+    if dir.starts_with('/') || first_component == "." || first_component == ".." {
+        return None;
+    }
+
+    let cdpath = get_variable("CDPATH").filter(|cdpath| false == cdpath.is_empty())?;
+    for cdpath_dir in cdpath.split(':') {
+        // 빈 값은 현재 디렉토리, 이 경우는 이동한 디렉토리를 출력하지 않으므로 그대로 둠
+        if cdpath_dir.is_empty() || cdpath_dir == "." {
+            if Path::new(dir).is_dir() {
+                return None;
+            }
+            continue;
+        }
+
+        let candidate = Path::new(cdpath_dir).join(dir);
+        if candidate.is_dir() {
+            return Some(candidate.to_string_lossy().into_owned());
+        }
+    }
+
+    None
+}
+
+// . 은 지우고 .. 는 앞 요소를 지움 (symlink 는 풀지 않음)
+pub fn normalize_logical_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::from("/");
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::Normal(name) => normalized.push(name),
+            Component::RootDir | Component::CurDir | Component::Prefix(_) => {}
+        }
+    }
+
+    normalized
+}
+
+// 외부 command 도 같은 값을 보도록 환경변수도 함께 바꿈
+fn set_directory_variable(name: &str, value: &str) {
+    set_variable(name, value);
+    // builtin 은 main thread 에서만 실행됨
+    unsafe { env::set_var(name, value) };
+}
+
+fn format_io_error(e: &io::Error) -> String {
+    match e.kind() {
+        io::ErrorKind::NotFound => "No such file or directory".to_string(),
+        io::ErrorKind::PermissionDenied => "Permission denied".to_string(),
+        io::ErrorKind::NotADirectory => "Not a directory".to_string(),
+        _ => e.to_string(),
+    }
+}
@@ -0,0 +1,209 @@
+// complete / compgen builtin
+//   complete [-bcdfjuv] [-A action] [-o option] [-W wordlist] [-F function] [-C command] [-X filterpat] name...
+//   complete -p [name...]   : 등록된 규칙을 다시 입력할 수 있는 형태로 출력
+//   complete -r [name...]   : 규칙 삭제 (name 이 없으면 전체)
+//   compgen [option...] [word]  : complete 와 같은 option 으로 word 의 후보를 출력
+
+use crate::rustyline_editor::completion::CompletionWord;
+use crate::rustyline_editor::programmable_completion::{CompletionRequest, get_spec_candidates};
+use crate::shell_state::completion_specs::{CompletionAction, CompletionSpec, clear_completion_specs, get_all_completion_specs, get_completion_spec, remove_completion_spec, set_completion_spec};
+
+const COMPLETE_OPTIONS: [&str; 4] = ["default", "dirnames", "filenames", "nospace"];
+
+#[derive(Debug, Default)]
+struct CompleteArgs {
+    completion_spec: CompletionSpec,
+    names: Vec<String>,
+    is_print: bool,
+    is_remove: bool,
+}
+
+fn parse_complete_args(command: &str, args: &[String]) -> Result<CompleteArgs, String> {
+    let mut complete_args = CompleteArgs::default();
+    let mut idx = 0;
+
+    while idx < args.len() {
+        let arg = &args[idx];
+        if arg == "--" {
+            idx += 1;
+            break;
+        }
+        if false == arg.starts_with('-') || arg.len() < 2 {
+            break;
+        }
+
+        let flags: Vec<char> = arg[1..].chars().collect();
+        for (flag_idx, flag) in flags.iter().enumerate() {
+            if let Some(action) = CompletionAction::from_flag(*flag) {
+                complete_args.completion_spec.actions.push(action);
+                continue;
+            }
+
+            match flag {
+                'p' => complete_args.is_print = true,
+                'r' => complete_args.is_remove = true,
+                'A' | 'o' | 'W' | 'F' | 'C' | 'X' => {
+                    // -Wvalue 처럼 붙여쓰거나 다음 인자로
+                    let value = if flag_idx + 1 < flags.len() {
+                        flags[flag_idx + 1..].iter().collect::<String>()
+                    } else {
+                        idx += 1;
+                        match args.get(idx) {
+                            Some(value) => value.to_owned(),
+                            None => return Err(format!("{}: -{}: option requires an argument", command, flag)),
+                        }
+                    };
+                    set_complete_option_value(command, &mut complete_args.completion_spec, *flag, value)?;
+                    break;
+                }
+                _ => return Err(format!("{}: -{}: invalid option", command, flag)),
+            }
+        }
+        idx += 1;
+    }
+
+    complete_args.names = args[idx.min(args.len())..].to_vec();
+    Ok(complete_args)
+}
+
+fn set_complete_option_value(command: &str, completion_spec: &mut CompletionSpec, flag: char, value: String) -> Result<(), String> {
+    match flag {
+        'A' => {
+            let Some(action) = CompletionAction::from_name(&value) else {
+                return Err(format!("{}: {}: invalid action name", command, value));
+            };
+            completion_spec.actions.push(action);
+        }
+        'o' => {
+            if false == COMPLETE_OPTIONS.contains(&value.as_str()) {
+                return Err(format!("{}: {}: invalid option name", command, value));
+            }
+            completion_spec.options.push(value);
+        }
+        'W' => completion_spec.word_list = Some(value),
+        'F' => completion_spec.function = Some(value),
+        'C' => completion_spec.command = Some(value),
+        _ => completion_spec.filter_pattern = Some(value),
+    }
+
+    Ok(())
+}
+
+pub fn command_complete(args: &[String]) -> i32 {
+    let complete_args = match parse_complete_args("complete", args) {
+        Ok(complete_args) => complete_args,
+        Err(e) => {
+            println!("{}", e);
+            println!("complete: usage: complete [-pr] [-bcdfjuv] [-A action] [-o option] [-W wordlist] [-F function] [-C command] [-X filterpat] [name ...]");
+            return 2;
+        }
+    };
+
+    if complete_args.is_remove {
+        if complete_args.names.is_empty() {
+            clear_completion_specs();
+            return 0;
+        }
+
+        let mut status = 0;
+        for name in &complete_args.names {
+            if false == remove_completion_spec(name) {
+                println!("complete: {}: no completion specification", name);
+                status = 1;
+            }
+        }
+        return status;
+    }
+
+    // 인자가 없거나 -p 면 출력
+    if complete_args.is_print || complete_args.names.is_empty() {
+        if complete_args.names.is_empty() {
+            for (name, completion_spec) in get_all_completion_specs() {
+                println!("{}", format_completion_spec(&name, &completion_spec));
+            }
+            return 0;
+        }
+
+        let mut status = 0;
+        for name in &complete_args.names {
+            match get_completion_spec(name) {
+                Some(completion_spec) => println!("{}", format_completion_spec(name, &completion_spec)),
+                None => {
+                    println!("complete: {}: no completion specification", name);
+                    status = 1;
+                }
+            }
+        }
+        return status;
+    }
+
+    for name in &complete_args.names {
+        set_completion_spec(name, complete_args.completion_spec.to_owned());
+    }
+
+    0
+}
+
+pub fn command_compgen(args: &[String]) -> i32 {
+    let complete_args = match parse_complete_args("compgen", args) {
+        Ok(complete_args) => complete_args,
+        Err(e) => {
+            println!("{}", e);
+            println!("compgen: usage: compgen [-bcdfjuv] [-A action] [-o option] [-W wordlist] [-F function] [-C command] [-X filterpat] [word]");
+            return 2;
+        }
+    };
+
+    let word = complete_args.names.first().map(|word| word.to_owned()).unwrap_or_default();
+    let line = format!("compgen {}", word);
+    let completion_word = CompletionWord {
+        text: word,
+        quote: None,
+        is_command_position: false,
+        previous_words: vec!["compgen".to_string()],
+        start: "compgen ".len(),
+    };
+    let completion_request = CompletionRequest {
+        line: &line,
+        pos: line.len(),
+        completion_word: &completion_word,
+    };
+
+    let candidates = get_spec_candidates(&complete_args.completion_spec, &completion_request);
+    for candidate in &candidates {
+        println!("{}", candidate);
+    }
+
+    if candidates.is_empty() { 1 } else { 0 }
+}
+
+// complete -p 출력 형태
+fn format_completion_spec(name: &str, completion_spec: &CompletionSpec) -> String {
+    let mut words = vec!["complete".to_string()];
+
+    for option in &completion_spec.options {
+        words.push(format!("-o {}", option));
+    }
+    for action in &completion_spec.actions {
+        words.push(format!("-A {}", action.name()));
+    }
+    if let Some(word_list) = &completion_spec.word_list {
+        words.push(format!("-W {}", quote_word(word_list)));
+    }
+    if let Some(filter_pattern) = &completion_spec.filter_pattern {
+        words.push(format!("-X {}", quote_word(filter_pattern)));
+    }
+    if let Some(function) = &completion_spec.function {
+        words.push(format!("-F {}", function));
+    }
+    if let Some(command) = &completion_spec.command {
+        words.push(format!("-C {}", quote_word(command)));
+    }
+    words.push(name.to_string());
+
+    words.join(" ")
+}
+
+fn quote_word(word: &str) -> String {
+    format!("'{}'", word.replace('\'', "'\\''"))
+}
@@ -0,0 +1,241 @@
+// directory stack builtin (맨 위 0 번째는 현재 디렉토리)
+//   pushd [-n] [dir]     : dir 로 이동하고 이전 디렉토리를 stack 에 넣음, dir 이 없으면 위의 두 entry 를 바꿈
+//   pushd [-n] +N | -N   : N 번째 entry 가 맨 위로 오도록 stack 을 돌리고 이동
+//   popd [-n] [+N | -N]  : 맨 위 (혹은 N 번째) entry 를 빼고 새로 맨 위가 된 디렉토리로 이동
+//   dirs [-clpv] [+N | -N] : stack 출력
+//     -c : 현재 디렉토리만 남기고 비움 (출력하지 않음)
+//     -l : HOME 을 ~ 로 줄이지 않음
+//     -p : 한 줄에 하나씩
+//     -v : 한 줄에 하나씩 번호와 함께
+// -n 은 디렉토리를 이동하지 않고 stack 만 바꿈 (맨 위는 현재 디렉토리로 유지)
+// pushd, popd 는 성공하면 dirs 처럼 stack 을 출력
+// ~N, ~+N, ~-N 은 N 번째 entry 로 확장됨 (expansion 참고)
+
+use std::path::Path;
+
+use crate::shell_builtin::cd_command::change_directory;
+use crate::shell_state::directory_stack::{get_directory_stack, parse_directory_stack_index, set_saved_directories};
+use crate::shell_state::variables::get_variable;
+
+pub fn command_dirs(args: &[String]) -> i32 {
+    let mut is_long = false;
+    let mut is_per_line = false;
+    let mut is_numbered = false;
+    let mut index: Option<&String> = None;
+
+    for arg in args {
+        if is_stack_index(arg) {
+            index = Some(arg);
+            continue;
+        }
+        if false == arg.starts_with('-') || arg.len() == 1 {
+            eprintln!("dirs: {}: invalid argument", arg);
+            eprintln!("dirs: usage: dirs [-clpv] [+N] [-N]");
+            return 2;
+        }
+        for flag in arg[1..].chars() {
+            match flag {
+                'c' => {
+                    set_saved_directories(vec![]);
+                    return 0;
+                }
+                'l' => is_long = true,
+                'p' => is_per_line = true,
+                'v' => is_numbered = true,
+                _ => {
+                    eprintln!("dirs: -{}: invalid option", flag);
+                    eprintln!("dirs: usage: dirs [-clpv] [+N] [-N]");
+                    return 2;
+                }
+            }
+        }
+    }
+
+    let directory_stack = get_directory_stack();
+    if let Some(index) = index {
+        let Some(index) = parse_directory_stack_index(index, directory_stack.len()) else {
+            eprintln!("dirs: {}: directory stack index out of range", index);
+            return 1;
+        };
+        println!("{}", format_directory(&directory_stack[index], is_long));
+        return 0;
+    }
+
+    print_directory_stack(is_long, is_per_line, is_numbered);
+    0
+}
+
+pub fn command_pushd(args: &[String]) -> i32 {
+    let (is_no_cd, operands) = match parse_no_cd_option("pushd", args) {
+        Ok(parsed) => parsed,
+        Err(status) => return status,
+    };
+    if operands.len() > 1 {
+        eprintln!("pushd: too many arguments");
+        return 1;
+    }
+
+    let mut directory_stack = get_directory_stack();
+    if let Some(dir) = operands.first().filter(|operand| false == is_stack_index(operand)) {
+        if is_no_cd {
+            // 상대 경로는 현재 디렉토리 기준으로 저장
+            let dir = Path::new(&directory_stack[0]).join(dir).to_string_lossy().into_owned();
+            directory_stack.insert(1, dir);
+        } else if let Err(e) = change_directory(dir, false) {
+            eprintln!("pushd: {}", e);
+            return 1;
+        }
+        // 이동했으면 이전 맨 위가 새 디렉토리 아래로 감
+        let saved_directories = if is_no_cd { directory_stack.split_off(1) } else { directory_stack };
+        set_saved_directories(saved_directories);
+
+        print_directory_stack(false, false, false);
+        return 0;
+    }
+
+    // 인자가 없으면 +1 과 같음
+    let index = match operands.first() {
+        Some(operand) => match parse_directory_stack_index(operand, directory_stack.len()) {
+            Some(index) => index,
+            None => {
+                eprintln!("pushd: {}: directory stack index out of range", operand);
+                return 1;
+            }
+        },
+        None if directory_stack.len() < 2 => {
+            eprintln!("pushd: no other directory");
+            return 1;
+        }
+        None => 1,
+    };
+
+    if is_no_cd {
+        directory_stack[1..].rotate_left(index.saturating_sub(1));
+    } else {
+        directory_stack.rotate_left(index);
+        if let Err(e) = change_directory(&directory_stack[0], false) {
+            eprintln!("pushd: {}", e);
+            return 1;
+        }
+    }
+    set_saved_directories(directory_stack.split_off(1));
+
+    print_directory_stack(false, false, false);
+    0
+}
+
+pub fn command_popd(args: &[String]) -> i32 {
+    let (is_no_cd, operands) = match parse_no_cd_option("popd", args) {
+        Ok(parsed) => parsed,
+        Err(status) => return status,
+    };
+    if let Some(operand) = operands.iter().find(|operand| false == is_stack_index(operand)) {
+        eprintln!("popd: {}: invalid argument", operand);
+        eprintln!("popd: usage: popd [-n] [+N | -N]");
+        return 2;
+    }
+    if operands.len() > 1 {
+        eprintln!("popd: too many arguments");
+        return 1;
+    }
+
+    let mut directory_stack = get_directory_stack();
+    if directory_stack.len() < 2 {
+        eprintln!("popd: directory stack empty");
+        return 1;
+    }
+
+    let index = match operands.first() {
+        Some(operand) => match parse_directory_stack_index(operand, directory_stack.len()) {
+            Some(index) => index,
+            None => {
+                eprintln!("popd: {}: directory stack index out of range", operand);
+                return 1;
+            }
+        },
+        None => 0,
+    };
+
+    // 맨 위를 빼면 다음 entry 로 이동, -n 이면 이동하지 않고 그 다음 entry 를 뺌
+    if index == 0 && false == is_no_cd {
+        if let Err(e) = change_directory(&directory_stack[1], false) {
+            eprintln!("popd: {}", e);
+            return 1;
+        }
+        directory_stack.remove(0);
+    } else {
+        directory_stack.remove(index.max(1));
+    }
+    set_saved_directories(directory_stack.split_off(1));
+
+    print_directory_stack(false, false, false);
+    0
+}
+
+// -n 과 나머지 인자, -N 은 옵션이 아니라 stack index
+fn parse_no_cd_option<'a>(command: &str, args: &'a [String]) -> Result<(bool, &'a [String]), i32> {
+    let mut is_no_cd = false;
+
+    let mut idx = 0;
+    while idx < args.len() && args[idx].starts_with('-') && args[idx].len() > 1 && false == is_stack_index(&args[idx]) {
+        match args[idx].as_str() {
+            "--" => {
+                idx += 1;
+                break;
+            }
+            "-n" => is_no_cd = true,
+            arg => {
+                eprintln!("{}: {}: invalid option", command, arg);
+                match command {
+                    "pushd" => eprintln!("pushd: usage: pushd [-n] [+N | -N | dir]"),
+                    _ => eprintln!("popd: usage: popd [-n] [+N | -N]"),
+                }
+                return Err(2);
+            }
+        }
+        idx += 1;
+    }
+
+    Ok((is_no_cd, &args[idx..]))
+}
+
+fn is_stack_index(arg: &str) -> bool {
+    matches!(arg.strip_prefix(['+', '-']), Some(number) if false == number.is_empty() && number.chars().all(|char| char.is_ascii_digit()))
+}
+
+fn print_directory_stack(is_long: bool, is_per_line: bool, is_numbered: bool) {
+    let directory_stack = get_directory_stack();
+
+    if is_numbered {
+        for (idx, dir) in directory_stack.iter().enumerate() {
+            println!("{:>2}  {}", idx, format_directory(dir, is_long));
+        }
+        return;
+    }
+    if is_per_line {
+        for dir in &directory_stack {
+            println!("{}", format_directory(dir, is_long));
+        }
+        return;
+    }
+
+    let directories: Vec<String> = directory_stack.iter().map(|dir| format_directory(dir, is_long)).collect();
+    println!("{}", directories.join(" "));
+}
+
+// HOME 아래는 ~ 로 줄임
+fn format_directory(dir: &str, is_long: bool) -> String {
+    let home = get_variable("HOME").unwrap_or_default();
+    let home = home.trim_end_matches('/');
+    if is_long || home.is_empty() {
+        return dir.to_string();
+    }
+
+    if dir == home {
+        return "~".to_string();
+    }
+    match dir.strip_prefix(home).filter(|rest| rest.starts_with('/')) {
+        Some(rest) => format!("~{}", rest),
+        None => dir.to_string(),
+    }
+}
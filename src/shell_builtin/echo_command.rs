@@ -0,0 +1,144 @@
+// echo builtin
+//   echo [-neE] [arg ...] : arg 를 공백으로 이어서 출력하고 개행
+//     -n : 마지막 개행을 출력하지 않음
+//     -e : 백슬래쉬 escape 해석
+//     -E : 백슬래쉬 escape 를 해석하지 않음 (기본값)
+// -neE 조합만 옵션으로 보고, 그 외(-x, --, -n- 등)는 그대로 출력
+// -e 의 escape : \a \b \c \e \E \f \n \r \t \v \\ \0NNN \xHH \uHHHH \UHHHHHHHH, 모르는 escape 는 백슬래쉬까지 그대로
+// \c 는 그 뒤의 출력(마지막 개행 포함)을 모두 생략
+// 출력은 stdout(fd 1) 으로 바로 써서 redirection 이 외부 command 와 같게 동작
+
+use std::io::{self, Write};
+
+pub fn command_echo(args: &[String]) -> i32 {
+    let mut is_newline = true;
+    let mut is_escape = false;
+
+    let mut idx = 0;
+    while idx < args.len() && is_echo_option(&args[idx]) {
+        for flag in args[idx][1..].chars() {
+            match flag {
+                'n' => is_newline = false,
+                'e' => is_escape = true,
+                _ => is_escape = false,
+            }
+        }
+        idx += 1;
+    }
+
+    let mut output: Vec<u8> = vec![];
+    for (arg_idx, arg) in args[idx..].iter().enumerate() {
+        if arg_idx > 0 {
+            output.push(b' ');
+        }
+
+        if false == is_escape {
+            output.extend_from_slice(arg.as_bytes());
+            continue;
+        }
+
+        let (interpreted, is_stopped) = interpret_backslash_escapes(arg, true);
+        output.extend(interpreted);
+        if is_stopped {
+            is_newline = false;
+            break;
+        }
+    }
+    if is_newline {
+        output.push(b'\n');
+    }
+
+    let mut stdout = io::stdout().lock();
+    if let Err(e) = stdout.write_all(&output).and_then(|_| stdout.flush()) {
+        eprintln!("echo: write error: {}", e);
+        return 1;
+    }
+
+    0
+}
+
+fn is_echo_option(arg: &str) -> bool {
+    arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|char| matches!(char, 'n' | 'e' | 'E'))
+}
+
+// 백슬래쉬 escape 를 해석한 byte 와 \c 로 출력이 멈췄는지 여부
+// is_echo_style 이 true 면 echo, printf %b 처럼 \0NNN (0 뒤로 최대 3자리)
+// false 면 printf format 처럼 \NNN (최대 3자리) 이고 \" \' \? 도 해석
+pub fn interpret_backslash_escapes(input: &str, is_echo_style: bool) -> (Vec<u8>, bool) {
+    let chars: Vec<char> = input.chars().collect();
+    let mut output: Vec<u8> = vec![];
+
+    let mut idx = 0;
+    while idx < chars.len() {
+        let char = chars[idx];
+        idx += 1;
+        if char != '\\' || idx == chars.len() {
+            push_char(&mut output, char);
+            continue;
+        }
+
+        let escape = chars[idx];
+        idx += 1;
+        match escape {
+            'a' => output.push(0x07),
+            'b' => output.push(0x08),
+            'c' => return (output, true),
+            'e' | 'E' => output.push(0x1b),
+            'f' => output.push(0x0c),
+            'n' => output.push(b'\n'),
+            'r' => output.push(b'\r'),
+            't' => output.push(b'\t'),
+            'v' => output.push(0x0b),
+            '\\' => output.push(b'\\'),
+            '"' | '\'' | '?' if false == is_echo_style => push_char(&mut output, escape),
+            '0'..='7' if escape == '0' || false == is_echo_style => {
+                // echo 는 \0 뒤로 3자리, printf 는 \ 뒤로 3자리 (첫 자리 포함)
+                let start = if is_echo_style { idx } else { idx - 1 };
+                let len = count_digits(&chars[start..], 3, 8);
+                let value = parse_digits(&chars[start..start + len], 8);
+                output.push(value as u8);
+                idx = start + len;
+            }
+            'x' => {
+                let len = count_digits(&chars[idx..], 2, 16);
+                if len == 0 {
+                    output.extend_from_slice(b"\\x");
+                    continue;
+                }
+                output.push(parse_digits(&chars[idx..idx + len], 16) as u8);
+                idx += len;
+            }
+            'u' | 'U' => {
+                let max_len = if escape == 'u' { 4 } else { 8 };
+                let len = count_digits(&chars[idx..], max_len, 16);
+                if len == 0 {
+                    output.push(b'\\');
+                    push_char(&mut output, escape);
+                    continue;
+                }
+                let value = parse_digits(&chars[idx..idx + len], 16);
+                push_char(&mut output, char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER));
+                idx += len;
+            }
+            _ => {
+                output.push(b'\\');
+                push_char(&mut output, escape);
+            }
+        }
+    }
+
+    (output, false)
+}
+
+fn count_digits(chars: &[char], max_len: usize, radix: u32) -> usize {
+    chars.iter().take(max_len).take_while(|char| char.is_digit(radix)).count()
+}
+
+fn parse_digits(chars: &[char], radix: u32) -> u32 {
+    chars.iter().fold(0u32, |value, char| value.wrapping_mul(radix).wrapping_add(char.to_digit(radix).unwrap_or(0)))
+}
+
+fn push_char(output: &mut Vec<u8>, char: char) {
+    let mut buf = [0u8; 4];
+    output.extend_from_slice(char.encode_utf8(&mut buf).as_bytes());
+}
@@ -0,0 +1,140 @@
+// hash builtin
+//   hash               : 기억된 command 의 사용 횟수와 경로 출력
+//   hash name...       : PATH 에서 찾아 경로를 기억
+//   hash -r            : 기억된 경로를 모두 지우고 PATH 를 다시 scan
+//   hash -l            : 다시 입력할 수 있는 형태로 출력
+//   hash -p path name  : name 을 path 로 기억
+//   hash -d name...    : 기억된 경로 삭제
+//   hash -t name...    : 기억된 경로 출력
+
+use std::path::Path;
+
+use crate::shell_state::command_hash::{HashedCommand, clear_command_cache, get_hashed_command, get_hashed_commands, hash_command, remove_hashed_command, set_hashed_command};
+
+pub fn command_hash(args: &[String]) -> i32 {
+    let mut is_list = false;
+    let mut is_delete = false;
+    let mut is_print_path = false;
+    let mut hash_path: Option<&String> = None;
+
+    let mut idx = 0;
+    while idx < args.len() && args[idx].starts_with('-') && args[idx].len() > 1 {
+        let arg = &args[idx];
+        idx += 1;
+        if arg == "--" {
+            break;
+        }
+
+        for flag in arg[1..].chars() {
+            match flag {
+                'r' => clear_command_cache(),
+                'l' => is_list = true,
+                'd' => is_delete = true,
+                't' => is_print_path = true,
+                'p' => {
+                    let Some(path) = args.get(idx) else {
+                        eprintln!("hash: -p: option requires an argument");
+                        return 2;
+                    };
+                    hash_path = Some(path);
+                    idx += 1;
+                }
+                _ => {
+                    eprintln!("hash: -{}: invalid option", flag);
+                    eprintln!("hash: usage: hash [-lr] [-p pathname] [-dt] [name ...]");
+                    return 2;
+                }
+            }
+        }
+    }
+    let names = &args[idx..];
+
+    if names.is_empty() {
+        if is_delete || is_print_path || hash_path.is_some() {
+            eprintln!("hash: option requires an argument");
+            return 1;
+        }
+        // -r 만 있으면 출력하지 않음
+        if args.is_empty() || is_list {
+            print_hashed_commands(&get_hashed_commands(), is_list);
+        }
+        return 0;
+    }
+
+    let mut status = 0;
+
+    if let Some(hash_path) = hash_path {
+        for name in names {
+            set_hashed_command(name, Path::new(hash_path));
+        }
+        return status;
+    }
+
+    if is_delete {
+        for name in names {
+            if false == remove_hashed_command(name) {
+                eprintln!("hash: {}: not found", name);
+                status = 1;
+            }
+        }
+        return status;
+    }
+
+    if is_print_path || is_list {
+        let mut hashed_commands = vec![];
+        for name in names {
+            match get_hashed_command(name) {
+                Some(hashed_command) => hashed_commands.push(hashed_command),
+                None => {
+                    eprintln!("hash: {}: not found", name);
+                    status = 1;
+                }
+            }
+        }
+
+        if is_list {
+            print_hashed_commands(&hashed_commands, true);
+        } else if names.len() == 1 {
+            for hashed_command in &hashed_commands {
+                println!("{}", hashed_command.path.display());
+            }
+        } else {
+            for hashed_command in &hashed_commands {
+                println!("{}\t{}", hashed_command.name, hashed_command.path.display());
+            }
+        }
+        return status;
+    }
+
+    for name in names {
+        // builtin 은 hash 하지 않음
+        if crate::COMMAND.contains(&name.as_str()) {
+            continue;
+        }
+        if hash_command(name).is_none() {
+            eprintln!("hash: {}: not found", name);
+            status = 1;
+        }
+    }
+
+    status
+}
+
+fn print_hashed_commands(hashed_commands: &[HashedCommand], is_reusable: bool) {
+    if hashed_commands.is_empty() {
+        println!("hash: hash table empty");
+        return;
+    }
+
+    if is_reusable {
+        for hashed_command in hashed_commands {
+            println!("builtin hash -p {} {}", hashed_command.path.display(), hashed_command.name);
+        }
+        return;
+    }
+
+    println!("hits\tcommand");
+    for hashed_command in hashed_commands {
+        println!("{:>4}\t{}", hashed_command.hits, hashed_command.path.display());
+    }
+}
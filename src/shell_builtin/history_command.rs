@@ -0,0 +1,275 @@
+// history builtin
+//   history [N]        : 전체 혹은 마지막 N 개 출력
+//   history -c         : 전체 삭제
+//   history -d N       : N 번째 삭제 (음수면 뒤에서부터)
+//   history -w [file]  : 현재 history 로 파일 덮어쓰기
+//   history -r [file]  : 파일 내용을 history 에 추가
+//   history -a [file]  : 이번 세션에서 추가된 history 를 파일에 append
+// 실행 기록(HISTDB) 조회, 옵션은 같이 쓸 수 있음
+//   history --cwd [dir]     : 현재 (혹은 지정한) 디렉토리에서 실행한 command, --cwd=dir 도 가능
+//                             숫자만 있는 인자는 dir 이 아니라 N
+//   history --failed        : exit status 가 0 이 아닌 command
+//   history --since time    : 30s, 10m, 2h, 7d, 1w 전 혹은 YYYY-MM-DD [HH:MM[:SS]], @unixtime 이후
+//   history --json          : 기록된 JSON 형식 그대로 출력
+//   history --failed N      : 조회 결과 중 마지막 N 개
+// 디렉토리는 cd 와 같은 논리 경로(PWD)로 기록하고 비교
+
+use std::path::PathBuf;
+
+use crate::rustyline_editor::history_search::format_duration;
+use crate::rustyline_editor::prompt::format_timestamp;
+use crate::shell_builtin::cd_command::{get_logical_current_dir, normalize_logical_path};
+use crate::shell_state::history::{append_history_file, clear_history, delete_history_entry, get_history_entries, get_history_file_path, get_unix_time, read_history_file, write_history_file};
+use crate::shell_state::history_database::{format_history_record, get_history_database_path, read_history_records};
+
+pub fn command_history(args: &[String]) -> i32 {
+    if args.iter().any(|arg| arg.starts_with("--") && arg.len() > 2) {
+        return query_history_database(args);
+    }
+
+    let Some(option) = args.first() else {
+        print_history(None);
+        return 0;
+    };
+
+    match option.as_str() {
+        "-c" => {
+            clear_history();
+            0
+        }
+        "-d" => {
+            let Some(offset) = args.get(1) else {
+                eprintln!("history: -d: option requires an argument");
+                return 2;
+            };
+            history_delete(offset)
+        }
+        "-w" | "-r" | "-a" => {
+            let Some(history_file_path) = args.get(1).map(PathBuf::from).or_else(get_history_file_path) else {
+                eprintln!("history: HISTFILE not set");
+                return 1;
+            };
+
+            let result = match option.as_str() {
+                "-w" => write_history_file(&history_file_path),
+                "-r" => read_history_file(&history_file_path),
+                _ => append_history_file(&history_file_path),
+            };
+
+            match result {
+                Ok(_) => 0,
+                Err(e) => {
+                    eprintln!("history: {}", e);
+                    1
+                }
+            }
+        }
+        _ if option.starts_with('-') && option.len() > 1 && option.parse::<i64>().is_err() => {
+            eprintln!("history: {}: invalid option", option);
+            eprintln!("history: usage: history [-c] [-d offset] [n] or history -awr [filename]");
+            2
+        }
+        _ => {
+            let Ok(count) = option.parse::<usize>() else {
+                eprintln!("history: {}: numeric argument required", option);
+                return 1;
+            };
+            print_history(Some(count));
+            0
+        }
+    }
+}
+
+fn print_history(count: Option<usize>) {
+    let history_entries = get_history_entries();
+    let skip_count = match count {
+        Some(count) => history_entries.len().saturating_sub(count),
+        None => 0,
+    };
+
+    for (idx, history_entry) in history_entries.iter().enumerate().skip(skip_count) {
+        println!("{:>5}  {}", idx + 1, history_entry.line);
+    }
+}
+
+fn history_delete(offset: &str) -> i32 {
+    let history_len = get_history_entries().len() as i64;
+
+    let Ok(offset_number) = offset.parse::<i64>() else {
+        eprintln!("history: {}: history position out of range", offset);
+        return 1;
+    };
+
+    // 양수는 1 부터, 음수는 뒤에서부터
+    let index = if offset_number < 0 { history_len + offset_number } else { offset_number - 1 };
+    if index < 0 || false == delete_history_entry(index as usize) {
+        eprintln!("history: {}: history position out of range", offset);
+        return 1;
+    }
+
+    0
+}
+
+struct HistoryQuery {
+    cwd: Option<String>,
+    is_failed_only: bool,
+    since: Option<i64>,
+    is_json: bool,
+    count: Option<usize>,
+}
+
+fn query_history_database(args: &[String]) -> i32 {
+    let mut history_query = HistoryQuery {
+        cwd: None,
+        is_failed_only: false,
+        since: None,
+        is_json: false,
+        count: None,
+    };
+
+    let mut idx = 0;
+    while idx < args.len() {
+        let arg = &args[idx];
+        idx += 1;
+
+        let (option, value) = match arg.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => (option, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        match option {
+            "--cwd" => {
+                // --cwd dir 처럼 따로 쓴 dir, 숫자는 N
+                let dir = value.or_else(|| {
+                    let next_arg = args.get(idx).filter(|next_arg| false == next_arg.starts_with('-') && false == next_arg.chars().all(|char| char.is_ascii_digit()))?;
+                    idx += 1;
+                    Some(next_arg.clone())
+                });
+                // 기록된 디렉토리와 같은 형식 (. .. 가 없는 논리 절대 경로)
+                let current_dir = get_logical_current_dir();
+                let cwd = match dir {
+                    Some(dir) => normalize_logical_path(&current_dir.join(dir)),
+                    None => current_dir,
+                };
+                history_query.cwd = Some(cwd.to_string_lossy().into_owned());
+            }
+            "--failed" => history_query.is_failed_only = true,
+            "--json" => history_query.is_json = true,
+            "--since" => {
+                let Some(time) = value.or_else(|| args.get(idx).cloned()) else {
+                    eprintln!("history: --since: option requires an argument");
+                    return 2;
+                };
+                if false == arg.contains('=') {
+                    idx += 1;
+                }
+                let Some(since) = parse_since_time(&time) else {
+                    eprintln!("history: {}: invalid time", time);
+                    return 1;
+                };
+                history_query.since = Some(since);
+            }
+            _ if false == arg.starts_with('-') => {
+                let Ok(count) = arg.parse::<usize>() else {
+                    eprintln!("history: {}: numeric argument required", arg);
+                    return 1;
+                };
+                history_query.count = Some(count);
+            }
+            _ => {
+                eprintln!("history: {}: invalid option", arg);
+                eprintln!("history: usage: history [--cwd [dir]] [--failed] [--since time] [--json] [n]");
+                return 2;
+            }
+        }
+    }
+
+    let Some(history_database_path) = get_history_database_path() else {
+        eprintln!("history: HISTDB not set");
+        return 1;
+    };
+    let history_records = match read_history_records(&history_database_path) {
+        Ok(history_records) => history_records,
+        Err(e) => {
+            eprintln!("history: {}", e);
+            return 1;
+        }
+    };
+
+    // 번호는 기록 파일에서의 순서
+    let matched_records: Vec<_> = history_records
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, history_record)| history_record.metadata.as_ref().map(|metadata| (idx, &history_record.line, metadata)))
+        .filter(|(_, _, metadata)| history_query.cwd.as_ref().is_none_or(|cwd| &metadata.cwd == cwd))
+        .filter(|(_, _, metadata)| false == history_query.is_failed_only || metadata.exit_status.is_some_and(|exit_status| exit_status != 0))
+        .filter(|(_, _, metadata)| history_query.since.is_none_or(|since| metadata.start_time >= since))
+        .collect();
+    let skip_count = history_query.count.map(|count| matched_records.len().saturating_sub(count)).unwrap_or(0);
+
+    for (idx, line, metadata) in matched_records.into_iter().skip(skip_count) {
+        if history_query.is_json {
+            println!("{}", format_history_record(line, metadata));
+            continue;
+        }
+
+        let exit_status = metadata.exit_status.map(|exit_status| exit_status.to_string()).unwrap_or("-".to_string());
+        let duration = metadata.duration_ms.map(format_duration).unwrap_or("-".to_string());
+        println!(
+            "{:>5}  {}  {:>3}  {:>7}  {}  {}",
+            idx + 1,
+            format_timestamp(metadata.start_time, "%Y-%m-%d %H:%M:%S"),
+            exit_status,
+            duration,
+            metadata.cwd,
+            line
+        );
+    }
+
+    0
+}
+
+// --since 의 시각을 unix time 으로
+fn parse_since_time(time: &str) -> Option<i64> {
+    if let Some(unix_time) = time.strip_prefix('@') {
+        return unix_time.parse().ok();
+    }
+
+    // 지금부터 얼마 전
+    if let Some(unit) = time.chars().last().filter(|unit| "smhdw".contains(*unit))
+        && let Ok(amount) = time[..time.len() - 1].parse::<i64>() {
+        let seconds = match unit {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            _ => 7 * 24 * 60 * 60,
+        };
+        return Some(get_unix_time() - amount * seconds);
+    }
+
+    // 지역 시간의 YYYY-MM-DD [HH:MM[:SS]], 날짜와 시간 사이는 T 도 가능
+    let (date, clock) = time.split_once([' ', 'T']).unwrap_or((time, "00:00"));
+    let date: Vec<i32> = date.split('-').map(|number| number.parse().ok()).collect::<Option<_>>()?;
+    let clock: Vec<i32> = clock.split(':').map(|number| number.parse().ok()).collect::<Option<_>>()?;
+    if date.len() != 3 || false == (2..=3).contains(&clock.len()) {
+        return None;
+    }
+
+    let timestamp = unsafe {
+        let mut local_time: libc::tm = std::mem::zeroed();
+        local_time.tm_year = date[0] - 1900;
+        local_time.tm_mon = date[1] - 1;
+        local_time.tm_mday = date[2];
+        local_time.tm_hour = clock[0];
+        local_time.tm_min = clock[1];
+        local_time.tm_sec = clock.get(2).copied().unwrap_or(0);
+        // 서머타임 여부는 mktime 이 판단
+        local_time.tm_isdst = -1;
+        libc::mktime(&mut local_time)
+    };
+    if timestamp == -1 {
+        return None;
+    }
+
+    Some(timestamp)
+}
@@ -0,0 +1,12 @@
+pub mod bind_command;
+pub mod cd_command;
+pub mod complete_command;
+pub mod dirs_command;
+pub mod echo_command;
+pub mod hash_command;
+pub mod history_command;
+pub mod printf_command;
+pub mod read_command;
+pub mod set_command;
+pub mod test_command;
+pub mod z_command;
\ No newline at end of file
@@ -0,0 +1,653 @@
+// printf builtin
+//   printf [-v var] format [arguments]
+//     -v var : 출력하지 않고 변수 var 에 저장
+// format 의 지시자 : %[flags][width][.precision]conversion
+//   flags      : - (왼쪽 정렬) + (부호) 공백 (양수 앞 공백) # (0x, 0 접두사 / 소수점 유지) 0 (0 으로 채움)
+//   width      : 숫자 혹은 * (인자에서 읽음, 음수면 왼쪽 정렬)
+//   precision  : .숫자 혹은 .* , 정수는 최소 자릿수, 실수는 소수점 아래 자릿수, 문자열은 최대 길이
+//   conversion : s d i u x X o f F e E g G c b q %
+//     %b 는 인자의 백슬래쉬 escape 를 echo -e 처럼 해석, %q 는 shell 입력으로 다시 쓸 수 있게 quote
+// 인자가 format 에서 쓰는 것보다 많으면 다 쓸 때까지 format 을 반복, 모자라면 빈 문자열이나 0 으로 취급
+// 숫자 인자는 10진수, 0x 로 시작하면 16진수, 0 으로 시작하면 8진수, 'c 나 "c 면 문자 c 의 코드
+// 숫자가 아닌 인자는 에러를 출력하고 앞부분의 숫자만 사용, 종료 상태는 1
+// \c (format 혹은 %b 인자) 를 만나면 그 뒤는 출력하지 않음
+
+use std::io::{self, Write};
+
+use crate::shell_builtin::echo_command::interpret_backslash_escapes;
+use crate::shell_state::variables::{is_valid_variable_name, set_variable};
+
+pub fn command_printf(args: &[String]) -> i32 {
+    let mut variable_name: Option<&str> = None;
+
+    let mut idx = 0;
+    while idx < args.len() && args[idx].starts_with('-') && args[idx].len() > 1 {
+        let arg = &args[idx];
+        idx += 1;
+        if arg == "--" {
+            break;
+        }
+
+        match arg.strip_prefix("-v") {
+            Some("") if idx < args.len() => {
+                variable_name = Some(&args[idx]);
+                idx += 1;
+            }
+            Some(name) if false == name.is_empty() => variable_name = Some(name),
+            Some(_) => {
+                eprintln!("printf: -v: option requires an argument");
+                eprintln!("printf: usage: printf [-v var] format [arguments]");
+                return 2;
+            }
+            None => {
+                eprintln!("printf: {}: invalid option", arg);
+                eprintln!("printf: usage: printf [-v var] format [arguments]");
+                return 2;
+            }
+        }
+    }
+
+    if let Some(name) = variable_name && false == is_valid_variable_name(name) {
+        eprintln!("printf: `{}': not a valid identifier", name);
+        return 2;
+    }
+
+    let Some(format) = args.get(idx) else {
+        eprintln!("printf: usage: printf [-v var] format [arguments]");
+        return 2;
+    };
+
+    let mut formatter = PrintfFormatter {
+        args: &args[idx + 1..],
+        arg_idx: 0,
+        output: vec![],
+        status: 0,
+    };
+    formatter.format_all(format);
+
+    match variable_name {
+        Some(name) => set_variable(name, &String::from_utf8_lossy(&formatter.output)),
+        None => {
+            let mut stdout = io::stdout().lock();
+            if let Err(e) = stdout.write_all(&formatter.output).and_then(|_| stdout.flush()) {
+                eprintln!("printf: write error: {}", e);
+                return 1;
+            }
+        }
+    }
+
+    formatter.status
+}
+
+#[derive(Debug, Default)]
+struct FormatSpec {
+    is_left_align: bool,
+    is_plus_sign: bool,
+    is_space_sign: bool,
+    is_alternate: bool,
+    is_zero_pad: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+struct PrintfFormatter<'a> {
+    args: &'a [String],
+    arg_idx: usize,
+    output: Vec<u8>,
+    status: i32,
+}
+
+impl PrintfFormatter<'_> {
+    // 인자를 다 쓸 때까지 format 반복, 인자를 하나도 쓰지 않는 format 이면 한번만
+    fn format_all(&mut self, format: &str) {
+        loop {
+            let previous_arg_idx = self.arg_idx;
+            if false == self.format_once(format) {
+                return;
+            }
+            if self.arg_idx >= self.args.len() || self.arg_idx == previous_arg_idx {
+                return;
+            }
+        }
+    }
+
+    // \c 나 잘못된 지시자로 멈췄으면 false
+    fn format_once(&mut self, format: &str) -> bool {
+        let chars: Vec<char> = format.chars().collect();
+
+        let mut idx = 0;
+        while idx < chars.len() {
+            if chars[idx] != '%' {
+                // 다음 % 까지는 escape 만 해석
+                let end = chars[idx..].iter().position(|char| *char == '%').map_or(chars.len(), |offset| idx + offset);
+                let literal: String = chars[idx..end].iter().collect();
+                let (interpreted, is_stopped) = interpret_backslash_escapes(&literal, false);
+                self.output.extend(interpreted);
+                if is_stopped {
+                    return false;
+                }
+                idx = end;
+                continue;
+            }
+
+            idx += 1;
+            if chars.get(idx) == Some(&'%') {
+                self.output.push(b'%');
+                idx += 1;
+                continue;
+            }
+
+            let mut spec = FormatSpec::default();
+            while let Some(flag) = chars.get(idx) {
+                match flag {
+                    '-' => spec.is_left_align = true,
+                    '+' => spec.is_plus_sign = true,
+                    ' ' => spec.is_space_sign = true,
+                    '#' => spec.is_alternate = true,
+                    '0' => spec.is_zero_pad = true,
+                    _ => break,
+                }
+                idx += 1;
+            }
+
+            if chars.get(idx) == Some(&'*') {
+                let width = self.next_integer();
+                if width < 0 {
+                    spec.is_left_align = true;
+                }
+                spec.width = width.unsigned_abs() as usize;
+                idx += 1;
+            } else {
+                let (width, next_idx) = parse_format_number(&chars, idx);
+                spec.width = width;
+                idx = next_idx;
+            }
+
+            if chars.get(idx) == Some(&'.') {
+                idx += 1;
+                if chars.get(idx) == Some(&'*') {
+                    // 음수면 precision 이 없는 것과 같음
+                    let precision = self.next_integer();
+                    spec.precision = if precision < 0 { None } else { Some(precision as usize) };
+                    idx += 1;
+                } else {
+                    let (precision, next_idx) = parse_format_number(&chars, idx);
+                    spec.precision = Some(precision);
+                    idx = next_idx;
+                }
+            }
+
+            // bash 처럼 길이 지정자는 무시
+            while let Some('h' | 'l' | 'L') = chars.get(idx) {
+                idx += 1;
+            }
+
+            let Some(conversion) = chars.get(idx).copied() else {
+                eprintln!("printf: `%': missing format character");
+                self.status = 1;
+                return false;
+            };
+            idx += 1;
+
+            if false == self.format_argument(conversion, &spec) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn format_argument(&mut self, conversion: char, spec: &FormatSpec) -> bool {
+        match conversion {
+            's' => {
+                let arg = self.next_string();
+                let arg = match spec.precision {
+                    Some(precision) => arg.chars().take(precision).collect(),
+                    None => arg,
+                };
+                self.push_padded(arg.as_bytes(), spec);
+            }
+            'b' => {
+                let (mut interpreted, is_stopped) = interpret_backslash_escapes(&self.next_string(), true);
+                if let Some(precision) = spec.precision {
+                    interpreted.truncate(precision);
+                }
+                self.push_padded(&interpreted, spec);
+                if is_stopped {
+                    return false;
+                }
+            }
+            'q' => {
+                let quoted = quote_shell_word(&self.next_string());
+                self.push_padded(quoted.as_bytes(), spec);
+            }
+            'c' => {
+                let arg = self.next_string();
+                let first: String = arg.chars().take(1).collect();
+                self.push_padded(first.as_bytes(), spec);
+            }
+            'd' | 'i' => {
+                let value = self.next_integer();
+                let sign = get_sign(value < 0, spec);
+                let digits = apply_integer_precision(value.unsigned_abs().to_string(), spec);
+                self.push_number(sign, "", &digits, spec, spec.precision.is_none());
+            }
+            'u' | 'o' | 'x' | 'X' => {
+                // 음수는 2의 보수로
+                let value = self.next_unsigned_integer();
+                let digits = match conversion {
+                    'o' => format!("{:o}", value),
+                    'x' => format!("{:x}", value),
+                    'X' => format!("{:X}", value),
+                    _ => value.to_string(),
+                };
+                let mut digits = apply_integer_precision(digits, spec);
+                let prefix = match conversion {
+                    'x' if spec.is_alternate && value != 0 => "0x",
+                    'X' if spec.is_alternate && value != 0 => "0X",
+                    _ => "",
+                };
+                if conversion == 'o' && spec.is_alternate && false == digits.starts_with('0') {
+                    digits.insert(0, '0');
+                }
+                self.push_number("", prefix, &digits, spec, spec.precision.is_none());
+            }
+            'f' | 'F' | 'e' | 'E' | 'g' | 'G' => {
+                let value = self.next_float();
+                let sign = get_sign(value.is_sign_negative() && false == value.is_nan(), spec);
+                let digits = format_float(value.abs(), conversion, spec);
+                self.push_number(sign, "", &digits, spec, value.is_finite());
+            }
+            _ => {
+                eprintln!("printf: `{}': invalid format character", conversion);
+                self.status = 1;
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn next_string(&mut self) -> String {
+        let arg = self.args.get(self.arg_idx).cloned().unwrap_or_default();
+        self.arg_idx += 1;
+        arg
+    }
+
+    fn next_integer(&mut self) -> i64 {
+        let arg = self.next_string();
+        let (value, error) = parse_integer_argument(&arg);
+        match error {
+            Some(error) => self.report_invalid_argument(&arg, error),
+            None if i64::try_from(value).is_err() => self.report_invalid_argument(&arg, "Numerical result out of range"),
+            None => {}
+        }
+
+        value.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    fn next_unsigned_integer(&mut self) -> u64 {
+        let arg = self.next_string();
+        let (value, error) = parse_integer_argument(&arg);
+        if let Some(error) = error {
+            self.report_invalid_argument(&arg, error);
+        }
+
+        if value < 0 {
+            return (value as i64) as u64;
+        }
+        value as u64
+    }
+
+    fn next_float(&mut self) -> f64 {
+        let arg = self.next_string();
+        let trimmed = arg.trim_start();
+        if let Ok(value) = trimmed.parse::<f64>() {
+            return value;
+        }
+
+        // 0x10, 'a 처럼 정수로만 읽을 수 있는 인자
+        let (value, error) = parse_integer_argument(&arg);
+        if error.is_none() {
+            return value as f64;
+        }
+
+        // 앞에서부터 가장 길게 읽을 수 있는 부분
+        let prefix_value = (1..trimmed.len())
+            .rev()
+            .filter(|len| trimmed.is_char_boundary(*len))
+            .find_map(|len| trimmed[..len].parse::<f64>().ok())
+            .unwrap_or(0.0);
+        self.report_invalid_argument(&arg, "invalid number");
+        prefix_value
+    }
+
+    fn report_invalid_argument(&mut self, arg: &str, error: &str) {
+        eprintln!("printf: {}: {}", arg, error);
+        self.status = 1;
+    }
+
+    fn push_padded(&mut self, value: &[u8], spec: &FormatSpec) {
+        let len = String::from_utf8_lossy(value).chars().count();
+        let padding = " ".repeat(spec.width.saturating_sub(len));
+
+        if spec.is_left_align {
+            self.output.extend_from_slice(value);
+            self.output.extend_from_slice(padding.as_bytes());
+        } else {
+            self.output.extend_from_slice(padding.as_bytes());
+            self.output.extend_from_slice(value);
+        }
+    }
+
+    // 0 으로 채울 때는 부호와 0x 뒤에 채움, 정수에 precision 이 있거나 inf, nan 이면 0 으로 채우지 않음
+    fn push_number(&mut self, sign: &str, prefix: &str, digits: &str, spec: &FormatSpec, is_zero_pad_allowed: bool) {
+        let len = sign.len() + prefix.len() + digits.chars().count();
+        let is_zero_pad = spec.is_zero_pad && false == spec.is_left_align && is_zero_pad_allowed;
+
+        let mut number = String::new();
+        if is_zero_pad {
+            number.push_str(sign);
+            number.push_str(prefix);
+            number.push_str(&"0".repeat(spec.width.saturating_sub(len)));
+            number.push_str(digits);
+            self.output.extend_from_slice(number.as_bytes());
+            return;
+        }
+
+        number.push_str(sign);
+        number.push_str(prefix);
+        number.push_str(digits);
+        self.push_padded(number.as_bytes(), spec);
+    }
+}
+
+fn get_sign(is_negative: bool, spec: &FormatSpec) -> &'static str {
+    if is_negative {
+        "-"
+    } else if spec.is_plus_sign {
+        "+"
+    } else if spec.is_space_sign {
+        " "
+    } else {
+        ""
+    }
+}
+
+// 정수의 precision 은 최소 자릿수, .0 에 0 이면 아무것도 출력하지 않음
+fn apply_integer_precision(digits: String, spec: &FormatSpec) -> String {
+    match spec.precision {
+        Some(0) if digits == "0" => String::new(),
+        Some(precision) if digits.len() < precision => format!("{}{}", "0".repeat(precision - digits.len()), digits),
+        _ => digits,
+    }
+}
+
+fn parse_format_number(chars: &[char], start: usize) -> (usize, usize) {
+    let mut idx = start;
+    let mut value: usize = 0;
+    while let Some(digit) = chars.get(idx).and_then(|char| char.to_digit(10)) {
+        value = value.saturating_mul(10).saturating_add(digit as usize);
+        idx += 1;
+    }
+    (value, idx)
+}
+
+// 숫자 인자와 에러 메세지, 에러가 있으면 값은 앞부분에서 읽은 숫자
+// u64 범위까지 읽어야 해서 i128 로 반환
+fn parse_integer_argument(arg: &str) -> (i128, Option<&'static str>) {
+    let trimmed = arg.trim_start();
+    if trimmed.is_empty() {
+        return (0, None);
+    }
+
+    // 'a, "a 는 문자 코드
+    if let Some(rest) = trimmed.strip_prefix(['\'', '"']) {
+        return (rest.chars().next().map_or(0, |char| char as i128), None);
+    }
+
+    let (is_negative, unsigned) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let (radix, digits) = if let Some(hex) = unsigned.strip_prefix("0x").or_else(|| unsigned.strip_prefix("0X")) {
+        (16, hex)
+    } else if unsigned.len() > 1 && unsigned.starts_with('0') {
+        (8, &unsigned[1..])
+    } else {
+        (10, unsigned)
+    };
+
+    let mut value: i128 = 0;
+    let mut is_out_of_range = false;
+    let mut digit_count = 0;
+    for char in digits.chars() {
+        let Some(digit) = char.to_digit(radix) else {
+            break;
+        };
+        value = value * radix as i128 + digit as i128;
+        if value > u64::MAX as i128 {
+            value = u64::MAX as i128;
+            is_out_of_range = true;
+        }
+        digit_count += 1;
+    }
+    if is_negative {
+        value = -value;
+    }
+
+    // 0x 뒤에 숫자가 없거나 숫자 뒤에 다른 문자가 있으면 잘못된 숫자
+    let is_invalid = digit_count < digits.chars().count() || (radix == 16 && digit_count == 0) || (radix == 10 && digit_count == 0);
+    if is_invalid {
+        return (value, Some("invalid number"));
+    }
+    if is_out_of_range {
+        return (value, Some("Numerical result out of range"));
+    }
+
+    (value, None)
+}
+
+// 부호를 뺀 실수 문자열 (value 는 0 이상)
+fn format_float(value: f64, conversion: char, spec: &FormatSpec) -> String {
+    let is_upper = conversion.is_ascii_uppercase();
+    if false == value.is_finite() {
+        let text = if value.is_nan() { "nan" } else { "inf" };
+        return if is_upper { text.to_uppercase() } else { text.to_string() };
+    }
+
+    let precision = spec.precision.unwrap_or(6);
+    let text = match conversion.to_ascii_lowercase() {
+        'f' => {
+            let mut text = format!("{:.*}", precision, value);
+            if spec.is_alternate && precision == 0 {
+                text.push('.');
+            }
+            text
+        }
+        'e' => format_exponent(value, precision, spec.is_alternate),
+        _ => {
+            // %g : 지수가 -4 이상 precision 미만이면 %f, 아니면 %e, # 이 없으면 끝의 0 을 지움
+            let precision = precision.max(1);
+            let exponent = get_decimal_exponent(value, precision - 1);
+            let text = if exponent < -4 || exponent >= precision as i32 {
+                format_exponent(value, precision - 1, spec.is_alternate)
+            } else {
+                format!("{:.*}", (precision as i32 - 1 - exponent) as usize, value)
+            };
+            if spec.is_alternate {
+                text
+            } else {
+                trim_fraction_zeros(&text)
+            }
+        }
+    };
+
+    if is_upper { text.to_uppercase() } else { text }
+}
+
+// C 의 %e 형식 : 1.500000e+02
+fn format_exponent(value: f64, precision: usize, is_alternate: bool) -> String {
+    let text = format!("{:.*e}", precision, value);
+    let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+
+    let mut mantissa = mantissa.to_string();
+    if is_alternate && precision == 0 {
+        mantissa.push('.');
+    }
+    format!("{}e{}{:02}", mantissa, if exponent < 0 { '-' } else { '+' }, exponent.abs())
+}
+
+// precision 자리로 반올림한 뒤의 10진 지수
+fn get_decimal_exponent(value: f64, precision: usize) -> i32 {
+    if value == 0.0 {
+        return 0;
+    }
+    let text = format!("{:.*e}", precision, value);
+    text.split_once('e').and_then(|(_, exponent)| exponent.parse().ok()).unwrap_or(0)
+}
+
+fn trim_fraction_zeros(text: &str) -> String {
+    let (mantissa, exponent) = match text.find('e') {
+        Some(idx) => text.split_at(idx),
+        None => (text, ""),
+    };
+    if false == mantissa.contains('.') {
+        return text.to_string();
+    }
+
+    let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+    format!("{}{}", mantissa, exponent)
+}
+
+// shell 입력으로 다시 쓸 수 있게 quote, 제어 문자가 있으면 $'...' 형식
+fn quote_shell_word(word: &str) -> String {
+    if word.is_empty() {
+        return "''".to_string();
+    }
+
+    if word.chars().any(|char| char.is_control()) {
+        let mut quoted = String::from("$'");
+        for char in word.chars() {
+            match char {
+                '\n' => quoted.push_str("\\n"),
+                '\t' => quoted.push_str("\\t"),
+                '\r' => quoted.push_str("\\r"),
+                '\x1b' => quoted.push_str("\\E"),
+                '\x07' => quoted.push_str("\\a"),
+                '\x08' => quoted.push_str("\\b"),
+                '\x0b' => quoted.push_str("\\v"),
+                '\x0c' => quoted.push_str("\\f"),
+                '\\' | '\'' => {
+                    quoted.push('\\');
+                    quoted.push(char);
+                }
+                _ if char.is_control() => quoted.push_str(&format!("\\{:03o}", char as u32)),
+                _ => quoted.push(char),
+            }
+        }
+        quoted.push('\'');
+        return quoted;
+    }
+
+    let mut quoted = String::new();
+    for (idx, char) in word.chars().enumerate() {
+        let is_safe = char.is_alphanumeric() || matches!(char, '_' | '.' | '/' | '-' | '+' | ',' | ':' | '=' | '@' | '%' | '^')
+            || (char == '~' && idx > 0);
+        if false == is_safe {
+            quoted.push('\\');
+        }
+        quoted.push(char);
+    }
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PrintfFormatter, command_printf};
+    use crate::shell_state::variables::get_variable;
+
+    // format 을 실행한 출력과 종료 상태
+    fn printf(format: &str, args: &[&str]) -> (String, i32) {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let mut formatter = PrintfFormatter {
+            args: &args,
+            arg_idx: 0,
+            output: vec![],
+            status: 0,
+        };
+        formatter.format_all(format);
+
+        (String::from_utf8_lossy(&formatter.output).into_owned(), formatter.status)
+    }
+
+    #[test]
+    fn format_is_reused_for_remaining_arguments() {
+        assert_eq!(printf("%s-%s\n", &["1", "2", "3"]), ("1-2\n3-\n".to_string(), 0));
+        assert_eq!(printf("[%s]", &["a", "b"]), ("[a][b]".to_string(), 0));
+        // 인자를 쓰지 않는 format 은 한번만
+        assert_eq!(printf("plain\n", &["unused"]), ("plain\n".to_string(), 0));
+        // 모자란 인자는 빈 문자열이나 0
+        assert_eq!(printf("%s|%d|%s", &[]), ("|0|".to_string(), 0));
+    }
+
+    #[test]
+    fn width_precision_and_flags() {
+        assert_eq!(printf("[%5s][%-5s][%.1s]", &["ab", "ab", "xyz"]).0, "[   ab][ab   ][x]");
+        assert_eq!(printf("%04d|%+d|% d|%.3d", &["42", "5", "5", "7"]).0, "0042|+5| 5|007");
+        assert_eq!(printf("%x|%X|%#x|%o|%#o", &["255", "255", "255", "8", "8"]).0, "ff|FF|0xff|10|010");
+        assert_eq!(printf("%5.2f|%e|%g", &["3.14159", "12345.678", "0.0001"]).0, " 3.14|1.234568e+04|0.0001");
+        assert_eq!(printf("%c%c|%%", &["hello", "world"]).0, "hw|%");
+    }
+
+    #[test]
+    fn star_width_and_precision_read_arguments() {
+        assert_eq!(printf("[%*d]", &["5", "42"]).0, "[   42]");
+        // 음수 width 는 왼쪽 정렬
+        assert_eq!(printf("[%*d]", &["-4", "7"]).0, "[7   ]");
+        assert_eq!(printf("[%.*s]", &["2", "abcdef"]).0, "[ab]");
+        assert_eq!(printf("[%*.*f]", &["8", "3", "2.5"]).0, "[   2.500]");
+    }
+
+    #[test]
+    fn b_conversion_interprets_escapes() {
+        assert_eq!(printf("%b", &["a\\tb\\x41\\n"]).0, "a\tbA\n");
+        // %s 는 escape 를 해석하지 않음
+        assert_eq!(printf("%s", &["a\\tb"]).0, "a\\tb");
+        // \c 뒤는 출력하지 않고 format 반복도 멈춤
+        assert_eq!(printf("%b|", &["one\\ctwo", "three"]).0, "one");
+    }
+
+    #[test]
+    fn q_conversion_quotes_for_shell_input() {
+        assert_eq!(printf("%q", &["a b"]).0, "a\\ b");
+        assert_eq!(printf("%q", &["it's"]).0, "it\\'s");
+        assert_eq!(printf("%q", &[""]).0, "''");
+        assert_eq!(printf("%q", &["tab\there"]).0, "$'tab\\there'");
+        assert_eq!(printf("%q", &["~/path"]).0, "\\~/path");
+    }
+
+    #[test]
+    fn numeric_arguments() {
+        assert_eq!(printf("%d %d %d", &["0x10", "010", "'A"]), ("16 8 65".to_string(), 0));
+        // 숫자가 아니면 앞부분의 숫자만 쓰고 종료 상태는 1
+        assert_eq!(printf("%d", &["12abc"]), ("12".to_string(), 1));
+        assert_eq!(printf("%d|%f", &["abc", "x"]), ("0|0.000000".to_string(), 1));
+    }
+
+    #[test]
+    fn v_option_assigns_variable() {
+        let args: Vec<String> = ["-v", "printf_test_variable", "%s=%03d", "n", "7"].iter().map(|arg| arg.to_string()).collect();
+
+        assert_eq!(command_printf(&args), 0);
+        assert_eq!(get_variable("printf_test_variable"), Some("n=007".to_string()));
+    }
+
+    #[test]
+    fn v_option_rejects_invalid_name() {
+        let args: Vec<String> = ["-v", "1invalid", "%s", "x"].iter().map(|arg| arg.to_string()).collect();
+
+        assert_eq!(command_printf(&args), 2);
+    }
+}
@@ -0,0 +1,408 @@
+// read builtin
+//   read [-rs] [-a array] [-d delim] [-n N] [-p prompt] [-t timeout] [name ...]
+//     -r         : 백슬래쉬를 escape 로 해석하지 않음
+//     -s         : 입력한 글자를 터미널에 표시하지 않음
+//     -a array   : 나눈 field 를 배열 변수 array 에 저장
+//     -d delim   : 개행 대신 delim 의 첫 글자까지 읽음 (빈 값이면 NUL)
+//     -n N       : delim 전이라도 N 글자를 읽으면 멈춤
+//     -p prompt  : 입력이 터미널이면 읽기 전에 prompt 를 stderr 로 출력
+//     -t timeout : timeout 초 (소수 가능) 안에 다 읽지 못하면 실패, 0 이면 읽을 입력이 있는지만 확인
+// 읽은 줄을 IFS (기본값 공백, 탭, 개행) 로 나눠서 name 에 차례로 저장하고 마지막 name 에는 나머지 전부를 저장
+// name 이 없으면 REPLY 에 줄 전체를 그대로 저장
+// -r 이 없으면 백슬래쉬 뒤의 글자는 IFS 로 나누지 않고, 백슬래쉬-개행은 이어지는 줄로 봄
+// 종료 상태 : 0 성공, 1 delim 전에 EOF (읽은 만큼은 저장), 142 timeout
+// 다음 command 가 남은 입력을 읽을 수 있도록 stdin 을 한 byte 씩 버퍼 없이 읽음
+// 입력이 터미널이 아니면 shell 도 read_input_line 으로 command 를 읽어서 read 와 같은 입력을 이어서 읽음
+
+use std::{io::{self, Write}, time::{Duration, Instant}};
+
+use crate::shell_state::variables::{get_variable, is_valid_variable_name, set_array_variable, set_variable};
+
+const DEFAULT_IFS: &str = " \t\n";
+const TIMEOUT_STATUS: i32 = 142;
+
+struct ReadOptions<'a> {
+    is_raw: bool,
+    is_silent: bool,
+    array_name: Option<&'a str>,
+    delimiter: u8,
+    max_chars: Option<usize>,
+    prompt: Option<&'a str>,
+    timeout: Option<Duration>,
+    names: &'a [String],
+}
+
+enum ReadResult {
+    Delimiter,
+    Eof,
+    Timeout,
+    Error(String),
+}
+
+pub fn command_read(args: &[String]) -> i32 {
+    let read_options = match parse_read_options(args) {
+        Ok(read_options) => read_options,
+        Err(status) => return status,
+    };
+    let ReadOptions { is_raw, is_silent, array_name, delimiter, max_chars, prompt, timeout, names } = read_options;
+
+    let is_terminal = unsafe { libc::isatty(libc::STDIN_FILENO) == 1 };
+    if let Some(prompt) = prompt && is_terminal {
+        eprint!("{}", prompt);
+        io::stderr().flush().ok();
+    }
+
+    // -t 0 은 읽지 않고 입력이 있는지만 확인
+    if timeout == Some(Duration::ZERO) {
+        return if wait_for_input(Some(Duration::ZERO)) { 0 } else { 1 };
+    }
+
+    let saved_termios = if is_terminal {
+        set_terminal_mode(is_silent, max_chars.is_some() || delimiter != b'\n')
+    } else {
+        None
+    };
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let (line, escaped, read_result) = read_line(delimiter, max_chars, is_raw, deadline);
+    if let Some(saved_termios) = saved_termios {
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &saved_termios) };
+        // 입력한 개행도 보이지 않았으므로 줄을 바꿔 줌
+        if is_silent {
+            eprintln!();
+        }
+    }
+
+    if let ReadResult::Error(e) = &read_result {
+        eprintln!("read: read error: {}", e);
+        return 1;
+    }
+
+    let ifs = get_variable("IFS").unwrap_or_else(|| DEFAULT_IFS.to_string());
+    if let Some(array_name) = array_name {
+        set_array_variable(array_name, split_fields(&line, &escaped, ifs.as_bytes(), None));
+    } else if names.is_empty() {
+        set_variable("REPLY", &String::from_utf8_lossy(&line));
+    } else {
+        let fields = split_fields(&line, &escaped, ifs.as_bytes(), Some(names.len()));
+        for (name_idx, name) in names.iter().enumerate() {
+            set_variable(name, fields.get(name_idx).map_or("", |field| field.as_str()));
+        }
+    }
+
+    match read_result {
+        ReadResult::Delimiter => 0,
+        ReadResult::Timeout => TIMEOUT_STATUS,
+        _ => 1,
+    }
+}
+
+// 옵션과 변수 이름, 잘못된 옵션이면 에러 메시지를 출력하고 종료 상태를 반환
+fn parse_read_options(args: &[String]) -> Result<ReadOptions<'_>, i32> {
+    let mut is_raw = false;
+    let mut is_silent = false;
+    let mut array_name: Option<&str> = None;
+    let mut delimiter = b'\n';
+    let mut max_chars: Option<usize> = None;
+    let mut prompt: Option<&str> = None;
+    let mut timeout: Option<Duration> = None;
+
+    let mut idx = 0;
+    while idx < args.len() && args[idx].starts_with('-') && args[idx].len() > 1 {
+        let arg = &args[idx];
+        idx += 1;
+        if arg == "--" {
+            break;
+        }
+
+        let flags: Vec<char> = arg[1..].chars().collect();
+        for (flag_idx, flag) in flags.iter().enumerate() {
+            match flag {
+                'r' => is_raw = true,
+                's' => is_silent = true,
+                'a' | 'd' | 'n' | 'p' | 't' => {
+                    // -p prompt 혹은 -pprompt
+                    let value = if flag_idx + 1 < flags.len() {
+                        &arg[1 + flags[..=flag_idx].iter().map(|char| char.len_utf8()).sum::<usize>()..]
+                    } else if idx < args.len() {
+                        idx += 1;
+                        &args[idx - 1]
+                    } else {
+                        eprintln!("read: -{}: option requires an argument", flag);
+                        eprintln!("read: usage: read [-rs] [-a array] [-d delim] [-n nchars] [-p prompt] [-t timeout] [name ...]");
+                        return Err(2);
+                    };
+
+                    match flag {
+                        'a' => array_name = Some(value),
+                        'd' => delimiter = value.bytes().next().unwrap_or(0),
+                        'n' => match value.parse::<usize>() {
+                            Ok(count) => max_chars = Some(count),
+                            Err(_) => {
+                                eprintln!("read: {}: invalid number", value);
+                                return Err(1);
+                            }
+                        },
+                        'p' => prompt = Some(value),
+                        _ => match value.parse::<f64>() {
+                            Ok(seconds) if seconds >= 0.0 && seconds.is_finite() => timeout = Some(Duration::from_secs_f64(seconds)),
+                            _ => {
+                                eprintln!("read: {}: invalid timeout specification", value);
+                                return Err(1);
+                            }
+                        },
+                    }
+                    break;
+                }
+                _ => {
+                    eprintln!("read: -{}: invalid option", flag);
+                    eprintln!("read: usage: read [-rs] [-a array] [-d delim] [-n nchars] [-p prompt] [-t timeout] [name ...]");
+                    return Err(2);
+                }
+            }
+        }
+    }
+
+    let names = &args[idx..];
+    if let Some(name) = array_name.into_iter().chain(names.iter().map(|name| name.as_str())).find(|name| false == is_valid_variable_name(name)) {
+        eprintln!("read: `{}': not a valid identifier", name);
+        return Err(1);
+    }
+
+    Ok(ReadOptions { is_raw, is_silent, array_name, delimiter, max_chars, prompt, timeout, names })
+}
+
+// 개행까지 한 줄 (개행과 줄 끝의 \r 은 제외), 더 읽을 입력이 없으면 None
+pub fn read_input_line() -> Option<String> {
+    let (line, _, read_result) = read_line(b'\n', None, true, None);
+    match read_result {
+        ReadResult::Delimiter => {}
+        // 개행 없이 끝난 마지막 줄
+        ReadResult::Eof if false == line.is_empty() => {}
+        _ => return None,
+    }
+
+    let mut line = String::from_utf8_lossy(&line).into_owned();
+    if line.ends_with('\r') {
+        line.pop();
+    }
+    Some(line)
+}
+
+// 읽은 byte 와 각 byte 가 백슬래쉬로 escape 되었는지 여부
+fn read_line(delimiter: u8, max_chars: Option<usize>, is_raw: bool, deadline: Option<Instant>) -> (Vec<u8>, Vec<bool>, ReadResult) {
+    let mut line: Vec<u8> = vec![];
+    let mut escaped: Vec<bool> = vec![];
+    let mut char_count = 0;
+    let mut is_escape_next = false;
+
+    loop {
+        if max_chars.is_some_and(|max_chars| char_count >= max_chars) {
+            return (line, escaped, ReadResult::Delimiter);
+        }
+
+        let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        if false == wait_for_input(remaining) {
+            return (line, escaped, ReadResult::Timeout);
+        }
+
+        let mut byte = 0u8;
+        let read_len = unsafe { libc::read(libc::STDIN_FILENO, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+        if read_len < 0 {
+            let e = io::Error::last_os_error();
+            if e.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return (line, escaped, ReadResult::Error(e.to_string()));
+        }
+        if read_len == 0 {
+            return (line, escaped, ReadResult::Eof);
+        }
+
+        // UTF-8 의 이어지는 byte 는 글자 수에 세지 않음
+        let is_char_start = byte & 0xC0 != 0x80;
+
+        if is_escape_next {
+            is_escape_next = false;
+            // 백슬래쉬-개행은 줄 이어짐
+            if byte == b'\n' {
+                continue;
+            }
+            line.push(byte);
+            escaped.push(true);
+            if is_char_start {
+                char_count += 1;
+            }
+            continue;
+        }
+
+        if byte == delimiter {
+            return (line, escaped, ReadResult::Delimiter);
+        }
+        if byte == b'\\' && false == is_raw {
+            is_escape_next = true;
+            continue;
+        }
+
+        line.push(byte);
+        escaped.push(false);
+        if is_char_start {
+            char_count += 1;
+        }
+    }
+}
+
+// timeout 안에 stdin 에 읽을 입력이 생기면 true, timeout 이 없으면 기다림
+fn wait_for_input(timeout: Option<Duration>) -> bool {
+    let Some(timeout) = timeout else {
+        return true;
+    };
+
+    let mut poll_fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+    let timeout_ms = timeout.as_millis().min(i32::MAX as u128) as i32;
+    unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) > 0 }
+}
+
+// -s 면 echo 를 끄고, -n, -d 면 줄 단위가 아니라 한 글자씩 읽도록, 바꾸기 전 설정을 반환
+fn set_terminal_mode(is_silent: bool, is_char_mode: bool) -> Option<libc::termios> {
+    if false == is_silent && false == is_char_mode {
+        return None;
+    }
+
+    let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut termios) } != 0 {
+        return None;
+    }
+    let saved_termios = termios;
+
+    if is_silent {
+        termios.c_lflag &= !(libc::ECHO | libc::ECHONL);
+    }
+    if is_char_mode {
+        termios.c_lflag &= !libc::ICANON;
+        termios.c_cc[libc::VMIN] = 1;
+        termios.c_cc[libc::VTIME] = 0;
+    }
+    unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios) };
+
+    Some(saved_termios)
+}
+
+// IFS 의 공백 글자는 앞뒤를 지우고 여러 개를 하나로, 그 외 글자는 하나마다 field 를 나눔
+// max_fields 가 있으면 마지막 field 에 나머지를 모두 담음 (끝의 IFS 공백만 지움)
+fn split_fields(line: &[u8], escaped: &[bool], ifs: &[u8], max_fields: Option<usize>) -> Vec<String> {
+    let is_ifs = |idx: usize| false == escaped[idx] && ifs.contains(&line[idx]);
+    let is_ifs_whitespace = |idx: usize| is_ifs(idx) && matches!(line[idx], b' ' | b'\t' | b'\n');
+
+    let mut fields = vec![];
+    let mut idx = 0;
+    while idx < line.len() && is_ifs_whitespace(idx) {
+        idx += 1;
+    }
+
+    while idx < line.len() {
+        if max_fields.is_some_and(|max_fields| fields.len() + 1 == max_fields) {
+            let mut end = line.len();
+            while end > idx && is_ifs_whitespace(end - 1) {
+                end -= 1;
+            }
+            fields.push(String::from_utf8_lossy(&line[idx..end]).into_owned());
+            return fields;
+        }
+
+        let start = idx;
+        while idx < line.len() && false == is_ifs(idx) {
+            idx += 1;
+        }
+        fields.push(String::from_utf8_lossy(&line[start..idx]).into_owned());
+
+        // field 사이의 구분자 : IFS 공백 + (공백이 아닌 IFS 글자 하나) + IFS 공백
+        while idx < line.len() && is_ifs_whitespace(idx) {
+            idx += 1;
+        }
+        if idx < line.len() && is_ifs(idx) && false == is_ifs_whitespace(idx) {
+            idx += 1;
+            while idx < line.len() && is_ifs_whitespace(idx) {
+                idx += 1;
+            }
+        }
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DEFAULT_IFS, parse_read_options, split_fields};
+    use crate::special_char_args_builder;
+
+    // escape 된 글자가 없는 line 을 나눔
+    fn split(line: &str, ifs: &str, max_fields: Option<usize>) -> Vec<String> {
+        split_fields(line.as_bytes(), &vec![false; line.len()], ifs.as_bytes(), max_fields)
+    }
+
+    #[test]
+    fn quoted_empty_delimiter_is_nul() {
+        let args = special_char_args_builder("-r -d '' file");
+
+        let read_options = parse_read_options(&args).unwrap();
+
+        assert_eq!(read_options.delimiter, 0);
+        assert!(read_options.is_raw);
+        assert_eq!(read_options.names, ["file".to_string()]);
+    }
+
+    #[test]
+    fn quoted_space_delimiter() {
+        let args = special_char_args_builder("-d ' ' word");
+
+        let read_options = parse_read_options(&args).unwrap();
+
+        assert_eq!(read_options.delimiter, b' ');
+        assert_eq!(read_options.names, ["word".to_string()]);
+    }
+
+    #[test]
+    fn quoted_empty_and_blank_words_are_arguments() {
+        assert_eq!(special_char_args_builder(r#"a '' "  " b"#), ["a", "", "  ", "b"]);
+        assert_eq!(special_char_args_builder("-d ''"), ["-d", ""]);
+        // 쿼터 안의 빈 문자열이 다른 글자와 붙으면 인자를 따로 만들지 않음
+        assert_eq!(special_char_args_builder("hello''world"), ["helloworld"]);
+    }
+
+    #[test]
+    fn whitespace_ifs_trims_and_merges() {
+        assert_eq!(split(" a  b ", DEFAULT_IFS, None), ["a", "b"]);
+        assert_eq!(split("\ta\t\tb\n", DEFAULT_IFS, None), ["a", "b"]);
+        assert!(split("   ", DEFAULT_IFS, None).is_empty());
+    }
+
+    #[test]
+    fn non_whitespace_ifs_keeps_empty_fields() {
+        assert_eq!(split("a::b", ":", None), ["a", "", "b"]);
+        assert_eq!(split(":a:", ":", None), ["", "a"]);
+        // 공백이 아닌 IFS 글자 주변의 IFS 공백은 구분자의 일부
+        assert_eq!(split("a : b", ": ", None), ["a", "b"]);
+        assert_eq!(split("a :: b", ": ", None), ["a", "", "b"]);
+    }
+
+    #[test]
+    fn escaped_ifs_does_not_split() {
+        // read 가 `a\ b c` 에서 백슬래쉬를 지우고 공백을 escape 로 표시한 상태
+        let line = b"a b c";
+        let escaped = [false, true, false, false, false];
+
+        assert_eq!(split_fields(line, &escaped, DEFAULT_IFS.as_bytes(), None), ["a b", "c"]);
+    }
+
+    #[test]
+    fn last_field_gets_remaining_text() {
+        assert_eq!(split(" a  b  c ", DEFAULT_IFS, Some(2)), ["a", "b  c"]);
+        assert_eq!(split("a:b:c", ":", Some(2)), ["a", "b:c"]);
+        assert_eq!(split("a b", DEFAULT_IFS, Some(1)), ["a b"]);
+    }
+
+    #[test]
+    fn empty_ifs_does_not_split() {
+        assert_eq!(split(" a b ", "", None), [" a b "]);
+    }
+}
@@ -0,0 +1,71 @@
+// set builtin
+//   set               : shell 변수 출력
+//   set -o / set +o   : option 목록 출력
+//   set -o name       : option 켜기, set +o name : option 끄기
+//   set -H / set +H   : 한 글자 flag 로 option 켜고 끄기 (-H histexpand, -x xtrace)
+//   set -o vi / set -o emacs : line 편집 방식 변경
+
+use crate::shell_state::options::{SHELL_OPTIONS, find_shell_option, find_shell_option_by_flag, set_shell_option};
+use crate::shell_state::variables::{ShellVariable, get_all_variables};
+
+pub fn command_set(args: &[String]) -> i32 {
+    if args.is_empty() {
+        for (name, variable) in get_all_variables() {
+            match variable {
+                ShellVariable::Scalar(value) => println!("{}={}", name, value),
+                ShellVariable::Array(values) => {
+                    let values: Vec<String> = values.iter().enumerate().map(|(idx, value)| format!("[{}]=\"{}\"", idx, value)).collect();
+                    println!("{}=({})", name, values.join(" "));
+                }
+            }
+        }
+        return 0;
+    }
+
+    let mut idx = 0;
+    while idx < args.len() {
+        let arg = &args[idx];
+        let is_enabled = arg.starts_with('-');
+
+        if false == (arg.starts_with('-') || arg.starts_with('+')) || arg.len() < 2 {
+            eprintln!("set: {}: invalid option", arg);
+            return 2;
+        }
+
+        // -o name / +o name
+        if &arg[1..] == "o" {
+            let Some(option_name) = args.get(idx + 1) else {
+                print_shell_options(is_enabled);
+                return 0;
+            };
+            let Some(option) = find_shell_option(option_name) else {
+                eprintln!("set: {}: invalid option name", option_name);
+                return 2;
+            };
+            set_shell_option(option, is_enabled);
+            idx += 2;
+            continue;
+        }
+
+        for flag in arg[1..].chars() {
+            let Some(option) = find_shell_option_by_flag(flag) else {
+                eprintln!("set: {}{}: invalid option", &arg[..1], flag);
+                return 2;
+            };
+            set_shell_option(option, is_enabled);
+        }
+        idx += 1;
+    }
+
+    0
+}
+
+fn print_shell_options(is_human_readable: bool) {
+    for option in &SHELL_OPTIONS {
+        if is_human_readable {
+            println!("{:<15} {}", option.name, if option.is_enabled() { "on" } else { "off" });
+        } else {
+            println!("set {}o {}", if option.is_enabled() { "-" } else { "+" }, option.name);
+        }
+    }
+}
@@ -0,0 +1,717 @@
+// test, [ builtin 과 [[ ]] 조건식
+// 결과는 참이면 0, 거짓이면 1, 문법 에러면 2 를 반환한다
+
+use std::{ffi::CString, fs, os::unix::fs::{FileTypeExt, MetadataExt}, path::Path};
+
+use regex::Regex;
+
+use crate::shell_parser::expansion::{evaluate_arithmetic_expression, expand_command_line};
+use crate::shell_parser::pattern::glob_match;
+use crate::shell_state::variables::set_array_variable;
+
+const UNARY_OPERATORS: [&str; 19] = [
+    "-e", "-f", "-d", "-x", "-s", "-r", "-w", "-L", "-h", "-b", "-c", "-p", "-S", "-g", "-u", "-k", "-z", "-n", "-v",
+];
+
+const BINARY_OPERATORS: [&str; 14] = [
+    "=", "==", "!=", "<", ">", "-eq", "-ne", "-lt", "-le", "-gt", "-ge", "-nt", "-ot", "-ef",
+];
+
+pub fn command_test(args: &[String]) -> i32 {
+    evaluate_test("test", args)
+}
+
+pub fn command_bracket(args: &[String]) -> i32 {
+    let Some((last, args)) = args.split_last() else {
+        println!("[: missing `]'");
+        return 2;
+    };
+
+    if last != "]" {
+        println!("[: missing `]'");
+        return 2;
+    }
+
+    evaluate_test("[", args)
+}
+
+fn evaluate_test(command: &str, args: &[String]) -> i32 {
+    let mut test_parser = TestParser { args, pos: 0 };
+
+    // 인자가 없으면 거짓
+    if args.is_empty() {
+        return 1;
+    }
+
+    let result = test_parser.parse_or().and_then(|result| {
+        if let Some(arg) = args.get(test_parser.pos) {
+            return Err(format!("{}: unexpected operator", arg));
+        }
+        Ok(result)
+    });
+
+    match result {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            println!("{}: {}", command, e);
+            2
+        }
+    }
+}
+
+// test / [ 의 인자 파싱
+// ! > -a > -o 순서로 결합, ( ) 로 묶을 수 있음
+struct TestParser<'a> {
+    args: &'a [String],
+    pos: usize,
+}
+
+impl<'a> TestParser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.args.get(self.pos).map(|arg| arg.as_str())
+    }
+
+    fn remaining(&self) -> usize {
+        self.args.len() - self.pos
+    }
+
+    fn parse_or(&mut self) -> Result<bool, String> {
+        let mut result = self.parse_and()?;
+        while self.peek() == Some("-o") && self.remaining() > 1 {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            result = result || rhs;
+        }
+        Ok(result)
+    }
+
+    fn parse_and(&mut self) -> Result<bool, String> {
+        let mut result = self.parse_not()?;
+        while self.peek() == Some("-a") && self.remaining() > 1 {
+            self.pos += 1;
+            let rhs = self.parse_not()?;
+            result = result && rhs;
+        }
+        Ok(result)
+    }
+
+    fn parse_not(&mut self) -> Result<bool, String> {
+        // 마지막 인자인 ! 는 그냥 문자열
+        if self.peek() == Some("!") && self.remaining() > 1 {
+            self.pos += 1;
+            return Ok(false == self.parse_not()?);
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<bool, String> {
+        let Some(arg) = self.peek() else {
+            return Err("argument expected".to_string());
+        };
+
+        // arg binary_op arg 가 우선
+        if self.remaining() >= 3 && BINARY_OPERATORS.contains(&self.args[self.pos + 1].as_str()) {
+            let lhs = &self.args[self.pos];
+            let operator = &self.args[self.pos + 1];
+            let rhs = &self.args[self.pos + 2];
+            self.pos += 3;
+            return evaluate_binary_operator(operator, lhs, rhs, parse_test_integer);
+        }
+
+        if arg == "(" && self.remaining() > 1 {
+            self.pos += 1;
+            let result = self.parse_or()?;
+            if self.peek() != Some(")") {
+                return Err("`)' expected".to_string());
+            }
+            self.pos += 1;
+            return Ok(result);
+        }
+
+        if UNARY_OPERATORS.contains(&arg) && self.remaining() >= 2 {
+            let operator = arg.to_string();
+            let operand = self.args[self.pos + 1].to_owned();
+            self.pos += 2;
+            return evaluate_unary_operator(&operator, &operand);
+        }
+
+        // 인자 하나는 비어있지 않으면 참
+        self.pos += 1;
+        Ok(false == arg.is_empty())
+    }
+}
+
+fn parse_test_integer(value: &str) -> Result<i64, String> {
+    value.trim().parse::<i64>().map_err(|_| format!("{}: integer expression expected", value))
+}
+
+fn parse_conditional_integer(value: &str) -> Result<i64, String> {
+    evaluate_arithmetic_expression(value)
+}
+
+fn is_accessible(path: &str, mode: i32) -> bool {
+    let Ok(c_path) = CString::new(path) else {
+        return false;
+    };
+    unsafe { libc::access(c_path.as_ptr(), mode) == 0 }
+}
+
+fn evaluate_unary_operator(operator: &str, operand: &str) -> Result<bool, String> {
+    let path = Path::new(operand);
+
+    let result = match operator {
+        "-z" => operand.is_empty(),
+        "-n" => false == operand.is_empty(),
+        "-v" => crate::shell_state::variables::get_variable(operand).is_some(),
+        "-e" => path.exists(),
+        "-f" => path.is_file(),
+        "-d" => path.is_dir(),
+        "-s" => fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0),
+        "-r" => is_accessible(operand, libc::R_OK),
+        "-w" => is_accessible(operand, libc::W_OK),
+        "-x" => is_accessible(operand, libc::X_OK),
+        "-L" | "-h" => fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()),
+        "-b" => fs::metadata(path).is_ok_and(|metadata| metadata.file_type().is_block_device()),
+        "-c" => fs::metadata(path).is_ok_and(|metadata| metadata.file_type().is_char_device()),
+        "-p" => fs::metadata(path).is_ok_and(|metadata| metadata.file_type().is_fifo()),
+        "-S" => fs::metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()),
+        "-g" => fs::metadata(path).is_ok_and(|metadata| metadata.mode() & libc::S_ISGID != 0),
+        "-u" => fs::metadata(path).is_ok_and(|metadata| metadata.mode() & libc::S_ISUID != 0),
+        "-k" => fs::metadata(path).is_ok_and(|metadata| metadata.mode() & libc::S_ISVTX != 0),
+        _ => return Err(format!("{}: unary operator expected", operator)),
+    };
+
+    Ok(result)
+}
+
+fn evaluate_binary_operator(operator: &str, lhs: &str, rhs: &str, parse_integer: fn(&str) -> Result<i64, String>) -> Result<bool, String> {
+    let result = match operator {
+        "=" | "==" => lhs == rhs,
+        "!=" => lhs != rhs,
+        "<" => lhs < rhs,
+        ">" => lhs > rhs,
+        "-eq" => parse_integer(lhs)? == parse_integer(rhs)?,
+        "-ne" => parse_integer(lhs)? != parse_integer(rhs)?,
+        "-lt" => parse_integer(lhs)? < parse_integer(rhs)?,
+        "-le" => parse_integer(lhs)? <= parse_integer(rhs)?,
+        "-gt" => parse_integer(lhs)? > parse_integer(rhs)?,
+        "-ge" => parse_integer(lhs)? >= parse_integer(rhs)?,
+        "-nt" | "-ot" => {
+            let lhs_modified = fs::metadata(lhs).and_then(|metadata| metadata.modified()).ok();
+            let rhs_modified = fs::metadata(rhs).and_then(|metadata| metadata.modified()).ok();
+            match (lhs_modified, rhs_modified) {
+                (Some(lhs_modified), Some(rhs_modified)) if operator == "-nt" => lhs_modified > rhs_modified,
+                (Some(lhs_modified), Some(rhs_modified)) => lhs_modified < rhs_modified,
+                // 한쪽만 있으면 있는 쪽이 더 최신
+                (Some(_), None) => operator == "-nt",
+                (None, Some(_)) => operator == "-ot",
+                (None, None) => false,
+            }
+        }
+        "-ef" => match (fs::metadata(lhs), fs::metadata(rhs)) {
+            (Ok(lhs_metadata), Ok(rhs_metadata)) => lhs_metadata.dev() == rhs_metadata.dev() && lhs_metadata.ino() == rhs_metadata.ino(),
+            _ => false,
+        },
+        _ => return Err(format!("{}: binary operator expected", operator)),
+    };
+
+    Ok(result)
+}
+
+// [[ ]]
+// 단어 분리와 pathname 확장을 하지 않고, == / != 우변은 glob pattern, =~ 우변은 정규식으로 매칭한다
+// 쿼터로 묶인 부분은 pattern / 정규식에서도 문자 그대로 매칭한다
+
+#[derive(Debug, Clone, PartialEq)]
+enum ConditionalToken {
+    // 원본 문자열 (확장 전)
+    Word(String),
+    And,
+    Or,
+    Not,
+    LeftParen,
+    RightParen,
+}
+
+pub fn execute_conditional_expression(expression: &str) -> i32 {
+    let result = tokenize_conditional(expression).and_then(|tokens| {
+        let mut conditional_parser = ConditionalParser { tokens, pos: 0 };
+        if conditional_parser.tokens.is_empty() {
+            return Err("syntax error in conditional expression".to_string());
+        }
+
+        let result = conditional_parser.parse_or()?;
+        if let Some(token) = conditional_parser.tokens.get(conditional_parser.pos) {
+            return Err(format!("syntax error in conditional expression: unexpected token `{}'", conditional_token_to_string(token)));
+        }
+        Ok(result)
+    });
+
+    match result {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            println!("{}", e);
+            2
+        }
+    }
+}
+
+fn conditional_token_to_string(token: &ConditionalToken) -> String {
+    match token {
+        ConditionalToken::Word(word) => word.to_owned(),
+        ConditionalToken::And => "&&".to_string(),
+        ConditionalToken::Or => "||".to_string(),
+        ConditionalToken::Not => "!".to_string(),
+        ConditionalToken::LeftParen => "(".to_string(),
+        ConditionalToken::RightParen => ")".to_string(),
+    }
+}
+
+fn tokenize_conditional(expression: &str) -> Result<Vec<ConditionalToken>, String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = vec![];
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let char = chars[idx];
+
+        if char.is_whitespace() {
+            idx += 1;
+            continue;
+        }
+
+        if char == '&' && chars.get(idx + 1) == Some(&'&') {
+            tokens.push(ConditionalToken::And);
+            idx += 2;
+            continue;
+        }
+        if char == '|' && chars.get(idx + 1) == Some(&'|') {
+            tokens.push(ConditionalToken::Or);
+            idx += 2;
+            continue;
+        }
+
+        // =~ 의 우변 정규식은 ( ) | 를 포함할 수 있어서 괄호 깊이가 0 인 공백까지를 한 단어로 본다
+        // (ex. ([a-z]+)([0-9]+) 처럼 괄호로 시작해도 정규식)
+        let is_regex_word = matches!(tokens.last(), Some(ConditionalToken::Word(word)) if word == "=~");
+
+        if char == '(' && false == is_regex_word {
+            tokens.push(ConditionalToken::LeftParen);
+            idx += 1;
+            continue;
+        }
+        if char == ')' {
+            tokens.push(ConditionalToken::RightParen);
+            idx += 1;
+            continue;
+        }
+
+        let start = idx;
+        let mut is_single_quote = false;
+        let mut is_double_quote = false;
+        let mut paren_depth = 0;
+        while idx < chars.len() {
+            let char = chars[idx];
+
+            if is_single_quote {
+                if char == '\'' {
+                    is_single_quote = false;
+                }
+                idx += 1;
+                continue;
+            }
+
+            if char == '\\' {
+                idx += 2;
+                continue;
+            }
+            if char == '\'' && false == is_double_quote {
+                is_single_quote = true;
+                idx += 1;
+                continue;
+            }
+            if char == '"' {
+                is_double_quote = !is_double_quote;
+                idx += 1;
+                continue;
+            }
+
+            if false == is_double_quote {
+                if is_regex_word {
+                    if char == '(' {
+                        paren_depth += 1;
+                    } else if char == ')' && paren_depth > 0 {
+                        paren_depth -= 1;
+                    } else if paren_depth == 0 && (char.is_whitespace() || char == ')'
+                        || (char == '&' && chars.get(idx + 1) == Some(&'&'))
+                        || (char == '|' && chars.get(idx + 1) == Some(&'|'))) {
+                        break;
+                    }
+                } else if char.is_whitespace() || char == '(' || char == ')'
+                    || (char == '&' && chars.get(idx + 1) == Some(&'&'))
+                    || (char == '|' && chars.get(idx + 1) == Some(&'|')) {
+                    break;
+                }
+            }
+
+            idx += 1;
+        }
+
+        if is_single_quote || is_double_quote {
+            return Err("unexpected EOF while looking for matching quote".to_string());
+        }
+
+        let word: String = chars[start..idx.min(chars.len())].iter().collect();
+        if word == "!" {
+            tokens.push(ConditionalToken::Not);
+        } else {
+            tokens.push(ConditionalToken::Word(word));
+        }
+    }
+
+    Ok(tokens)
+}
+
+// 확장 후 쿼터를 제거한 문자열과, 각 문자가 쿼터(혹은 escape) 로 묶여서 문자 그대로인지 여부
+fn expand_conditional_word(word: &str) -> Result<Vec<(char, bool)>, String> {
+    let expanded = expand_command_line(word)?;
+    let chars: Vec<char> = expanded.chars().collect();
+    let mut result = vec![];
+    let mut is_single_quote = false;
+    let mut is_double_quote = false;
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let char = chars[idx];
+
+        if is_single_quote {
+            if char == '\'' {
+                is_single_quote = false;
+            } else {
+                result.push((char, true));
+            }
+            idx += 1;
+            continue;
+        }
+
+        match char {
+            '\\' if idx + 1 < chars.len() => {
+                let next_char = chars[idx + 1];
+                // double quotes 안에서는 \ " $ ` 만 escape
+                if is_double_quote && false == matches!(next_char, '\\' | '"' | '$' | '`' | '\'') {
+                    result.push((char, true));
+                    idx += 1;
+                    continue;
+                }
+                result.push((next_char, true));
+                idx += 2;
+                continue;
+            }
+            '\'' if false == is_double_quote => is_single_quote = true,
+            '"' => is_double_quote = !is_double_quote,
+            _ => result.push((char, is_double_quote)),
+        }
+        idx += 1;
+    }
+
+    Ok(result)
+}
+
+fn conditional_word_to_string(word: &str) -> Result<String, String> {
+    Ok(expand_conditional_word(word)?.into_iter().map(|(char, _)| char).collect())
+}
+
+fn conditional_word_to_glob(word: &str) -> Result<String, String> {
+    let mut pattern = String::new();
+    for (char, is_literal) in expand_conditional_word(word)? {
+        if is_literal && matches!(char, '*' | '?' | '[' | ']' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(char);
+    }
+    Ok(pattern)
+}
+
+fn conditional_word_to_regex(word: &str) -> Result<String, String> {
+    let mut pattern = String::new();
+    for (char, is_literal) in expand_conditional_word(word)? {
+        if is_literal {
+            pattern.push_str(&regex::escape(&char.to_string()));
+        } else {
+            pattern.push(char);
+        }
+    }
+    Ok(pattern)
+}
+
+struct ConditionalParser {
+    tokens: Vec<ConditionalToken>,
+    pos: usize,
+}
+
+impl ConditionalParser {
+    fn peek(&self) -> Option<&ConditionalToken> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_word(&self, offset: usize) -> Option<&str> {
+        match self.tokens.get(self.pos + offset) {
+            Some(ConditionalToken::Word(word)) => Some(word.as_str()),
+            _ => None,
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<bool, String> {
+        let mut result = self.parse_and()?;
+        while self.peek() == Some(&ConditionalToken::Or) {
+            self.pos += 1;
+            // short circuit 이지만 =~ 의 BASH_REMATCH 처럼 side effect 가 있어서 평가는 건너뜀
+            if result {
+                self.skip_and()?;
+            } else {
+                result = self.parse_and()?;
+            }
+        }
+        Ok(result)
+    }
+
+    fn parse_and(&mut self) -> Result<bool, String> {
+        let mut result = self.parse_not()?;
+        while self.peek() == Some(&ConditionalToken::And) {
+            self.pos += 1;
+            if result {
+                result = self.parse_not()?;
+            } else {
+                self.skip_not()?;
+            }
+        }
+        Ok(result)
+    }
+
+    fn parse_not(&mut self) -> Result<bool, String> {
+        if self.peek() == Some(&ConditionalToken::Not) {
+            self.pos += 1;
+            return Ok(false == self.parse_not()?);
+        }
+        self.parse_primary()
+    }
+
+    // 평가하지 않고 토큰만 건너뛰기
+    fn skip_and(&mut self) -> Result<(), String> {
+        self.skip_not()?;
+        while self.peek() == Some(&ConditionalToken::And) {
+            self.pos += 1;
+            self.skip_not()?;
+        }
+        Ok(())
+    }
+
+    fn skip_not(&mut self) -> Result<(), String> {
+        while self.peek() == Some(&ConditionalToken::Not) {
+            self.pos += 1;
+        }
+
+        if self.peek() == Some(&ConditionalToken::LeftParen) {
+            let mut depth = 0;
+            while let Some(token) = self.peek() {
+                match token {
+                    ConditionalToken::LeftParen => depth += 1,
+                    ConditionalToken::RightParen => depth -= 1,
+                    _ => {}
+                }
+                self.pos += 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+            return Err("syntax error in conditional expression: expected `)'".to_string());
+        }
+
+        if self.peek_word(1).is_some_and(|operator| BINARY_OPERATORS.contains(&operator) || operator == "=~") && self.peek_word(2).is_some() {
+            self.pos += 3;
+        } else if self.peek_word(0).is_some_and(|operator| UNARY_OPERATORS.contains(&operator)) && self.peek_word(1).is_some() {
+            self.pos += 2;
+        } else if self.peek_word(0).is_some() {
+            self.pos += 1;
+        } else {
+            return Err("syntax error in conditional expression".to_string());
+        }
+        Ok(())
+    }
+
+    fn parse_primary(&mut self) -> Result<bool, String> {
+        if self.peek() == Some(&ConditionalToken::LeftParen) {
+            self.pos += 1;
+            let result = self.parse_or()?;
+            if self.peek() != Some(&ConditionalToken::RightParen) {
+                return Err("syntax error in conditional expression: expected `)'".to_string());
+            }
+            self.pos += 1;
+            return Ok(result);
+        }
+
+        let Some(word) = self.peek_word(0).map(|word| word.to_string()) else {
+            return Err("syntax error in conditional expression".to_string());
+        };
+
+        // word binary_op word
+        if let (Some(operator), Some(rhs)) = (self.peek_word(1).map(|w| w.to_string()), self.peek_word(2).map(|w| w.to_string()))
+            && (BINARY_OPERATORS.contains(&operator.as_str()) || operator == "=~") {
+            self.pos += 3;
+            let lhs = conditional_word_to_string(&word)?;
+
+            return match operator.as_str() {
+                "==" | "=" => Ok(glob_match(&conditional_word_to_glob(&rhs)?, &lhs)),
+                "!=" => Ok(false == glob_match(&conditional_word_to_glob(&rhs)?, &lhs)),
+                "=~" => regex_match(&lhs, &conditional_word_to_regex(&rhs)?),
+                _ => evaluate_binary_operator(&operator, &lhs, &conditional_word_to_string(&rhs)?, parse_conditional_integer),
+            };
+        }
+
+        // unary_op word
+        if UNARY_OPERATORS.contains(&word.as_str()) && let Some(operand) = self.peek_word(1).map(|w| w.to_string()) {
+            self.pos += 2;
+            return evaluate_unary_operator(&word, &conditional_word_to_string(&operand)?);
+        }
+
+        // 단어 하나는 비어있지 않으면 참
+        self.pos += 1;
+        Ok(false == conditional_word_to_string(&word)?.is_empty())
+    }
+}
+
+// =~ 매칭 결과는 BASH_REMATCH 배열에 [전체, 그룹1, 그룹2, ...] 로 저장
+fn regex_match(text: &str, pattern: &str) -> Result<bool, String> {
+    let regex = match Regex::new(pattern) {
+        Ok(regex) => regex,
+        Err(_) => return Err(format!("invalid regular expression `{}'", pattern)),
+    };
+
+    let Some(captures) = regex.captures(text) else {
+        set_array_variable("BASH_REMATCH", vec![]);
+        return Ok(false);
+    };
+
+    let rematch = captures.iter()
+        .map(|capture| capture.map(|m| m.as_str().to_string()).unwrap_or_default())
+        .collect();
+    set_array_variable("BASH_REMATCH", rematch);
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::{command_bracket, command_test, execute_conditional_expression};
+    use crate::shell_state::variables::{get_array_variable, set_variable};
+    use crate::special_char_args_builder;
+
+    fn test(args: &str) -> i32 {
+        command_test(&special_char_args_builder(args))
+    }
+
+    #[test]
+    fn string_unary_operators() {
+        assert_eq!(test("-z ''"), 0);
+        assert_eq!(test("-z abc"), 1);
+        assert_eq!(test("-n abc"), 0);
+        assert_eq!(test("-n ''"), 1);
+        // 인자 하나는 비어있지 않으면 참, 인자가 없으면 거짓
+        assert_eq!(test("abc"), 0);
+        assert_eq!(test("''"), 1);
+        assert_eq!(test(""), 1);
+
+        set_variable("test_command_set_variable", "");
+        assert_eq!(test("-v test_command_set_variable"), 0);
+        assert_eq!(test("-v test_command_unset_variable"), 1);
+    }
+
+    #[test]
+    fn file_unary_operators() {
+        let test_dir = env::temp_dir().join(format!("test_command_{}", std::process::id()));
+        fs::create_dir_all(&test_dir).unwrap();
+        let empty_file = test_dir.join("empty");
+        let data_file = test_dir.join("data");
+        fs::write(&empty_file, "").unwrap();
+        fs::write(&data_file, "data").unwrap();
+
+        assert_eq!(test(&format!("-e {}", empty_file.display())), 0);
+        assert_eq!(test(&format!("-f {}", empty_file.display())), 0);
+        assert_eq!(test(&format!("-d {}", empty_file.display())), 1);
+        assert_eq!(test(&format!("-d {}", test_dir.display())), 0);
+        assert_eq!(test(&format!("-s {}", empty_file.display())), 1);
+        assert_eq!(test(&format!("-s {}", data_file.display())), 0);
+        assert_eq!(test(&format!("-e {}/missing", test_dir.display())), 1);
+
+        fs::remove_dir_all(&test_dir).ok();
+    }
+
+    #[test]
+    fn binary_operators() {
+        assert_eq!(test("abc = abc"), 0);
+        assert_eq!(test("abc == abd"), 1);
+        assert_eq!(test("abc != abd"), 0);
+        assert_eq!(test("abc '<' abd"), 0);
+        assert_eq!(test("abc '>' abd"), 1);
+        assert_eq!(test("10 -eq 10"), 0);
+        assert_eq!(test("10 -ne 10"), 1);
+        assert_eq!(test("-5 -lt 3"), 0);
+        assert_eq!(test("3 -le 3"), 0);
+        assert_eq!(test("3 -gt 4"), 1);
+        assert_eq!(test("4 -ge 5"), 1);
+        // 정수가 아니면 문법 에러
+        assert_eq!(test("abc -eq 1"), 2);
+    }
+
+    #[test]
+    fn not_and_or_precedence() {
+        assert_eq!(test("! abc = abc"), 1);
+        // -a 가 -o 보다 먼저 결합 : a -o (b -a c)
+        assert_eq!(test("abc -o '' -a ''"), 0);
+        assert_eq!(test("'' -a abc -o abc"), 0);
+        assert_eq!(test("! '' -a abc"), 0);
+        assert_eq!(test("( abc -o '' ) -a ''"), 1);
+        // 마지막 인자인 ! 는 문자열
+        assert_eq!(test("!"), 0);
+        assert_eq!(test("( abc"), 2);
+    }
+
+    #[test]
+    fn bracket_requires_closing_bracket() {
+        assert_eq!(command_bracket(&special_char_args_builder("abc = abc ]")), 0);
+        assert_eq!(command_bracket(&special_char_args_builder("abc = abc")), 2);
+    }
+
+    #[test]
+    fn conditional_expression_operators() {
+        assert_eq!(execute_conditional_expression("abc == a*"), 0);
+        assert_eq!(execute_conditional_expression("abc == 'a*'"), 1);
+        assert_eq!(execute_conditional_expression("abc != b*"), 0);
+        assert_eq!(execute_conditional_expression("2 -lt 10 && ! abc < abb"), 0);
+        assert_eq!(execute_conditional_expression("( -z '' || -n '' ) && 1 -eq 1"), 0);
+        assert_eq!(execute_conditional_expression("1 + 1 -eq 2"), 2);
+    }
+
+    // BASH_REMATCH 는 공유 변수라서 =~ 는 한 test 안에서만 확인
+    #[test]
+    fn regex_match_sets_rematch_groups() {
+        assert_eq!(execute_conditional_expression("abc123 =~ ^[a-z]+[0-9]+$"), 0);
+        assert_eq!(execute_conditional_expression("abc =~ ^[0-9]+$"), 1);
+        // 쿼터 안의 정규식 문자는 글자 그대로
+        assert_eq!(execute_conditional_expression("a.c =~ 'a.c'"), 0);
+        assert_eq!(execute_conditional_expression("abc =~ 'a.c'"), 1);
+
+        // 괄호로 시작하는 정규식도 한 단어
+        assert_eq!(execute_conditional_expression("abc123 =~ ([a-z]+)([0-9]+)"), 0);
+        assert_eq!(get_array_variable("BASH_REMATCH"), Some(vec!["abc123".to_string(), "abc".to_string(), "123".to_string()]));
+        assert_eq!(execute_conditional_expression("x =~ (a|x) && 1 -eq 1"), 0);
+
+        // 매칭되지 않으면 비움
+        assert_eq!(execute_conditional_expression("abc =~ ([0-9]+)"), 1);
+        assert_eq!(get_array_variable("BASH_REMATCH"), Some(vec![]));
+    }
+}
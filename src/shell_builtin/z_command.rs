@@ -0,0 +1,107 @@
+// z, j builtin : 자주, 최근에 방문한 디렉토리 중 keyword 에 맞는 곳으로 이동 (directory_frecency 참고)
+//   z keyword...       : 경로에 keyword 가 순서대로 들어있는 디렉토리 중 점수가 가장 높은 곳으로 이동
+//   z                  : 기록된 디렉토리를 점수와 함께 출력 (z -l 과 같음)
+//   z -l [keyword...]  : 맞는 디렉토리를 점수가 낮은 순으로 출력 (마지막이 이동할 곳), -t 와 같이 쓰면 점수 대신 마지막 방문 시각
+//   z -e keyword...    : 이동하지 않고 이동할 경로만 출력
+//   z -r, z -t         : 점수 대신 방문 횟수, 마지막 방문 시각으로 고름
+//   z -x               : 현재 디렉토리의 기록 삭제
+// keyword 가 하나이고 / . .. 로 시작하는 디렉토리면 기록을 찾지 않고 그대로 이동
+
+use std::path::Path;
+
+use crate::rustyline_editor::prompt::format_timestamp;
+use crate::shell_builtin::cd_command::{change_directory, get_logical_current_dir};
+use crate::shell_state::directory_frecency::{FrecencySort, find_matching_directories, remove_directory_record};
+
+pub fn command_z(command: &str, args: &[String]) -> i32 {
+    let mut is_list = false;
+    let mut is_echo = false;
+    let mut frecency_sort = FrecencySort::Frecency;
+
+    let mut idx = 0;
+    while idx < args.len() && args[idx].starts_with('-') && args[idx].len() > 1 {
+        let arg = &args[idx];
+        idx += 1;
+        if arg == "--" {
+            break;
+        }
+
+        for flag in arg[1..].chars() {
+            match flag {
+                'l' => is_list = true,
+                'e' => is_echo = true,
+                'r' => frecency_sort = FrecencySort::Rank,
+                't' => frecency_sort = FrecencySort::Recent,
+                'x' => {
+                    let current_dir = get_logical_current_dir().to_string_lossy().into_owned();
+                    return match remove_directory_record(&current_dir) {
+                        Ok(_) => 0,
+                        Err(e) => {
+                            eprintln!("{}: {}", command, e);
+                            1
+                        }
+                    };
+                }
+                _ => {
+                    eprintln!("{}: -{}: invalid option", command, flag);
+                    eprintln!("{}: usage: {} [-elrtx] [keyword ...]", command, command);
+                    return 2;
+                }
+            }
+        }
+    }
+
+    let keywords = &args[idx..];
+    if keywords.is_empty() {
+        is_list = true;
+    }
+
+    if let [keyword] = keywords
+        && is_directory_path(keyword)
+        && false == is_list {
+        if is_echo {
+            println!("{}", keyword);
+            return 0;
+        }
+        return jump_to_directory(command, keyword);
+    }
+
+    let matched_directories = find_matching_directories(keywords, frecency_sort);
+    if is_list {
+        // 점수가 높은 것이 마지막 (바로 위에 보이도록)
+        for (record, score) in matched_directories.iter().rev() {
+            let score = match frecency_sort {
+                FrecencySort::Recent => format_timestamp(record.last_access, "%m-%d %H:%M"),
+                _ => format!("{:.1}", score),
+            };
+            println!("{:<10} {}", score, record.path);
+        }
+        return if matched_directories.is_empty() { 1 } else { 0 };
+    }
+
+    let Some((best_record, _)) = matched_directories.first() else {
+        eprintln!("{}: no match for {}", command, keywords.join(" "));
+        return 1;
+    };
+
+    if is_echo {
+        println!("{}", best_record.path);
+        return 0;
+    }
+    jump_to_directory(command, &best_record.path)
+}
+
+fn jump_to_directory(command: &str, dir: &str) -> i32 {
+    match change_directory(dir, false) {
+        Ok(_) => 0,
+        Err(e) => {
+            eprintln!("{}: {}", command, e);
+            1
+        }
+    }
+}
+
+fn is_directory_path(keyword: &str) -> bool {
+    (keyword.starts_with('/') || keyword == "." || keyword == ".." || keyword.starts_with("./") || keyword.starts_with("../"))
+        && Path::new(keyword).is_dir()
+}
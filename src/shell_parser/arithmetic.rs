@@ -0,0 +1,591 @@
+// $(( )) 와 (( )) 에서 쓰는 정수 산술 연산
+// 우선순위(높은 순)는 bash 와 동일하게 맞춘다
+//   id++ id--  /  ++id --id  /  - + (단항)  /  ! ~  /  **  /  * / %  /  + -  /  << >>
+//   <= >= < >  /  == !=  /  &  /  ^  /  |  /  &&  /  ||  /  ? :  /  = *= /= ...  /  ,
+
+use crate::shell_state::variables::{get_variable, is_valid_variable_name, set_variable};
+
+// 변수 값이 다시 수식인 경우 재귀 평가 깊이 제한
+const MAX_RECURSION_DEPTH: usize = 64;
+
+const ASSIGNMENT_OPERATORS: [&str; 11] = ["=", "*=", "/=", "%=", "+=", "-=", "<<=", ">>=", "&=", "^=", "|="];
+
+// 긴 operator 부터 매칭해야 "<<=" 가 "<<" + "=" 로 잘리지 않음
+const OPERATORS: [&str; 36] = [
+    "<<=", ">>=",
+    "**", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||",
+    "*=", "/=", "%=", "+=", "-=", "&=", "^=", "|=",
+    "+", "-", "*", "/", "%", "<", ">", "=", "!", "~", "&", "^", "|", "?", ":",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i64),
+    Identifier(String),
+    Operator(&'static str),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+pub fn evaluate_arithmetic(expression: &str) -> Result<i64, String> {
+    evaluate_with_depth(expression, 0)
+}
+
+fn evaluate_with_depth(expression: &str, depth: usize) -> Result<i64, String> {
+    if depth > MAX_RECURSION_DEPTH {
+        return Err(format!("{}: expression recursion level exceeded", expression.trim()));
+    }
+
+    let tokens = tokenize(expression)?;
+    // 빈 수식은 0
+    if tokens.is_empty() {
+        return Ok(0);
+    }
+
+    let mut evaluator = ArithmeticEvaluator {
+        expression,
+        tokens,
+        pos: 0,
+        depth,
+    };
+
+    let value = evaluator.parse_comma(true)?;
+    if let Some(token) = evaluator.tokens.get(evaluator.pos) {
+        return Err(format!("{}: syntax error in expression (error token is \"{}\")", expression.trim(), token_to_string(token)));
+    }
+
+    Ok(value)
+}
+
+fn token_to_string(token: &Token) -> String {
+    match token {
+        Token::Number(number) => number.to_string(),
+        Token::Identifier(name) => name.to_owned(),
+        Token::Operator(operator) => operator.to_string(),
+        Token::LeftParen => "(".to_string(),
+        Token::RightParen => ")".to_string(),
+        Token::Comma => ",".to_string(),
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = vec![];
+    let mut idx = 0;
+
+    'outer: while idx < chars.len() {
+        let char = chars[idx];
+
+        if char.is_whitespace() {
+            idx += 1;
+            continue;
+        }
+
+        // 숫자 혹은 base#number
+        if char.is_ascii_digit() {
+            let start = idx;
+            while idx < chars.len() && (chars[idx].is_ascii_alphanumeric() || chars[idx] == '#' || chars[idx] == '@' || chars[idx] == '_') {
+                idx += 1;
+            }
+            let literal: String = chars[start..idx].iter().collect();
+            tokens.push(Token::Number(parse_number(expression, &literal)?));
+            continue;
+        }
+
+        if char.is_ascii_alphabetic() || char == '_' {
+            let start = idx;
+            while idx < chars.len() && (chars[idx].is_ascii_alphanumeric() || chars[idx] == '_') {
+                idx += 1;
+            }
+            tokens.push(Token::Identifier(chars[start..idx].iter().collect()));
+            continue;
+        }
+
+        match char {
+            '(' => {
+                tokens.push(Token::LeftParen);
+                idx += 1;
+                continue;
+            }
+            ')' => {
+                tokens.push(Token::RightParen);
+                idx += 1;
+                continue;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                idx += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        for operator in OPERATORS {
+            let operator_len = operator.chars().count();
+            if idx + operator_len <= chars.len() && chars[idx..idx + operator_len].iter().copied().eq(operator.chars()) {
+                // ++, -- 는 변수에 붙어있을 때만 증감 연산자, 아니면 5--3 처럼 부호 두개로 취급
+                if operator == "++" || operator == "--" {
+                    let is_after_identifier = matches!(tokens.last(), Some(Token::Identifier(_)));
+                    let is_before_identifier = chars[idx + 2..].iter()
+                        .find(|c| false == c.is_whitespace())
+                        .is_some_and(|c| c.is_ascii_alphabetic() || *c == '_');
+                    if false == is_after_identifier && false == is_before_identifier {
+                        continue;
+                    }
+                }
+
+                tokens.push(Token::Operator(operator));
+                idx += operator_len;
+                continue 'outer;
+            }
+        }
+
+        return Err(format!("{}: syntax error: invalid arithmetic operator (error token is \"{}\")", expression.trim(), chars[idx..].iter().collect::<String>()));
+    }
+
+    Ok(tokens)
+}
+
+// 10진수, 0x 16진수, 0 으로 시작하는 8진수, base#number (2 ~ 64진수)
+fn parse_number(expression: &str, literal: &str) -> Result<i64, String> {
+    let invalid_number = || format!("{}: value too great for base (error token is \"{}\")", expression.trim(), literal);
+
+    let (base, digits) = if let Some((base, digits)) = literal.split_once('#') {
+        let Ok(base) = base.parse::<u32>() else {
+            return Err(format!("{}: invalid arithmetic base (error token is \"{}\")", expression.trim(), literal));
+        };
+        if false == (2..=64).contains(&base) {
+            return Err(format!("{}: invalid arithmetic base (error token is \"{}\")", expression.trim(), literal));
+        }
+        (base, digits)
+    } else if let Some(digits) = literal.strip_prefix("0x").or_else(|| literal.strip_prefix("0X")) {
+        (16, digits)
+    } else if literal.len() > 1 && literal.starts_with('0') {
+        (8, &literal[1..])
+    } else {
+        (10, literal)
+    };
+
+    if digits.is_empty() {
+        return Err(invalid_number());
+    }
+
+    let mut value: i64 = 0;
+    for char in digits.chars() {
+        // 36진수 이하는 대소문자 구분 없음, 그 이상은 a-z, A-Z, @, _ 순서
+        let digit = match char {
+            '0'..='9' => char as u32 - '0' as u32,
+            'a'..='z' => char as u32 - 'a' as u32 + 10,
+            'A'..='Z' if base <= 36 => char as u32 - 'A' as u32 + 10,
+            'A'..='Z' => char as u32 - 'A' as u32 + 36,
+            '@' => 62,
+            '_' => 63,
+            _ => return Err(invalid_number()),
+        };
+        if digit >= base {
+            return Err(invalid_number());
+        }
+        value = value.wrapping_mul(base as i64).wrapping_add(digit as i64);
+    }
+
+    Ok(value)
+}
+
+struct ArithmeticEvaluator<'a> {
+    expression: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl ArithmeticEvaluator<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_operator(&self) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Operator(operator)) => Some(operator),
+            _ => None,
+        }
+    }
+
+    fn syntax_error(&self) -> String {
+        match self.peek() {
+            Some(token) => format!("{}: syntax error in expression (error token is \"{}\")", self.expression.trim(), token_to_string(token)),
+            None => format!("{}: syntax error: operand expected", self.expression.trim()),
+        }
+    }
+
+    fn read_variable(&self, name: &str) -> Result<i64, String> {
+        let Some(value) = get_variable(name) else {
+            return Ok(0);
+        };
+
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(0);
+        }
+        if let Ok(number) = value.parse::<i64>() {
+            return Ok(number);
+        }
+
+        // 변수 값 자체가 수식인 경우 재귀 평가
+        evaluate_with_depth(value, self.depth + 1)
+    }
+
+    // is_evaluate 가 false 면 short circuit 등으로 평가되지 않는 쪽이라 side effect(대입, 에러) 를 만들지 않음
+    fn parse_comma(&mut self, is_evaluate: bool) -> Result<i64, String> {
+        let mut value = self.parse_assignment(is_evaluate)?;
+        while self.peek() == Some(&Token::Comma) {
+            self.pos += 1;
+            value = self.parse_assignment(is_evaluate)?;
+        }
+        Ok(value)
+    }
+
+    fn parse_assignment(&mut self, is_evaluate: bool) -> Result<i64, String> {
+        if let (Some(Token::Identifier(name)), Some(Token::Operator(operator))) = (self.tokens.get(self.pos), self.tokens.get(self.pos + 1))
+            && ASSIGNMENT_OPERATORS.contains(operator) {
+            let name = name.to_owned();
+            let operator = *operator;
+            self.pos += 2;
+
+            let rhs = self.parse_assignment(is_evaluate)?;
+            if false == is_evaluate {
+                return Ok(0);
+            }
+
+            let value = if operator == "=" {
+                rhs
+            } else {
+                let lhs = self.read_variable(&name)?;
+                self.apply_binary_operator(&operator[..operator.len() - 1], lhs, rhs)?
+            };
+
+            set_variable(&name, &value.to_string());
+            return Ok(value);
+        }
+
+        self.parse_ternary(is_evaluate)
+    }
+
+    fn parse_ternary(&mut self, is_evaluate: bool) -> Result<i64, String> {
+        let condition = self.parse_binary(0, is_evaluate)?;
+        if self.peek_operator() != Some("?") {
+            return Ok(condition);
+        }
+        self.pos += 1;
+
+        let then_value = self.parse_assignment(is_evaluate && condition != 0)?;
+        if self.peek_operator() != Some(":") {
+            return Err(format!("{}: `:' expected for conditional expression", self.expression.trim()));
+        }
+        self.pos += 1;
+        let else_value = self.parse_assignment(is_evaluate && condition == 0)?;
+
+        Ok(if condition != 0 { then_value } else { else_value })
+    }
+
+    // 우선순위가 낮은 순서대로의 binary operator
+    fn binary_levels() -> [&'static [&'static str]; 10] {
+        [
+            &["||"],
+            &["&&"],
+            &["|"],
+            &["^"],
+            &["&"],
+            &["==", "!="],
+            &["<=", ">=", "<", ">"],
+            &["<<", ">>"],
+            &["+", "-"],
+            &["*", "/", "%"],
+        ]
+    }
+
+    fn parse_binary(&mut self, level: usize, is_evaluate: bool) -> Result<i64, String> {
+        let levels = Self::binary_levels();
+        if level >= levels.len() {
+            return self.parse_power(is_evaluate);
+        }
+
+        let mut lhs = self.parse_binary(level + 1, is_evaluate)?;
+
+        while let Some(operator) = self.peek_operator() {
+            if false == levels[level].contains(&operator) {
+                break;
+            }
+            self.pos += 1;
+
+            // && 와 || 는 short circuit
+            if operator == "&&" {
+                let rhs = self.parse_binary(level + 1, is_evaluate && lhs != 0)?;
+                lhs = (lhs != 0 && rhs != 0) as i64;
+                continue;
+            }
+            if operator == "||" {
+                let rhs = self.parse_binary(level + 1, is_evaluate && lhs == 0)?;
+                lhs = (lhs != 0 || rhs != 0) as i64;
+                continue;
+            }
+
+            let rhs = self.parse_binary(level + 1, is_evaluate)?;
+            lhs = if is_evaluate {
+                self.apply_binary_operator(operator, lhs, rhs)?
+            } else {
+                0
+            };
+        }
+
+        Ok(lhs)
+    }
+
+    // ** 는 오른쪽 결합
+    fn parse_power(&mut self, is_evaluate: bool) -> Result<i64, String> {
+        let base = self.parse_unary(is_evaluate)?;
+        if self.peek_operator() != Some("**") {
+            return Ok(base);
+        }
+        self.pos += 1;
+
+        let exponent = self.parse_power(is_evaluate)?;
+        if false == is_evaluate {
+            return Ok(0);
+        }
+        self.apply_binary_operator("**", base, exponent)
+    }
+
+    fn parse_unary(&mut self, is_evaluate: bool) -> Result<i64, String> {
+        match self.peek_operator() {
+            Some("-") => {
+                self.pos += 1;
+                Ok(self.parse_unary(is_evaluate)?.wrapping_neg())
+            }
+            Some("+") => {
+                self.pos += 1;
+                self.parse_unary(is_evaluate)
+            }
+            Some("!") => {
+                self.pos += 1;
+                Ok((self.parse_unary(is_evaluate)? == 0) as i64)
+            }
+            Some("~") => {
+                self.pos += 1;
+                Ok(!self.parse_unary(is_evaluate)?)
+            }
+            // ++id, --id
+            Some(operator @ ("++" | "--")) => {
+                self.pos += 1;
+                let Some(Token::Identifier(name)) = self.peek().cloned() else {
+                    return Err(self.syntax_error());
+                };
+                self.pos += 1;
+
+                if false == is_evaluate {
+                    return Ok(0);
+                }
+                let value = self.read_variable(&name)?;
+                let value = if operator == "++" { value.wrapping_add(1) } else { value.wrapping_sub(1) };
+                set_variable(&name, &value.to_string());
+                Ok(value)
+            }
+            _ => self.parse_postfix(is_evaluate),
+        }
+    }
+
+    fn parse_postfix(&mut self, is_evaluate: bool) -> Result<i64, String> {
+        let Some(token) = self.peek().cloned() else {
+            return Err(self.syntax_error());
+        };
+
+        match token {
+            Token::Number(number) => {
+                self.pos += 1;
+                Ok(number)
+            }
+            Token::LeftParen => {
+                self.pos += 1;
+                let value = self.parse_comma(is_evaluate)?;
+                if self.peek() != Some(&Token::RightParen) {
+                    return Err(format!("{}: missing `)' (error token is \"{}\")", self.expression.trim(), self.peek().map(token_to_string).unwrap_or_default()));
+                }
+                self.pos += 1;
+                Ok(value)
+            }
+            Token::Identifier(name) => {
+                self.pos += 1;
+                if false == is_valid_variable_name(&name) {
+                    return Err(self.syntax_error());
+                }
+                if false == is_evaluate {
+                    // 평가 안되는 쪽이라도 id++ 토큰은 소비
+                    if let Some("++" | "--") = self.peek_operator() {
+                        self.pos += 1;
+                    }
+                    return Ok(0);
+                }
+
+                let value = self.read_variable(&name)?;
+                // id++, id-- 는 증감 전 값을 반환
+                match self.peek_operator() {
+                    Some("++") => {
+                        self.pos += 1;
+                        set_variable(&name, &value.wrapping_add(1).to_string());
+                    }
+                    Some("--") => {
+                        self.pos += 1;
+                        set_variable(&name, &value.wrapping_sub(1).to_string());
+                    }
+                    _ => {}
+                }
+                Ok(value)
+            }
+            _ => Err(self.syntax_error()),
+        }
+    }
+
+    fn apply_binary_operator(&self, operator: &str, lhs: i64, rhs: i64) -> Result<i64, String> {
+        let value = match operator {
+            "+" => lhs.wrapping_add(rhs),
+            "-" => lhs.wrapping_sub(rhs),
+            "*" => lhs.wrapping_mul(rhs),
+            "/" | "%" => {
+                if rhs == 0 {
+                    return Err(format!("{}: division by 0 (error token is \"{}\")", self.expression.trim(), rhs));
+                }
+                if operator == "/" { lhs.wrapping_div(rhs) } else { lhs.wrapping_rem(rhs) }
+            }
+            "**" => {
+                if rhs < 0 {
+                    return Err(format!("{}: exponent less than 0 (error token is \"{}\")", self.expression.trim(), rhs));
+                }
+                lhs.wrapping_pow(rhs.min(u32::MAX as i64) as u32)
+            }
+            "<<" => lhs.wrapping_shl(rhs as u32),
+            ">>" => lhs.wrapping_shr(rhs as u32),
+            "<" => (lhs < rhs) as i64,
+            ">" => (lhs > rhs) as i64,
+            "<=" => (lhs <= rhs) as i64,
+            ">=" => (lhs >= rhs) as i64,
+            "==" => (lhs == rhs) as i64,
+            "!=" => (lhs != rhs) as i64,
+            "&" => lhs & rhs,
+            "^" => lhs ^ rhs,
+            "|" => lhs | rhs,
+            _ => return Err(format!("{}: syntax error: invalid arithmetic operator (error token is \"{}\")", self.expression.trim(), operator)),
+        };
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::evaluate_arithmetic;
+    use crate::shell_state::variables::{get_variable, set_variable};
+
+    #[test]
+    fn operator_precedence() {
+        assert_eq!(evaluate_arithmetic("1 + 2 * 3"), Ok(7));
+        assert_eq!(evaluate_arithmetic("(1 + 2) * 3"), Ok(9));
+        assert_eq!(evaluate_arithmetic("2 ** 3 ** 2"), Ok(512));
+        assert_eq!(evaluate_arithmetic("-2 ** 2"), Ok(4));
+        assert_eq!(evaluate_arithmetic("1 + 2 << 1"), Ok(6));
+        assert_eq!(evaluate_arithmetic("1 < 2 == 1"), Ok(1));
+        assert_eq!(evaluate_arithmetic("6 & 3 ^ 1 | 8"), Ok(11));
+        assert_eq!(evaluate_arithmetic("1 || 0 && 0"), Ok(1));
+        assert_eq!(evaluate_arithmetic("!0 + ~0"), Ok(0));
+        assert_eq!(evaluate_arithmetic("7 / 2 * 2 + 7 % 2"), Ok(7));
+    }
+
+    #[test]
+    fn assignment_operators() {
+        // test 가 병렬로 실행되므로 변수 이름은 test 마다 다르게
+        assert_eq!(evaluate_arithmetic("arith_assign = 10"), Ok(10));
+        assert_eq!(evaluate_arithmetic("arith_assign += 5"), Ok(15));
+        assert_eq!(evaluate_arithmetic("arith_assign -= 3"), Ok(12));
+        assert_eq!(evaluate_arithmetic("arith_assign *= 2"), Ok(24));
+        assert_eq!(evaluate_arithmetic("arith_assign /= 5"), Ok(4));
+        assert_eq!(evaluate_arithmetic("arith_assign %= 3"), Ok(1));
+        assert_eq!(evaluate_arithmetic("arith_assign <<= 4"), Ok(16));
+        assert_eq!(evaluate_arithmetic("arith_assign >>= 1"), Ok(8));
+        assert_eq!(evaluate_arithmetic("arith_assign |= 3"), Ok(11));
+        assert_eq!(evaluate_arithmetic("arith_assign &= 6"), Ok(2));
+        assert_eq!(evaluate_arithmetic("arith_assign ^= 7"), Ok(5));
+        assert_eq!(get_variable("arith_assign"), Some("5".to_string()));
+
+        // 오른쪽부터 대입
+        assert_eq!(evaluate_arithmetic("arith_left = arith_right = 3"), Ok(3));
+        assert_eq!(get_variable("arith_left"), Some("3".to_string()));
+        assert_eq!(get_variable("arith_right"), Some("3".to_string()));
+    }
+
+    #[test]
+    fn increment_and_decrement() {
+        set_variable("arith_counter", "5");
+
+        assert_eq!(evaluate_arithmetic("arith_counter++"), Ok(5));
+        assert_eq!(get_variable("arith_counter"), Some("6".to_string()));
+        assert_eq!(evaluate_arithmetic("++arith_counter"), Ok(7));
+        assert_eq!(evaluate_arithmetic("arith_counter--"), Ok(7));
+        assert_eq!(evaluate_arithmetic("--arith_counter"), Ok(5));
+        assert_eq!(get_variable("arith_counter"), Some("5".to_string()));
+    }
+
+    #[test]
+    fn variables_are_evaluated_as_expressions() {
+        set_variable("arith_expression", "2 + 3");
+
+        assert_eq!(evaluate_arithmetic("arith_expression * 2"), Ok(10));
+        // 없는 변수는 0
+        assert_eq!(evaluate_arithmetic("arith_unset_variable + 1"), Ok(1));
+    }
+
+    #[test]
+    fn ternary_and_short_circuit_skip_side_effects() {
+        assert_eq!(evaluate_arithmetic("1 ? 10 : 20"), Ok(10));
+        assert_eq!(evaluate_arithmetic("0 ? 10 : 1 ? 20 : 30"), Ok(20));
+
+        set_variable("arith_skipped", "0");
+        assert_eq!(evaluate_arithmetic("1 ? 1 : (arith_skipped = 9)"), Ok(1));
+        assert_eq!(evaluate_arithmetic("0 && (arith_skipped = 9)"), Ok(0));
+        assert_eq!(evaluate_arithmetic("1 || (arith_skipped = 9)"), Ok(1));
+        assert_eq!(get_variable("arith_skipped"), Some("0".to_string()));
+    }
+
+    #[test]
+    fn comma_returns_last_value() {
+        assert_eq!(evaluate_arithmetic("arith_comma = 1, arith_comma + 1"), Ok(2));
+    }
+
+    #[test]
+    fn division_by_zero_is_error() {
+        assert!(evaluate_arithmetic("1 / 0").unwrap_err().contains("division by 0"));
+        assert!(evaluate_arithmetic("1 % 0").unwrap_err().contains("division by 0"));
+        // 단락 평가로 계산하지 않는 쪽은 에러가 아님
+        assert_eq!(evaluate_arithmetic("0 && 1 / 0"), Ok(0));
+    }
+
+    #[test]
+    fn number_bases() {
+        assert_eq!(evaluate_arithmetic("0x1f"), Ok(31));
+        assert_eq!(evaluate_arithmetic("010"), Ok(8));
+        assert_eq!(evaluate_arithmetic("2#1010"), Ok(10));
+        assert_eq!(evaluate_arithmetic("16#ff"), Ok(255));
+        assert_eq!(evaluate_arithmetic("36#z"), Ok(35));
+        assert!(evaluate_arithmetic("1#1").unwrap_err().contains("invalid arithmetic base"));
+        assert!(evaluate_arithmetic("65#1").unwrap_err().contains("invalid arithmetic base"));
+        assert!(evaluate_arithmetic("2#2").is_err());
+        assert!(evaluate_arithmetic("08").is_err());
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert!(evaluate_arithmetic("1 +").is_err());
+        assert!(evaluate_arithmetic("(1 + 2").unwrap_err().contains("missing `)'"));
+        assert!(evaluate_arithmetic("1 ? 2").unwrap_err().contains("`:' expected"));
+        assert!(evaluate_arithmetic("2 ** -1").unwrap_err().contains("exponent less than 0"));
+        assert_eq!(evaluate_arithmetic(""), Ok(0));
+    }
+}
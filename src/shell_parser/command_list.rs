@@ -0,0 +1,994 @@
+// 한 줄 입력을 `;`, `&&`, `||` 로 구분된 command list 로 파싱한다.
+// `( ... )` 는 fork 된 자식에서 실행되는 subshell, `{ ...; }` 는 현재 shell 에서 실행되는 group 으로 파싱한다.
+// `(( expr ))` 는 산술 command, `[[ expr ]]` 는 조건식 command 로 파싱한다.
+// `a | b | c` 는 각 command 를 fork 된 자식에서 pipe 로 연결해서 실행하는 pipeline 으로 파싱한다.
+// `name() { ...; }` 와 `function name { ...; }` 는 shell 함수 정의로 파싱한다.
+// `while list; do list; done` 과 `until list; do list; done` 은 반복 command 로 파싱하고, 뒤의 redirection 은 반복 전체에 적용한다.
+// `if`, `for`, `case` 도 같은 방식으로 파싱하고, 뒤의 redirection 은 command 전체에 적용한다.
+// `<<DELIM` here document 는 파싱 전에 본문을 쿼터로 묶은 단어로 바꿔서 `<<<` here string 과 같이 redirection 단어로 처리한다.
+// simple command 는 기존 파싱 로직(special_char_args_builder 등)을 그대로 쓰기 위해 원본 문자열 그대로 보관한다.
+// 실행하지 못하는 select 는 본문이 조건 없이 실행되지 않도록 syntax error 로 처리한다.
+
+use crate::shell_parser::expansion::find_arithmetic_end;
+use crate::shell_state::variables::is_valid_variable_name;
+
+const UNSUPPORTED_RESERVED_WORDS: [&str; 1] = ["select"];
+// 짝이 되는 compound command 없이 나오면 syntax error 인 예약어
+const CLOSING_RESERVED_WORDS: [&str; 7] = ["then", "elif", "else", "fi", "do", "done", "esac"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListOperator {
+    // 첫번째 command 혹은 `;` / 개행 뒤의 command
+    Sequence,
+    // `&&` 뒤의 command : 이전 command 가 성공했을 때만 실행
+    And,
+    // `||` 뒤의 command : 이전 command 가 실패했을 때만 실행
+    Or,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupRedirection {
+    // "1>", "2>", ">", "1>>", "2>>", ">>", "<", "0<" 중 하나
+    // fd 복제인 "1>&", "2>&", ">&" 는 output 이 복제할 fd 번호
+    // here string 인 "<<<", "0<<<" 는 output 이 stdin 으로 넘길 내용 (개행을 붙여서 넘김)
+    // here document 인 "<<", "0<<" 는 output 이 stdin 으로 넘길 본문
+    pub redirect: String,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandNode {
+    Simple(String),
+    Arithmetic(String),
+    Conditional(String),
+    Subshell(CommandList, Vec<GroupRedirection>),
+    BraceGroup(CommandList, Vec<GroupRedirection>),
+    // 조건 list 가 성공하는 동안 본문 list 를 반복
+    While(CommandList, CommandList, Vec<GroupRedirection>),
+    // 조건 list 가 실패하는 동안 본문 list 를 반복
+    Until(CommandList, CommandList, Vec<GroupRedirection>),
+    // if, elif 의 (조건 list, 본문 list) 들과 else 본문 list
+    If(Vec<(CommandList, CommandList)>, Option<CommandList>, Vec<GroupRedirection>),
+    // 변수 이름, in 뒤의 단어들 (실행할때 확장하기 위해 원본 문자열, 없으면 positional parameter), 본문 list
+    For(String, Option<String>, CommandList, Vec<GroupRedirection>),
+    // case 단어와 (pattern 들, 본문 list) 들, 단어와 pattern 은 실행할때 확장하기 위해 원본 문자열
+    Case(String, Vec<(Vec<String>, CommandList)>, Vec<GroupRedirection>),
+    // 앞 command 의 stdout 을 다음 command 의 stdin 으로 연결 (2개 이상)
+    Pipeline(Vec<CommandNode>),
+    FunctionDefinition(String, Box<CommandNode>),
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CommandList {
+    // 각 command 앞에 붙은 operator 와 command
+    pub commands: Vec<(ListOperator, CommandNode)>,
+}
+
+pub fn parse_command_list(input: &str) -> Result<CommandList, String> {
+    let mut parser = CommandListParser {
+        chars: inline_here_documents(input).chars().collect(),
+        pos: 0,
+    };
+
+    let command_list = parser.parse_list(&[])?;
+
+    // 최상위에서 list 파싱이 끝났는데 남은 문자가 있으면 짝이 안맞는 괄호
+    parser.skip_whitespace();
+    if let Some(char) = parser.peek() {
+        return Err(format!("syntax error near unexpected token `{}'", char));
+    }
+
+    Ok(command_list)
+}
+
+// simple command 에서 쿼터 밖의 `> file`, `2>> file`, `< file`, `<<< word`, `<<"본문"` 같은 redirection 을 떼어내고 나머지 인자와 함께 반환
+// builtin 을 apply_group_redirections 로 외부 command 와 같은 fd 에 출력하게 할 때 사용
+pub fn split_redirections(input: &str) -> Result<(String, Vec<GroupRedirection>), String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut rest = String::new();
+    let mut redirections = vec![];
+    let mut is_single_quote = false;
+    let mut is_double_quote = false;
+
+    let mut idx = 0;
+    while idx < chars.len() {
+        let char = chars[idx];
+
+        if is_single_quote {
+            if char == '\'' {
+                is_single_quote = false;
+            }
+            rest.push(char);
+            idx += 1;
+            continue;
+        }
+
+        match char {
+            '\\' => {
+                rest.push(char);
+                if let Some(next_char) = chars.get(idx + 1) {
+                    rest.push(*next_char);
+                }
+                idx += 2;
+                continue;
+            }
+            '\'' if false == is_double_quote => is_single_quote = true,
+            '"' => is_double_quote = !is_double_quote,
+            _ => {}
+        }
+
+        // 단어 시작의 1>, 2>, 0< 혹은 어디서든 > < << <<< 가 redirection
+        let is_word_start = idx == 0 || chars[idx - 1] == ' ' || chars[idx - 1] == '\t';
+        let is_redirection = false == is_double_quote
+            && (char == '>'
+                || char == '<'
+                || (is_word_start && matches!(char, '1' | '2') && chars.get(idx + 1) == Some(&'>'))
+                || (is_word_start && char == '0' && chars.get(idx + 1) == Some(&'<')));
+        if false == is_redirection {
+            rest.push(char);
+            idx += 1;
+            continue;
+        }
+
+        let mut parser = CommandListParser {
+            chars: chars.clone(),
+            pos: idx,
+        };
+        redirections.extend(parser.parse_group_redirections()?);
+        idx = parser.pos;
+        rest.push(' ');
+    }
+
+    Ok((rest.trim().to_string(), redirections))
+}
+
+// `<<` 뒤에 본문을 넣을 here document
+pub struct PendingHereDocument {
+    // output 에서 `<<` 바로 뒤의 위치
+    insert_idx: usize,
+    pub delimiter: String,
+    // delimiter 에 쿼터나 \ 가 있으면 본문을 확장하지 않음
+    is_quoted: bool,
+    // <<- 는 본문과 delimiter 줄 앞의 탭을 지움
+    pub is_strip_tabs: bool,
+}
+
+// `<<DELIM` 다음 줄부터 DELIM 만 있는 줄까지를 본문으로 떼어내서 `<<"본문"` 처럼 쿼터로 묶은 단어로 바꾼다
+// 한 줄에 여러개면 순서대로 본문을 읽고, DELIM 줄이 없으면 입력 끝까지가 본문
+fn inline_here_documents(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut output = String::with_capacity(input.len());
+    let mut pending_here_documents = vec![];
+    let mut is_single_quote = false;
+    let mut is_double_quote = false;
+
+    let mut idx = 0;
+    while idx < chars.len() {
+        let char = chars[idx];
+
+        if is_single_quote {
+            if char == '\'' {
+                is_single_quote = false;
+            }
+            output.push(char);
+            idx += 1;
+            continue;
+        }
+
+        match char {
+            '\\' => {
+                output.extend(&chars[idx..(idx + 2).min(chars.len())]);
+                idx += 2;
+                continue;
+            }
+            '\'' if false == is_double_quote => is_single_quote = true,
+            '"' => is_double_quote = !is_double_quote,
+            // (( a << 2 )) 의 << 는 shift 연산자
+            '(' if chars.get(idx + 1) == Some(&'(') && let Some(end_idx) = find_arithmetic_end(&chars, idx + 2) => {
+                output.extend(&chars[idx..end_idx + 2]);
+                idx = end_idx + 2;
+                continue;
+            }
+            // 주석은 줄 끝까지 그대로
+            '#' if false == is_double_quote && (idx == 0 || chars[idx - 1].is_whitespace()) => {
+                while idx < chars.len() && chars[idx] != '\n' {
+                    output.push(chars[idx]);
+                    idx += 1;
+                }
+                continue;
+            }
+            '<' if false == is_double_quote && chars.get(idx + 1) == Some(&'<') => {
+                // <<< 는 here string
+                if chars.get(idx + 2) == Some(&'<') {
+                    output.push_str("<<<");
+                    idx += 3;
+                    continue;
+                }
+
+                let (here_document, next_idx) = scan_here_document_delimiter(&chars, idx + 2);
+                idx = next_idx;
+
+                output.push_str("<<");
+                // delimiter 가 없으면 parse_group_redirections 에서 syntax error
+                if here_document.delimiter.is_empty() && false == here_document.is_quoted {
+                    continue;
+                }
+                pending_here_documents.push(PendingHereDocument { insert_idx: output.len(), ..here_document });
+                continue;
+            }
+            '\n' if false == is_double_quote && false == pending_here_documents.is_empty() => {
+                output.push('\n');
+                idx = read_here_document_bodies(&chars, idx + 1, &mut output, std::mem::take(&mut pending_here_documents));
+                continue;
+            }
+            _ => {}
+        }
+
+        output.push(char);
+        idx += 1;
+    }
+
+    // 본문 없이 입력이 끝난 here document 는 빈 본문
+    read_here_document_bodies(&chars, chars.len(), &mut output, pending_here_documents);
+
+    output
+}
+
+// `<<` 다음 위치부터 `-` 와 delimiter 단어를 읽어서 (insert_idx 는 0) delimiter 다음 위치와 함께 반환
+pub fn scan_here_document_delimiter(chars: &[char], start: usize) -> (PendingHereDocument, usize) {
+    let mut idx = start;
+    let is_strip_tabs = chars.get(idx) == Some(&'-');
+    if is_strip_tabs {
+        idx += 1;
+    }
+    while matches!(chars.get(idx), Some(' ' | '\t')) {
+        idx += 1;
+    }
+
+    // delimiter 는 쿼터와 \ 를 지운 단어
+    let mut delimiter = String::new();
+    let mut is_quoted = false;
+    let mut quote = None;
+    while let Some(&char) = chars.get(idx) {
+        match (quote, char) {
+            (Some(quote_char), _) if char == quote_char => quote = None,
+            (Some(_), _) => delimiter.push(char),
+            (None, '\'' | '"') => {
+                quote = Some(char);
+                is_quoted = true;
+            }
+            (None, '\\') => {
+                is_quoted = true;
+                idx += 1;
+                delimiter.extend(chars.get(idx));
+            }
+            (None, ' ' | '\t' | '\n' | ';' | '&' | '|' | '(' | ')' | '<' | '>') => break,
+            (None, _) => delimiter.push(char),
+        }
+        idx += 1;
+    }
+
+    (PendingHereDocument { insert_idx: 0, delimiter, is_quoted, is_strip_tabs }, idx.min(chars.len()))
+}
+
+// start 부터 순서대로 각 here document 의 본문을 읽어서 `<<` 뒤에 넣고, 본문 다음 위치를 반환
+fn read_here_document_bodies(chars: &[char], start: usize, output: &mut String, pending_here_documents: Vec<PendingHereDocument>) -> usize {
+    let mut idx = start;
+    let mut bodies = vec![];
+
+    for here_document in &pending_here_documents {
+        let mut body = String::new();
+        while idx < chars.len() {
+            let line_end = chars[idx..].iter().position(|char| *char == '\n').map_or(chars.len(), |offset| idx + offset);
+            let raw_line: String = chars[idx..line_end].iter().collect();
+            idx = (line_end + 1).min(chars.len());
+
+            let line = if here_document.is_strip_tabs { raw_line.trim_start_matches('\t') } else { raw_line.as_str() };
+            if line == here_document.delimiter {
+                break;
+            }
+            body.push_str(line);
+            body.push('\n');
+        }
+        bodies.push(quote_here_document(&body, here_document.is_quoted));
+    }
+
+    // 뒤에서부터 넣어야 앞쪽 위치가 바뀌지 않음
+    for (here_document, body) in pending_here_documents.iter().zip(bodies).rev() {
+        output.insert_str(here_document.insert_idx, &body);
+    }
+
+    idx
+}
+
+// 쿼터로 묶인 delimiter 면 확장하지 않도록 '' 로, 아니면 $ 확장이 되도록 "" 로 묶음 (본문의 " 는 글자 그대로)
+fn quote_here_document(body: &str, is_quoted: bool) -> String {
+    if is_quoted {
+        return format!("'{}'", body.replace('\'', "'\\''"));
+    }
+
+    let mut quoted = String::from("\"");
+    let mut chars = body.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => quoted.push_str("\\\""),
+            '\\' if chars.peek() == Some(&'"') => quoted.push_str("\\\\"),
+            _ => quoted.push(char),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+struct CommandListParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl CommandListParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(char) = self.peek() {
+            if char != ' ' && char != '\t' {
+                break;
+            }
+            self.pos += 1;
+        }
+    }
+
+    // `{`, `}`, `do`, `done`, `if`, `fi` 등은 단어 단위로만 예약어로 취급
+    fn is_reserved_word(&self, word: &str) -> bool {
+        let word: Vec<char> = word.chars().collect();
+        if false == self.chars[self.pos..].starts_with(&word) {
+            return false;
+        }
+        match self.peek_at(word.len()) {
+            None => true,
+            Some(next) => next == ' ' || next == '\t' || next == '\n' || next == ';' || next == ')' || next == '>' || next == '<' || next == '&' || next == '|',
+        }
+    }
+
+    // terminator 는 `)`, case 본문 끝의 `;;` 혹은 예약어 `}`, `do`, `done`, `then`, `fi` 등
+    fn is_list_end(&self, terminators: &[&str]) -> bool {
+        terminators.iter().any(|terminator| match *terminator {
+            ")" => self.peek() == Some(')'),
+            ";;" => self.peek() == Some(';') && self.peek_at(1) == Some(';'),
+            word => self.is_reserved_word(word),
+        })
+    }
+
+    fn parse_list(&mut self, terminators: &[&str]) -> Result<CommandList, String> {
+        let mut command_list = CommandList::default();
+        let mut operator = ListOperator::Sequence;
+
+        loop {
+            self.skip_whitespace();
+
+            // 빈 줄 혹은 `;` 뒤에서 끝난 경우
+            let Some(char) = self.peek() else {
+                if operator != ListOperator::Sequence {
+                    return Err("syntax error: unexpected end of input".to_string());
+                }
+                break;
+            };
+
+            if char == '\n' && operator == ListOperator::Sequence {
+                self.pos += 1;
+                continue;
+            }
+
+            if self.is_list_end(terminators) {
+                if operator != ListOperator::Sequence {
+                    return Err(format!("syntax error near unexpected token `{}'", char));
+                }
+                break;
+            }
+
+            let node = self.parse_pipeline(terminators)?;
+            command_list.commands.push((operator, node));
+
+            // command 뒤의 operator 파싱
+            self.skip_whitespace();
+            match (self.peek(), self.peek_at(1)) {
+                (Some('&'), Some('&')) => {
+                    self.pos += 2;
+                    operator = ListOperator::And;
+                }
+                (Some('|'), Some('|')) => {
+                    self.pos += 2;
+                    operator = ListOperator::Or;
+                }
+                (Some(';'), Some(';')) if self.is_list_end(terminators) => break,
+                (Some(';'), _) | (Some('\n'), _) => {
+                    self.pos += 1;
+                    operator = ListOperator::Sequence;
+                }
+                (None, _) => break,
+                _ => {
+                    if self.is_list_end(terminators) {
+                        break;
+                    }
+                    let char = self.peek().unwrap_or(' ');
+                    return Err(format!("syntax error near unexpected token `{}'", char));
+                }
+            }
+
+            // `&&` / `||` 뒤에는 개행이 와도 다음 command 로 이어짐
+            if operator != ListOperator::Sequence {
+                while self.peek() == Some('\n') || self.peek() == Some(' ') || self.peek() == Some('\t') {
+                    self.pos += 1;
+                }
+            }
+        }
+
+        Ok(command_list)
+    }
+
+    // `||` 가 아닌 `|` 로 이어진 command 들, 하나면 그 command 그대로
+    fn parse_pipeline(&mut self, terminators: &[&str]) -> Result<CommandNode, String> {
+        let mut nodes = vec![self.parse_node(terminators)?];
+
+        loop {
+            self.skip_whitespace();
+            if false == (self.peek() == Some('|') && self.peek_at(1) != Some('|')) {
+                break;
+            }
+            self.pos += 1;
+
+            // `|` 뒤에는 개행이 와도 다음 command 로 이어짐
+            while matches!(self.peek(), Some(' ' | '\t' | '\n')) {
+                self.pos += 1;
+            }
+            if self.peek().is_none() || self.is_list_end(terminators) {
+                return Err("syntax error: unexpected end of input after `|'".to_string());
+            }
+            nodes.push(self.parse_node(terminators)?);
+        }
+
+        if nodes.len() == 1 {
+            return Ok(nodes.remove(0));
+        }
+        Ok(CommandNode::Pipeline(nodes))
+    }
+
+    fn parse_node(&mut self, terminators: &[&str]) -> Result<CommandNode, String> {
+        if let Some(function_name) = self.parse_function_header() {
+            // 함수 본문은 { } 혹은 ( ) 만 가능
+            while matches!(self.peek(), Some(' ' | '\t' | '\n')) {
+                self.pos += 1;
+            }
+            if false == (self.peek() == Some('(') || self.is_reserved_word("{")) {
+                return Err(format!("syntax error: `{}' function body must be a group command", function_name));
+            }
+            let body = self.parse_node(terminators)?;
+            return Ok(CommandNode::FunctionDefinition(function_name, Box::new(body)));
+        }
+
+        // (( expr )), 짝이 맞는 `))` 가 없으면 `( (` 처럼 중첩된 subshell 로 취급
+        if self.peek() == Some('(') && self.peek_at(1) == Some('(')
+            && let Some(end_idx) = find_arithmetic_end(&self.chars, self.pos + 2) {
+            let expression: String = self.chars[self.pos + 2..end_idx].iter().collect();
+            self.pos = end_idx + 2;
+            return Ok(CommandNode::Arithmetic(expression));
+        }
+
+        // [[ expr ]]
+        if self.peek() == Some('[') && self.peek_at(1) == Some('[') && matches!(self.peek_at(2), Some(' ' | '\t')) {
+            let Some(end_idx) = self.find_conditional_end(self.pos + 2) else {
+                return Err("syntax error: unexpected end of input, expecting `]]'".to_string());
+            };
+            let expression: String = self.chars[self.pos + 2..end_idx].iter().collect();
+            self.pos = end_idx + 2;
+            return Ok(CommandNode::Conditional(expression.trim().to_string()));
+        }
+
+        // subshell
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            let command_list = self.parse_list(&[")"])?;
+            if self.peek() != Some(')') {
+                return Err("syntax error: unexpected end of input, expecting `)'".to_string());
+            }
+            if command_list.commands.is_empty() {
+                return Err("syntax error near unexpected token `)'".to_string());
+            }
+            self.pos += 1;
+            let redirections = self.parse_group_redirections()?;
+            return Ok(CommandNode::Subshell(command_list, redirections));
+        }
+
+        // brace group
+        if self.is_reserved_word("{") {
+            self.pos += 1;
+            let command_list = self.parse_list(&["}"])?;
+            if false == self.is_reserved_word("}") {
+                return Err("syntax error: unexpected end of input, expecting `}'".to_string());
+            }
+            if command_list.commands.is_empty() {
+                return Err("syntax error near unexpected token `}'".to_string());
+            }
+            self.pos += 1;
+            let redirections = self.parse_group_redirections()?;
+            return Ok(CommandNode::BraceGroup(command_list, redirections));
+        }
+
+        // while / until
+        for (keyword, is_until) in [("while", false), ("until", true)] {
+            if self.is_reserved_word(keyword) {
+                self.pos += keyword.len();
+                return self.parse_loop(is_until);
+            }
+        }
+
+        if self.is_reserved_word("if") {
+            self.pos += "if".len();
+            return self.parse_if();
+        }
+        if self.is_reserved_word("for") {
+            self.pos += "for".len();
+            return self.parse_for();
+        }
+        if self.is_reserved_word("case") {
+            self.pos += "case".len();
+            return self.parse_case();
+        }
+
+        // 지원하지 않는 compound command 예약어
+        let word = self.peek_word();
+        if UNSUPPORTED_RESERVED_WORDS.contains(&word.as_str()) {
+            return Err(format!("syntax error: `{}' is not supported", word));
+        }
+        // 짝이 되는 if, while, case 등이 없는 then, fi, do, done, esac 등
+        if CLOSING_RESERVED_WORDS.contains(&word.as_str()) {
+            return Err(format!("syntax error near unexpected token `{}'", word));
+        }
+
+        let simple_command = self.scan_simple_command(terminators)?;
+        if simple_command.is_empty() {
+            let char = self.peek().unwrap_or(' ');
+            return Err(format!("syntax error near unexpected token `{}'", char));
+        }
+
+        Ok(CommandNode::Simple(simple_command))
+    }
+
+    // list 뒤에 와야 하는 예약어를 확인하고 넘어감, list 가 비어있으면 그 예약어가 unexpected token
+    fn expect_reserved_word(&mut self, word: &str, command_list: &CommandList) -> Result<(), String> {
+        if false == self.is_reserved_word(word) {
+            return Err(format!("syntax error: unexpected end of input, expecting `{}'", word));
+        }
+        if command_list.commands.is_empty() {
+            return Err(format!("syntax error near unexpected token `{}'", word));
+        }
+        self.pos += word.len();
+        Ok(())
+    }
+
+    // while / until 뒤의 `list; do list; done [redirection]`
+    fn parse_loop(&mut self, is_until: bool) -> Result<CommandNode, String> {
+        let condition = self.parse_list(&["do"])?;
+        if condition.commands.is_empty() && self.is_reserved_word("do") {
+            return Err("syntax error near unexpected token `do'".to_string());
+        }
+        let body = self.parse_do_group()?;
+
+        let redirections = self.parse_group_redirections()?;
+        if is_until {
+            return Ok(CommandNode::Until(condition, body, redirections));
+        }
+        Ok(CommandNode::While(condition, body, redirections))
+    }
+
+    // `do list; done`
+    fn parse_do_group(&mut self) -> Result<CommandList, String> {
+        if false == self.is_reserved_word("do") {
+            return Err("syntax error: unexpected end of input, expecting `do'".to_string());
+        }
+        self.pos += "do".len();
+
+        let body = self.parse_list(&["done"])?;
+        self.expect_reserved_word("done", &body)?;
+        Ok(body)
+    }
+
+    // if 뒤의 `list; then list; [elif list; then list;]... [else list;] fi [redirection]`
+    fn parse_if(&mut self) -> Result<CommandNode, String> {
+        let mut branches = vec![];
+        let mut else_body = None;
+
+        loop {
+            let condition = self.parse_list(&["then"])?;
+            self.expect_reserved_word("then", &condition)?;
+
+            let body = self.parse_list(&["elif", "else", "fi"])?;
+            let next_word = ["elif", "else"].into_iter().find(|word| self.is_reserved_word(word)).unwrap_or("fi");
+            self.expect_reserved_word(next_word, &body)?;
+            branches.push((condition, body));
+
+            match next_word {
+                "elif" => continue,
+                "else" => {
+                    let body = self.parse_list(&["fi"])?;
+                    self.expect_reserved_word("fi", &body)?;
+                    else_body = Some(body);
+                }
+                _ => {}
+            }
+            break;
+        }
+
+        let redirections = self.parse_group_redirections()?;
+        Ok(CommandNode::If(branches, else_body, redirections))
+    }
+
+    // for 뒤의 `name [in words]; do list; done [redirection]`
+    fn parse_for(&mut self) -> Result<CommandNode, String> {
+        self.skip_whitespace();
+        let name = self.scan_word();
+        if false == is_valid_variable_name(&name) {
+            return Err(format!("syntax error: `{}': not a valid identifier", name));
+        }
+        self.skip_whitespace();
+
+        let mut words = None;
+        if self.is_reserved_word("in") {
+            self.pos += "in".len();
+            let start = self.pos;
+            loop {
+                self.skip_whitespace();
+                if self.scan_word().is_empty() {
+                    break;
+                }
+            }
+            words = Some(self.chars[start..self.pos].iter().collect::<String>().trim().to_string());
+        }
+
+        // do 앞의 ; 혹은 개행
+        if self.peek() == Some(';') {
+            self.pos += 1;
+        }
+        while matches!(self.peek(), Some(' ' | '\t' | '\n')) {
+            self.pos += 1;
+        }
+
+        let body = self.parse_do_group()?;
+        let redirections = self.parse_group_redirections()?;
+        Ok(CommandNode::For(name, words, body, redirections))
+    }
+
+    // case 뒤의 `word in [(]pattern [| pattern]...) list ;; ... esac [redirection]`
+    fn parse_case(&mut self) -> Result<CommandNode, String> {
+        self.skip_whitespace();
+        let word = self.scan_word();
+        while matches!(self.peek(), Some(' ' | '\t' | '\n')) {
+            self.pos += 1;
+        }
+        if word.is_empty() || false == self.is_reserved_word("in") {
+            return Err("syntax error: expecting `in' after case word".to_string());
+        }
+        self.pos += "in".len();
+
+        let mut items = vec![];
+        loop {
+            while matches!(self.peek(), Some(' ' | '\t' | '\n')) {
+                self.pos += 1;
+            }
+            if self.is_reserved_word("esac") {
+                self.pos += "esac".len();
+                break;
+            }
+
+            // 앞의 ( 는 생략 가능
+            if self.peek() == Some('(') {
+                self.pos += 1;
+            }
+            let mut patterns = vec![];
+            loop {
+                self.skip_whitespace();
+                let pattern = self.scan_word();
+                self.skip_whitespace();
+                if pattern.is_empty() {
+                    return Err(self.unexpected_token_error());
+                }
+                patterns.push(pattern);
+
+                match self.peek() {
+                    Some('|') => self.pos += 1,
+                    Some(')') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return Err(self.unexpected_token_error()),
+                }
+            }
+
+            let body = self.parse_list(&[";;", "esac"])?;
+            items.push((patterns, body));
+            if self.peek() == Some(';') && self.peek_at(1) == Some(';') {
+                self.pos += 2;
+            } else if false == self.is_reserved_word("esac") {
+                return Err("syntax error: unexpected end of input, expecting `esac'".to_string());
+            }
+        }
+
+        let redirections = self.parse_group_redirections()?;
+        Ok(CommandNode::Case(word, items, redirections))
+    }
+
+    fn unexpected_token_error(&self) -> String {
+        match self.peek() {
+            Some('\n') | None => "syntax error near unexpected token `newline'".to_string(),
+            Some(char) => format!("syntax error near unexpected token `{}'", char),
+        }
+    }
+
+    // 쿼터와 `$( )`, `${ }` 안쪽은 무시하고 공백이나 operator 가 나올때까지를 한 단어의 원본 문자열로 읽음
+    fn scan_word(&mut self) -> String {
+        let start = self.pos;
+        let mut is_single_quote = false;
+        let mut is_double_quote = false;
+        let mut depth = 0;
+
+        while let Some(char) = self.peek() {
+            if is_single_quote {
+                if char == '\'' {
+                    is_single_quote = false;
+                }
+                self.pos += 1;
+                continue;
+            }
+
+            match char {
+                '\\' => {
+                    self.pos = (self.pos + 2).min(self.chars.len());
+                    continue;
+                }
+                '\'' if false == is_double_quote => is_single_quote = true,
+                '"' => is_double_quote = !is_double_quote,
+                '$' if matches!(self.peek_at(1), Some('(' | '{')) => {
+                    depth += 1;
+                    self.pos += 2;
+                    continue;
+                }
+                '(' | '{' if depth > 0 => depth += 1,
+                ')' | '}' if depth > 0 => depth -= 1,
+                _ if is_double_quote || depth > 0 => {}
+                ' ' | '\t' | '\n' | ';' | '&' | '|' | '(' | ')' | '<' | '>' => break,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    // `name ()` 혹은 `function name [()]` 이면 함수 이름을 반환하고, 아니면 위치를 되돌림
+    fn parse_function_header(&mut self) -> Option<String> {
+        let start = self.pos;
+
+        let read_name = |parser: &mut Self| -> String {
+            let name_start = parser.pos;
+            while let Some(char) = parser.peek() {
+                if false == (char.is_ascii_alphanumeric() || char == '_' || char == '-' || char == '.') {
+                    break;
+                }
+                parser.pos += 1;
+            }
+            parser.chars[name_start..parser.pos].iter().collect()
+        };
+
+        let mut name = read_name(self);
+        let mut is_function_keyword = false;
+        if name == "function" && matches!(self.peek(), Some(' ' | '\t')) {
+            self.skip_whitespace();
+            name = read_name(self);
+            is_function_keyword = true;
+        }
+
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            self.pos = start;
+            return None;
+        }
+
+        self.skip_whitespace();
+        if self.peek() == Some('(') {
+            let paren_start = self.pos;
+            self.pos += 1;
+            self.skip_whitespace();
+            if self.peek() == Some(')') {
+                self.pos += 1;
+                return Some(name);
+            }
+            self.pos = paren_start;
+        }
+
+        // function 키워드가 있으면 () 생략 가능
+        if is_function_keyword {
+            return Some(name);
+        }
+
+        self.pos = start;
+        None
+    }
+
+    // 쿼터 밖에서 공백 뒤에 단어로 나오는 `]]` 의 시작 인덱스
+    fn find_conditional_end(&self, start: usize) -> Option<usize> {
+        let mut is_single_quote = false;
+        let mut is_double_quote = false;
+        let mut idx = start;
+
+        while idx < self.chars.len() {
+            let char = self.chars[idx];
+
+            if is_single_quote {
+                if char == '\'' {
+                    is_single_quote = false;
+                }
+                idx += 1;
+                continue;
+            }
+
+            match char {
+                '\\' => {
+                    idx += 2;
+                    continue;
+                }
+                '\'' if false == is_double_quote => is_single_quote = true,
+                '"' => is_double_quote = !is_double_quote,
+                ']' if false == is_double_quote
+                    && self.chars.get(idx + 1) == Some(&']')
+                    && matches!(self.chars[idx - 1], ' ' | '\t')
+                    && matches!(self.chars.get(idx + 2), None | Some(' ' | '\t' | '\n' | ';' | '&' | '|' | ')')) => {
+                    return Some(idx);
+                }
+                _ => {}
+            }
+            idx += 1;
+        }
+
+        None
+    }
+
+    // 현재 위치의 쿼터가 없는 단어 (예약어 확인용)
+    fn peek_word(&self) -> String {
+        self.chars[self.pos..]
+            .iter()
+            .take_while(|char| false == matches!(char, ' ' | '\t' | '\n' | ';' | '&' | '|' | '(' | ')' | '<' | '>'))
+            .collect()
+    }
+
+    // 쿼터와 `$( )` 안쪽은 무시하고, 최상위 operator 가 나올때까지를 simple command 로 자른다
+    fn scan_simple_command(&mut self, terminators: &[&str]) -> Result<String, String> {
+        let start = self.pos;
+        let mut is_single_quote = false;
+        let mut is_double_quote = false;
+        let mut is_ignore_next = false;
+        let mut dollar_paren_depth = 0;
+
+        while let Some(char) = self.peek() {
+            if is_ignore_next {
+                is_ignore_next = false;
+                self.pos += 1;
+                continue;
+            }
+
+            if is_single_quote {
+                if char == '\'' {
+                    is_single_quote = false;
+                }
+                self.pos += 1;
+                continue;
+            }
+
+            match char {
+                '\\' => is_ignore_next = true,
+                '\'' if false == is_double_quote => is_single_quote = true,
+                '"' => is_double_quote = !is_double_quote,
+                '$' if self.peek_at(1) == Some('(') => {
+                    dollar_paren_depth += 1;
+                    self.pos += 2;
+                    continue;
+                }
+                '(' if dollar_paren_depth > 0 && false == is_double_quote => dollar_paren_depth += 1,
+                ')' if dollar_paren_depth > 0 && false == is_double_quote => dollar_paren_depth -= 1,
+                _ if is_double_quote || dollar_paren_depth > 0 => {}
+                ';' | '\n' => break,
+                '&' if self.peek_at(1) == Some('&') => break,
+                '|' => break,
+                ')' if terminators.contains(&")") => break,
+                _ => {}
+            }
+
+            self.pos += 1;
+        }
+
+        Ok(self.chars[start..self.pos].iter().collect::<String>().trim().to_string())
+    }
+
+    // `) > file` 혹은 `} 2>> file` 처럼 group 뒤에 붙은 redirection 파싱
+    fn parse_group_redirections(&mut self) -> Result<Vec<GroupRedirection>, String> {
+        let mut redirections = vec![];
+
+        loop {
+            self.skip_whitespace();
+
+            let mut redirect = String::new();
+            if let Some(fd @ ('1' | '2')) = self.peek() && self.peek_at(1) == Some('>') {
+                redirect.push(fd);
+                self.pos += 1;
+            } else if self.peek() == Some('0') && self.peek_at(1) == Some('<') {
+                redirect.push('0');
+                self.pos += 1;
+            }
+
+            // <<< word 는 here string, << 뒤는 inline_here_documents 에서 쿼터로 묶은 here document 본문
+            if self.peek() == Some('<') && self.peek_at(1) == Some('<') && self.peek_at(2) == Some('<') {
+                redirect.push_str("<<<");
+                self.pos += 3;
+            } else if self.peek() == Some('<') && self.peek_at(1) == Some('<') {
+                redirect.push_str("<<");
+                self.pos += 2;
+            } else if self.peek() == Some('<') {
+                redirect.push('<');
+                self.pos += 1;
+            } else if self.peek() == Some('>') && false == redirect.starts_with('0') {
+                redirect.push('>');
+                self.pos += 1;
+                if self.peek() == Some('>') {
+                    redirect.push('>');
+                    self.pos += 1;
+                } else if self.peek() == Some('&') {
+                    // N>&M, >&M : fd 복제
+                    redirect.push('&');
+                    self.pos += 1;
+                }
+            } else {
+                self.pos -= redirect.len();
+                break;
+            }
+
+            self.skip_whitespace();
+            let start = self.pos;
+            let mut is_single_quote = false;
+            let mut is_double_quote = false;
+            while let Some(char) = self.peek() {
+                if char == '\\' && false == is_single_quote {
+                    self.pos = (self.pos + 2).min(self.chars.len());
+                    continue;
+                }
+                if false == is_single_quote && false == is_double_quote
+                    && (char == ' ' || char == '\t' || char == '\n' || char == ';' || char == '&' || char == '|' || char == ')' || char == '>' || char == '<') {
+                    break;
+                }
+                if char == '\'' && false == is_double_quote {
+                    is_single_quote = !is_single_quote;
+                } else if char == '"' && false == is_single_quote {
+                    is_double_quote = !is_double_quote;
+                }
+                self.pos += 1;
+            }
+
+            let output_raw: String = self.chars[start..self.pos].iter().collect();
+            let output = crate::special_char_args_builder(&output_raw).join(" ");
+            // here string, here document 는 쿼터로 묶인 빈 문자열도 내용
+            if output_raw.is_empty() || (output.is_empty() && false == redirect.ends_with("<<")) {
+                return Err("syntax error near unexpected token `newline'".to_string());
+            }
+            if redirect.ends_with('&') && false == output.chars().all(|char| char.is_ascii_digit()) {
+                return Err(format!("{}: ambiguous redirect", output));
+            }
+
+            redirections.push(GroupRedirection { redirect, output });
+        }
+
+        Ok(redirections)
+    }
+}
@@ -0,0 +1,219 @@
+// simple command 를 실행하기 전에 $ 로 시작하는 확장을 처리한다
+//   $((expr)) : 산술 확장
+//   ${NAME}, $NAME, $? : 변수 확장
+//   $1, ${10}, $#, $@, $* : 함수의 positional parameter
+//   ${NAME[N]}, ${NAME[@]}, ${#NAME}, ${#NAME[@]} : 배열 변수 확장
+//   단어 처음의 ~ : HOME, ~+ : PWD, ~- : OLDPWD, ~N ~+N ~-N : directory stack 의 N 번째 (쿼터 밖에서만)
+// single quotes 안쪽은 확장하지 않고, 확장된 값은 special_char_args_builder 에서 다시 쿼터로 해석되지 않게 escape 한다
+
+use crate::shell_parser::arithmetic::evaluate_arithmetic;
+use crate::shell_state::directory_stack::get_directory_stack_entry;
+use crate::shell_state::variables::{get_array_variable, get_variable, is_valid_variable_name};
+
+pub fn expand_command_line(input: &str) -> Result<String, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut result = String::with_capacity(input.len());
+    let mut is_single_quote = false;
+    let mut is_double_quote = false;
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let char = chars[idx];
+
+        if is_single_quote {
+            if char == '\'' {
+                is_single_quote = false;
+            }
+            result.push(char);
+            idx += 1;
+            continue;
+        }
+
+        match char {
+            // escape 된 문자는 그대로 두고 special_char_args_builder 에서 처리
+            '\\' => {
+                result.push(char);
+                if let Some(next_char) = chars.get(idx + 1) {
+                    result.push(*next_char);
+                }
+                idx += 2;
+                continue;
+            }
+            '\'' if false == is_double_quote => is_single_quote = true,
+            '"' => is_double_quote = !is_double_quote,
+            '$' => {
+                if let Some((value, next_idx)) = expand_dollar(&chars, idx)? {
+                    result.push_str(&escape_expanded_value(&value, is_double_quote));
+                    idx = next_idx;
+                    continue;
+                }
+            }
+            '~' if false == is_double_quote && (idx == 0 || chars[idx - 1].is_whitespace()) => {
+                if let Some((value, next_idx)) = expand_tilde(&chars, idx) {
+                    result.push_str(&escape_expanded_value(&value, false));
+                    idx = next_idx;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        result.push(char);
+        idx += 1;
+    }
+
+    Ok(result)
+}
+
+// chars[idx] 가 '~' 일때 확장된 값과 다음 인덱스, ~user 처럼 확장 대상이 아니거나 값이 없으면 None
+fn expand_tilde(chars: &[char], idx: usize) -> Option<(String, usize)> {
+    let mut end_idx = idx + 1;
+    while let Some(char) = chars.get(end_idx) {
+        if char.is_whitespace() || matches!(char, '/' | ';' | '|' | '&' | '<' | '>' | '(' | ')') {
+            break;
+        }
+        end_idx += 1;
+    }
+
+    let prefix: String = chars[idx + 1..end_idx].iter().collect();
+    let value = match prefix.as_str() {
+        "" => get_variable("HOME"),
+        "+" => get_directory_stack_entry("0"),
+        "-" => get_variable("OLDPWD"),
+        _ => get_directory_stack_entry(&prefix),
+    }?;
+
+    Some((value, end_idx))
+}
+
+// chars[idx] 가 '$' 일때 확장된 값과 다음 인덱스를 반환, 확장 대상이 아니면 None
+pub fn expand_dollar(chars: &[char], idx: usize) -> Result<Option<(String, usize)>, String> {
+    let next_char = chars.get(idx + 1).copied();
+
+    // $((expr))
+    if next_char == Some('(') && chars.get(idx + 2) == Some(&'(') {
+        let Some(end_idx) = find_arithmetic_end(chars, idx + 3) else {
+            return Err("unexpected EOF while looking for matching `))'".to_string());
+        };
+        let expression: String = chars[idx + 3..end_idx].iter().collect();
+        let value = evaluate_arithmetic_expression(&expression)?;
+        return Ok(Some((value.to_string(), end_idx + 2)));
+    }
+
+    // ${NAME}
+    if next_char == Some('{') {
+        let Some(close_offset) = chars[idx + 2..].iter().position(|c| *c == '}') else {
+            return Err("bad substitution".to_string());
+        };
+        let name: String = chars[idx + 2..idx + 2 + close_offset].iter().collect();
+        let value = expand_braced_variable(&name)?;
+        return Ok(Some((value, idx + 3 + close_offset)));
+    }
+
+    // $?, $#, $@, $*, $0 ~ $9
+    if let Some(special_char @ ('?' | '#' | '@' | '*' | '0'..='9')) = next_char {
+        let value = get_variable(&special_char.to_string()).unwrap_or_default();
+        return Ok(Some((value, idx + 2)));
+    }
+
+    // $NAME
+    let mut end_idx = idx + 1;
+    while let Some(char) = chars.get(end_idx) {
+        if false == (char.is_ascii_alphanumeric() || *char == '_') {
+            break;
+        }
+        end_idx += 1;
+    }
+    if end_idx == idx + 1 {
+        return Ok(None);
+    }
+
+    let name: String = chars[idx + 1..end_idx].iter().collect();
+    let value = get_variable(&name).unwrap_or_default();
+    Ok(Some((value, end_idx)))
+}
+
+// ${ } 안쪽 처리
+fn expand_braced_variable(name: &str) -> Result<String, String> {
+    let bad_substitution = || format!("${{{}}}: bad substitution", name);
+
+    // ${#NAME}, ${#NAME[@]} 는 길이
+    let (is_length, name) = match name.strip_prefix('#') {
+        Some(rest) if false == rest.is_empty() => (true, rest),
+        _ => (false, name),
+    };
+
+    let (variable_name, subscript) = match name.split_once('[') {
+        Some((variable_name, rest)) => {
+            let Some(subscript) = rest.strip_suffix(']') else {
+                return Err(bad_substitution());
+            };
+            (variable_name, Some(subscript))
+        }
+        None => (name, None),
+    };
+
+    let is_special_variable = matches!(variable_name, "?" | "#" | "@" | "*") || variable_name.parse::<usize>().is_ok();
+    if false == is_valid_variable_name(variable_name) && false == is_special_variable {
+        return Err(bad_substitution());
+    }
+
+    let value = match subscript {
+        None => {
+            let value = get_variable(variable_name).unwrap_or_default();
+            if is_length { value.chars().count().to_string() } else { value }
+        }
+        Some("@") | Some("*") => {
+            let values = get_array_variable(variable_name).unwrap_or_default();
+            if is_length { values.len().to_string() } else { values.join(" ") }
+        }
+        Some(subscript) => {
+            let index = evaluate_arithmetic_expression(subscript)?;
+            let values = get_array_variable(variable_name).unwrap_or_default();
+            // 음수 인덱스는 뒤에서부터
+            let index = if index < 0 { values.len() as i64 + index } else { index };
+            let value = usize::try_from(index).ok().and_then(|index| values.get(index).cloned()).unwrap_or_default();
+            if is_length { value.chars().count().to_string() } else { value }
+        }
+    };
+
+    Ok(value)
+}
+
+// `$((` 다음부터 짝이 맞는 `))` 의 시작 인덱스
+pub fn find_arithmetic_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut idx = start;
+
+    while idx < chars.len() {
+        match chars[idx] {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            ')' if chars.get(idx + 1) == Some(&')') => return Some(idx),
+            ')' => return None,
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    None
+}
+
+// 산술식 안의 $NAME 등을 먼저 확장한 뒤 평가
+pub fn evaluate_arithmetic_expression(expression: &str) -> Result<i64, String> {
+    let expression = expand_command_line(expression)?;
+    evaluate_arithmetic(&expression)
+}
+
+// 더블 쿼터 안에서는 \ 와 " 만 escape 가 필요 (' 앞의 백슬래쉬는 그대로 남음)
+// 쿼터 밖에서는 확장된 값의 > < & | 가 redirection 이나 operator 로 다시 파싱되지 않도록 escape
+fn escape_expanded_value(value: &str, is_double_quote: bool) -> String {
+    let mut result = String::with_capacity(value.len());
+    for char in value.chars() {
+        if char == '\\' || char == '"' || (false == is_double_quote && matches!(char, '\'' | '>' | '<' | '&' | '|')) {
+            result.push('\\');
+        }
+        result.push(char);
+    }
+    result
+}
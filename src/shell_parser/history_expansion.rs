@@ -0,0 +1,389 @@
+// csh/bash 스타일 history expansion, 입력 line 을 파싱하기 전에 적용한다
+//   event    : !! (직전), !n, !-n, !prefix, !?string?, ^old^new^ (직전 command 치환)
+//   word     : !$ !^ !* 혹은 :0 :n :^ :$ :* :x-y :x*
+//   modifier : :h :t :r :e :s/old/new/ :gs/old/new/ :& :p
+// single quotes 안쪽과 \! 는 확장하지 않는다
+
+use crate::shell_state::history::get_history_entries;
+
+pub struct HistoryExpansionResult {
+    pub line: String,
+    // 확장이 일어났으면 실행 전에 확장된 line 을 출력
+    pub is_expanded: bool,
+    // :p modifier 가 있으면 출력만 하고 실행하지 않음
+    pub is_print_only: bool,
+}
+
+pub fn expand_history(line: &str) -> Result<HistoryExpansionResult, String> {
+    let history_lines: Vec<String> = get_history_entries().into_iter().map(|entry| entry.line).collect();
+    let mut history_expander = HistoryExpander {
+        history_lines,
+        last_substitution: None,
+        is_print_only: false,
+    };
+
+    // ^old^new^ 는 !!:s/old/new/ 와 같음
+    if let Some(quick_substitution) = line.strip_prefix('^') {
+        let line = history_expander.expand_quick_substitution(quick_substitution)?;
+        return Ok(HistoryExpansionResult {
+            line,
+            is_expanded: true,
+            is_print_only: false,
+        });
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    let mut result = String::with_capacity(line.len());
+    let mut is_single_quote = false;
+    let mut is_double_quote = false;
+    let mut is_expanded = false;
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let char = chars[idx];
+
+        if is_single_quote {
+            if char == '\'' {
+                is_single_quote = false;
+            }
+            result.push(char);
+            idx += 1;
+            continue;
+        }
+
+        match char {
+            '\\' if chars.get(idx + 1) == Some(&'!') => {
+                result.push_str("\\!");
+                idx += 2;
+                continue;
+            }
+            '\'' if false == is_double_quote => is_single_quote = true,
+            '"' => is_double_quote = !is_double_quote,
+            '!' => {
+                if let Some((expanded, next_idx)) = history_expander.expand_event(&chars, idx)? {
+                    result.push_str(&expanded);
+                    is_expanded = true;
+                    idx = next_idx;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        result.push(char);
+        idx += 1;
+    }
+
+    Ok(HistoryExpansionResult {
+        line: result,
+        is_expanded,
+        is_print_only: history_expander.is_print_only,
+    })
+}
+
+// history line 을 쿼터를 고려해서 단어로 분리 (쿼터는 그대로 유지)
+fn split_history_words(line: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut quote: Option<char> = None;
+    let mut is_ignore_next = false;
+
+    for char in line.chars() {
+        if is_ignore_next {
+            word.push(char);
+            is_ignore_next = false;
+            continue;
+        }
+
+        match quote {
+            Some(quote_char) => {
+                if char == quote_char {
+                    quote = None;
+                }
+                word.push(char);
+            }
+            None => {
+                if char == '\\' {
+                    is_ignore_next = true;
+                    word.push(char);
+                } else if char == '\'' || char == '"' {
+                    quote = Some(char);
+                    word.push(char);
+                } else if char.is_whitespace() {
+                    if false == word.is_empty() {
+                        words.push(word);
+                        word = String::new();
+                    }
+                } else {
+                    word.push(char);
+                }
+            }
+        }
+    }
+
+    if false == word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+struct HistoryExpander {
+    history_lines: Vec<String>,
+    // :& 로 반복할 마지막 치환 (old, new)
+    last_substitution: Option<(String, String)>,
+    is_print_only: bool,
+}
+
+impl HistoryExpander {
+    fn previous_line(&self, event: &str) -> Result<String, String> {
+        self.history_lines.last().cloned().ok_or_else(|| format!("{}: event not found", event))
+    }
+
+    fn expand_quick_substitution(&mut self, quick_substitution: &str) -> Result<String, String> {
+        let mut parts = quick_substitution.splitn(3, '^');
+        let old = parts.next().unwrap_or_default();
+        let new = parts.next().unwrap_or_default();
+        let rest = parts.next().unwrap_or_default();
+
+        let previous_line = self.previous_line("^")?;
+        if old.is_empty() || false == previous_line.contains(old) {
+            return Err(format!("^{}^{}: substitution failed", old, new));
+        }
+
+        self.last_substitution = Some((old.to_string(), new.to_string()));
+        Ok(format!("{}{}", previous_line.replacen(old, new, 1), rest))
+    }
+
+    // chars[idx] 가 '!' 일때 확장된 문자열과 다음 인덱스, 확장 대상이 아니면 None
+    fn expand_event(&mut self, chars: &[char], idx: usize) -> Result<Option<(String, usize)>, String> {
+        let Some(&next_char) = chars.get(idx + 1) else {
+            return Ok(None);
+        };
+
+        // ! 뒤에 공백, =, ( 가 오면 그냥 문자
+        if next_char.is_whitespace() || next_char == '=' || next_char == '(' || next_char == '"' {
+            return Ok(None);
+        }
+
+        let mut pos = idx + 1;
+        let event_line;
+        // !$ !^ !* 처럼 바로 word designator 가 붙는 경우
+        let mut word_designator: Option<String> = None;
+
+        match next_char {
+            '!' => {
+                event_line = self.previous_line("!!")?;
+                pos += 1;
+            }
+            '$' | '^' | '*' => {
+                event_line = self.previous_line(&format!("!{}", next_char))?;
+                word_designator = Some(next_char.to_string());
+                pos += 1;
+            }
+            '0'..='9' | '-' => {
+                let start = pos;
+                if next_char == '-' {
+                    pos += 1;
+                }
+                while pos < chars.len() && chars[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+                let event: String = chars[start..pos].iter().collect();
+                let Ok(number) = event.parse::<i64>() else {
+                    return Ok(None);
+                };
+
+                let history_len = self.history_lines.len() as i64;
+                let index = if number < 0 { history_len + number } else { number - 1 };
+                event_line = usize::try_from(index).ok()
+                    .and_then(|index| self.history_lines.get(index).cloned())
+                    .ok_or_else(|| format!("!{}: event not found", event))?;
+            }
+            '?' => {
+                let start = pos + 1;
+                pos = start;
+                while pos < chars.len() && chars[pos] != '?' && chars[pos] != '\n' {
+                    pos += 1;
+                }
+                let search: String = chars[start..pos].iter().collect();
+                // 닫는 ? 는 생략 가능
+                if pos < chars.len() && chars[pos] == '?' {
+                    pos += 1;
+                }
+                event_line = self.history_lines.iter().rev()
+                    .find(|line| line.contains(&search))
+                    .cloned()
+                    .ok_or_else(|| format!("!?{}: event not found", search))?;
+            }
+            _ => {
+                let start = pos;
+                while pos < chars.len() && false == chars[pos].is_whitespace() && chars[pos] != ':' && chars[pos] != ';' && chars[pos] != '&' && chars[pos] != '|' && chars[pos] != '"' && chars[pos] != '\'' {
+                    pos += 1;
+                }
+                let prefix: String = chars[start..pos].iter().collect();
+                event_line = self.history_lines.iter().rev()
+                    .find(|line| line.starts_with(&prefix))
+                    .cloned()
+                    .ok_or_else(|| format!("!{}: event not found", prefix))?;
+            }
+        }
+
+        // :n, :^, :$, :*, :x-y word designator
+        if word_designator.is_none() && pos + 1 < chars.len() && chars[pos] == ':'
+            && (chars[pos + 1].is_ascii_digit() || matches!(chars[pos + 1], '^' | '$' | '*' | '-')) {
+            let start = pos + 1;
+            pos = start;
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || matches!(chars[pos], '^' | '$' | '*' | '-')) {
+                pos += 1;
+            }
+            word_designator = Some(chars[start..pos].iter().collect());
+        }
+
+        let mut expanded = match &word_designator {
+            Some(word_designator) => select_words(&event_line, word_designator)?,
+            None => event_line,
+        };
+
+        // modifier
+        while pos + 1 < chars.len() && chars[pos] == ':' {
+            let (modified, next_pos) = self.apply_modifier(&expanded, chars, pos + 1)?;
+            expanded = modified;
+            pos = next_pos;
+        }
+
+        Ok(Some((expanded, pos)))
+    }
+
+    fn apply_modifier(&mut self, value: &str, chars: &[char], pos: usize) -> Result<(String, usize), String> {
+        let modifier = chars[pos];
+
+        let result = match modifier {
+            // 마지막 path 요소 제거
+            'h' => match value.rfind('/') {
+                Some(0) => "/".to_string(),
+                Some(slash_idx) => value[..slash_idx].to_string(),
+                None => value.to_string(),
+            },
+            // 마지막 path 요소만
+            't' => match value.rfind('/') {
+                Some(slash_idx) => value[slash_idx + 1..].to_string(),
+                None => value.to_string(),
+            },
+            // 확장자 제거
+            'r' => match value.rfind('.') {
+                Some(dot_idx) if false == value[dot_idx..].contains('/') => value[..dot_idx].to_string(),
+                _ => value.to_string(),
+            },
+            // 확장자만
+            'e' => match value.rfind('.') {
+                Some(dot_idx) if false == value[dot_idx..].contains('/') => value[dot_idx..].to_string(),
+                _ => String::new(),
+            },
+            'p' => {
+                self.is_print_only = true;
+                value.to_string()
+            }
+            's' | 'g' | '&' => return self.apply_substitution(value, chars, pos),
+            _ => return Err(format!("{}: unrecognized history modifier", modifier)),
+        };
+
+        Ok((result, pos + 1))
+    }
+
+    // s/old/new/, gs/old/new/, &, g&
+    fn apply_substitution(&mut self, value: &str, chars: &[char], pos: usize) -> Result<(String, usize), String> {
+        let mut pos = pos;
+        let is_global = chars[pos] == 'g';
+        if is_global {
+            pos += 1;
+        }
+
+        let (old, new) = match chars.get(pos) {
+            Some('&') => {
+                pos += 1;
+                self.last_substitution.clone().ok_or_else(|| "&: no previous substitution".to_string())?
+            }
+            Some('s') => {
+                let Some(&delimiter) = chars.get(pos + 1) else {
+                    return Err("s: no previous substitution".to_string());
+                };
+                pos += 2;
+
+                let mut parts = vec![String::new(), String::new()];
+                for part in parts.iter_mut() {
+                    while pos < chars.len() && chars[pos] != delimiter {
+                        // \delimiter 는 문자 그대로
+                        if chars[pos] == '\\' && chars.get(pos + 1) == Some(&delimiter) {
+                            pos += 1;
+                        }
+                        part.push(chars[pos]);
+                        pos += 1;
+                    }
+                    // 마지막 구분자는 생략 가능
+                    if pos < chars.len() {
+                        pos += 1;
+                    }
+                }
+
+                let new = parts.pop().unwrap_or_default();
+                let mut old = parts.pop().unwrap_or_default();
+                // old 가 비어있으면 이전 치환의 old 재사용
+                if old.is_empty() {
+                    old = self.last_substitution.as_ref().map(|(old, _)| old.to_owned()).unwrap_or_default();
+                }
+                // new 안의 & 는 old 로 치환
+                let new = new.replace('&', &old);
+                (old, new)
+            }
+            _ => return Err(format!("{}: unrecognized history modifier", chars[pos - 1])),
+        };
+
+        if old.is_empty() || false == value.contains(&old) {
+            return Err(format!(":s/{}/{}/: substitution failed", old, new));
+        }
+
+        self.last_substitution = Some((old.to_owned(), new.to_owned()));
+        let result = if is_global { value.replace(&old, &new) } else { value.replacen(&old, &new, 1) };
+        Ok((result, pos))
+    }
+}
+
+// word designator 에 해당하는 단어들을 공백으로 합쳐서 반환
+fn select_words(line: &str, word_designator: &str) -> Result<String, String> {
+    let words = split_history_words(line);
+    let last_idx = words.len().saturating_sub(1);
+    let bad_word_specifier = || format!("{}: bad word specifier", word_designator);
+
+    let parse_word_idx = |word: &str| -> Result<usize, String> {
+        match word {
+            "^" => Ok(1),
+            "$" => Ok(last_idx),
+            _ => word.parse::<usize>().map_err(|_| bad_word_specifier()),
+        }
+    };
+
+    let (start, end) = if word_designator == "*" {
+        // 인자가 없으면 빈 문자열
+        if words.len() <= 1 {
+            return Ok(String::new());
+        }
+        (1, last_idx)
+    } else if let Some(start) = word_designator.strip_suffix('*') {
+        (parse_word_idx(start)?, last_idx)
+    } else if let Some((start, end)) = word_designator.split_once('-') {
+        let start = if start.is_empty() { 0 } else { parse_word_idx(start)? };
+        // x- 는 마지막 단어 제외
+        let end = if end.is_empty() { last_idx.saturating_sub(1) } else { parse_word_idx(end)? };
+        (start, end)
+    } else {
+        let idx = parse_word_idx(word_designator)?;
+        (idx, idx)
+    };
+
+    if start > end || end >= words.len() {
+        return Err(bad_word_specifier());
+    }
+
+    Ok(words[start..=end].join(" "))
+}
@@ -0,0 +1,508 @@
+// 입력이 끝나지 않아서 다음 줄을 더 읽어야 하는지 검사한다.
+//   닫히지 않은 ' " ` $( ( (( 와 줄 끝의 | && || \
+//   짝이 맞는 } 가 없는 {, 짝이 맞는 done 이 없는 while, until, for
+//   짝이 맞는 fi 가 없는 if, 짝이 맞는 esac 가 없는 case
+//   delimiter 줄이 아직 나오지 않은 <<DELIM here document
+// 짝이 맞지 않는 닫는 쪽(} ) fi 등)은 무시하고 command_list 파싱에서 에러로 처리한다.
+
+use crate::shell_parser::command_list::scan_here_document_delimiter;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputCompleteness {
+    Complete,
+    Incomplete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Block {
+    SingleQuote,
+    DoubleQuote,
+    Backtick,
+    // ( ) 와 $( )
+    Paren,
+    // (( )) 와 $(( ))
+    Arithmetic,
+    Brace,
+    // while, until, for 부터 done 까지
+    Loop,
+    // if 부터 fi 까지
+    If,
+    // case 의 pattern 자리 (pattern 은 예약어가 아님)
+    Case,
+    // case 의 `pattern)` 뒤 본문, ;; 에서 다시 pattern 자리
+    CaseItem,
+}
+
+pub fn check_input_completeness(input: &str) -> InputCompleteness {
+    CompletenessScanner::new(input).scan()
+}
+
+// 쿼터 밖 (' ' 안은 제외) 의 줄 끝 \ 를 개행과 함께 지워서 이어지는 줄을 붙임
+pub fn remove_line_continuations(input: &str) -> String {
+    let mut scanner = CompletenessScanner::new(input);
+    scanner.scan();
+
+    let mut result = String::with_capacity(input.len());
+    let mut idx = 0;
+    while idx < scanner.chars.len() {
+        if scanner.line_continuations.contains(&idx) {
+            idx += 2;
+            continue;
+        }
+        result.push(scanner.chars[idx]);
+        idx += 1;
+    }
+
+    result
+}
+
+struct CompletenessScanner {
+    chars: Vec<char>,
+    idx: usize,
+    blocks: Vec<Block>,
+    // 예약어 확인을 위한 쿼터 밖의 현재 단어
+    word: String,
+    is_word_quoted: bool,
+    is_command_position: bool,
+    // | && || 뒤에 아직 command 가 없음
+    is_operator_pending: bool,
+    // function 키워드 다음의 이름 자리
+    is_function_name: bool,
+    // 현재 줄이 끝나면 본문을 읽을 here document 의 (delimiter, <<- 인지)
+    here_documents: Vec<(String, bool)>,
+    // 줄 끝 \ 의 인덱스
+    line_continuations: Vec<usize>,
+}
+
+impl CompletenessScanner {
+    fn new(input: &str) -> Self {
+        CompletenessScanner {
+            chars: input.chars().collect(),
+            idx: 0,
+            blocks: vec![],
+            word: String::new(),
+            is_word_quoted: false,
+            is_command_position: true,
+            is_operator_pending: false,
+            is_function_name: false,
+            here_documents: vec![],
+            line_continuations: vec![],
+        }
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.idx + offset).copied()
+    }
+
+    fn scan(&mut self) -> InputCompleteness {
+        while self.idx < self.chars.len() {
+            let char = self.chars[self.idx];
+            let top = self.blocks.last().copied();
+
+            if top == Some(Block::SingleQuote) {
+                if char == '\'' {
+                    self.blocks.pop();
+                }
+                self.idx += 1;
+                continue;
+            }
+
+            if char == '\\' {
+                let Some(next_char) = self.peek_at(1) else {
+                    return InputCompleteness::Incomplete;
+                };
+                if next_char == '\n' {
+                    self.line_continuations.push(self.idx);
+                } else if top != Some(Block::DoubleQuote) {
+                    self.word.push(next_char);
+                    self.is_word_quoted = true;
+                }
+                self.idx += 2;
+                continue;
+            }
+
+            if char == '$' && self.peek_at(1) == Some('(') {
+                // $(...) 는 단어의 일부, 안쪽은 새로운 command
+                self.is_word_quoted = true;
+                self.finish_word();
+                if self.peek_at(2) == Some('(') {
+                    self.blocks.push(Block::Arithmetic);
+                    self.idx += 3;
+                } else {
+                    self.blocks.push(Block::Paren);
+                    self.is_command_position = true;
+                    self.idx += 2;
+                }
+                continue;
+            }
+
+            if top == Some(Block::DoubleQuote) {
+                match char {
+                    '"' => {
+                        self.blocks.pop();
+                    }
+                    '`' => self.blocks.push(Block::Backtick),
+                    _ => {}
+                }
+                self.idx += 1;
+                continue;
+            }
+
+            match char {
+                '\'' => {
+                    self.blocks.push(Block::SingleQuote);
+                    self.is_word_quoted = true;
+                }
+                '"' => {
+                    self.blocks.push(Block::DoubleQuote);
+                    self.is_word_quoted = true;
+                }
+                '`' => {
+                    self.finish_word();
+                    if top == Some(Block::Backtick) {
+                        self.blocks.pop();
+                        self.is_command_position = false;
+                    } else {
+                        self.blocks.push(Block::Backtick);
+                        self.is_command_position = true;
+                    }
+                }
+                '(' => {
+                    self.finish_word();
+                    // case 의 (pattern) 앞 괄호
+                    if self.blocks.last() == Some(&Block::Case) {
+                        self.idx += 1;
+                        continue;
+                    }
+                    if self.peek_at(1) == Some('(') {
+                        self.blocks.push(Block::Arithmetic);
+                        self.idx += 2;
+                        continue;
+                    }
+                    // name() 는 함수 정의, 뒤에 { 가 예약어로 옴
+                    if let Some(close_offset) = self.chars[self.idx + 1..].iter().position(|c| *c != ' ' && *c != '\t')
+                        && self.chars[self.idx + 1 + close_offset] == ')' {
+                        self.is_command_position = true;
+                        self.idx += close_offset + 2;
+                        continue;
+                    }
+                    self.blocks.push(Block::Paren);
+                    self.is_command_position = true;
+                }
+                ')' => {
+                    // `done)` 처럼 finish_word 에서 block 이 닫힐 수 있으므로 다시 확인
+                    self.finish_word();
+                    match self.blocks.last().copied() {
+                        Some(Block::Paren) => {
+                            self.blocks.pop();
+                            self.is_command_position = false;
+                        }
+                        Some(Block::Arithmetic) if self.peek_at(1) == Some(')') => {
+                            self.blocks.pop();
+                            self.is_command_position = false;
+                            self.idx += 1;
+                        }
+                        // pattern) 뒤는 본문
+                        Some(Block::Case) => {
+                            self.blocks.pop();
+                            self.blocks.push(Block::CaseItem);
+                            self.is_command_position = true;
+                        }
+                        _ => {}
+                    }
+                }
+                ' ' | '\t' => self.finish_word(),
+                '\n' => {
+                    self.finish_word();
+                    self.is_command_position = true;
+                    if false == self.skip_here_document_bodies() {
+                        return InputCompleteness::Incomplete;
+                    }
+                    continue;
+                }
+                ';' => {
+                    self.finish_word();
+                    self.is_command_position = true;
+                    self.is_operator_pending = false;
+                    // ;; 는 case 본문의 끝
+                    if self.peek_at(1) == Some(';') && top == Some(Block::CaseItem) {
+                        self.blocks.pop();
+                        self.blocks.push(Block::Case);
+                        self.idx += 1;
+                    }
+                }
+                '&' | '|' => {
+                    self.finish_word();
+                    if self.peek_at(1) == Some(char) {
+                        self.idx += 1;
+                        self.is_operator_pending = true;
+                    } else {
+                        // 단독 & 는 background 라서 뒤에 command 가 없어도 됨
+                        self.is_operator_pending = char == '|';
+                    }
+                    self.is_command_position = true;
+                }
+                // (( a << 2 )) 의 << 는 shift 연산자
+                '<' if self.peek_at(1) == Some('<') && top != Some(Block::Arithmetic) => {
+                    self.finish_word();
+                    // <<< 는 here string
+                    if self.peek_at(2) == Some('<') {
+                        self.idx += 3;
+                        continue;
+                    }
+                    let (here_document, next_idx) = scan_here_document_delimiter(&self.chars, self.idx + 2);
+                    self.here_documents.push((here_document.delimiter, here_document.is_strip_tabs));
+                    self.idx = next_idx;
+                    continue;
+                }
+                '<' | '>' => {
+                    self.finish_word();
+                    // 2>&1 의 & 는 background 가 아니라 fd 복제
+                    if self.peek_at(1) == Some('&') {
+                        self.idx += 1;
+                    }
+                }
+                '#' if self.word.is_empty() && false == self.is_word_quoted => {
+                    // 주석은 줄 끝까지
+                    while self.idx < self.chars.len() && self.chars[self.idx] != '\n' {
+                        self.idx += 1;
+                    }
+                    continue;
+                }
+                _ => self.word.push(char),
+            }
+
+            self.idx += 1;
+        }
+
+        self.finish_word();
+
+        if false == self.blocks.is_empty() || self.is_operator_pending || false == self.here_documents.is_empty() {
+            return InputCompleteness::Incomplete;
+        }
+        InputCompleteness::Complete
+    }
+
+    // 개행 다음부터 here document 본문들을 delimiter 줄까지 건너뜀, delimiter 줄 전에 입력이 끝나면 false
+    fn skip_here_document_bodies(&mut self) -> bool {
+        self.idx += 1;
+
+        while let Some((delimiter, is_strip_tabs)) = self.here_documents.first().cloned() {
+            if self.idx >= self.chars.len() {
+                return false;
+            }
+
+            let line_end = self.chars[self.idx..].iter().position(|char| *char == '\n').map_or(self.chars.len(), |offset| self.idx + offset);
+            let line: String = self.chars[self.idx..line_end].iter().collect();
+            let line = if is_strip_tabs { line.trim_start_matches('\t') } else { line.as_str() };
+            if line == delimiter {
+                self.here_documents.remove(0);
+            }
+            self.idx = line_end + 1;
+        }
+
+        true
+    }
+
+    // 단어가 끝났을 때 command 자리의 예약어면 block 을 열고 닫음
+    fn finish_word(&mut self) {
+        if self.word.is_empty() && false == self.is_word_quoted {
+            return;
+        }
+        let word = std::mem::take(&mut self.word);
+        let is_word_quoted = std::mem::take(&mut self.is_word_quoted);
+        self.is_operator_pending = false;
+
+        if self.is_function_name {
+            self.is_function_name = false;
+            self.is_command_position = true;
+            return;
+        }
+        if false == self.is_command_position || is_word_quoted {
+            self.is_command_position = false;
+            return;
+        }
+
+        let top = self.blocks.last().copied();
+        // case 의 pattern 자리에서는 esac 만 예약어
+        if top == Some(Block::Case) {
+            if word == "esac" {
+                self.blocks.pop();
+            }
+            self.is_command_position = false;
+            return;
+        }
+
+        self.is_command_position = match word.as_str() {
+            "{" => {
+                self.blocks.push(Block::Brace);
+                true
+            }
+            "}" => {
+                if top == Some(Block::Brace) {
+                    self.blocks.pop();
+                }
+                false
+            }
+            "while" | "until" => {
+                self.blocks.push(Block::Loop);
+                true
+            }
+            // for 뒤는 변수 이름과 in
+            "for" => {
+                self.blocks.push(Block::Loop);
+                false
+            }
+            "do" => true,
+            "done" => {
+                if top == Some(Block::Loop) {
+                    self.blocks.pop();
+                }
+                false
+            }
+            "if" => {
+                self.blocks.push(Block::If);
+                true
+            }
+            "then" | "elif" | "else" => true,
+            "fi" => {
+                if top == Some(Block::If) {
+                    self.blocks.pop();
+                }
+                false
+            }
+            // case 뒤는 단어와 in
+            "case" => {
+                self.blocks.push(Block::Case);
+                false
+            }
+            "esac" => {
+                if top == Some(Block::CaseItem) {
+                    self.blocks.pop();
+                }
+                false
+            }
+            "function" => {
+                self.is_function_name = true;
+                false
+            }
+            _ => false,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputCompleteness, check_input_completeness, remove_line_continuations};
+
+    fn is_complete(input: &str) -> bool {
+        check_input_completeness(input) == InputCompleteness::Complete
+    }
+
+    #[test]
+    fn unclosed_quotes() {
+        assert!(false == is_complete("echo 'abc"));
+        assert!(false == is_complete("echo \"abc"));
+        assert!(false == is_complete("echo `date"));
+        assert!(is_complete("echo 'a\"b'"));
+        assert!(is_complete("echo \"it's\""));
+        // 쿼터 안의 \ 와 escape 된 쿼터
+        assert!(is_complete("echo 'a\\'"));
+        assert!(false == is_complete("echo \"a\\\""));
+        assert!(is_complete("echo \\'"));
+    }
+
+    #[test]
+    fn trailing_backslash() {
+        assert!(false == is_complete("echo a \\"));
+        assert!(is_complete("echo a \\\nb"));
+        assert!(is_complete("echo a\\\\"));
+    }
+
+    #[test]
+    fn trailing_operators() {
+        assert!(false == is_complete("true &&"));
+        assert!(false == is_complete("false ||"));
+        assert!(false == is_complete("echo a |"));
+        assert!(is_complete("true &&\necho a"));
+        // 단독 & 는 background
+        assert!(is_complete("sleep 1 &"));
+        assert!(is_complete("echo a 2>&1"));
+    }
+
+    #[test]
+    fn unclosed_groups() {
+        assert!(false == is_complete("(echo a"));
+        assert!(false == is_complete("echo $(date"));
+        assert!(false == is_complete("(( 1 + 2"));
+        assert!(false == is_complete("{ echo a;"));
+        assert!(is_complete("{ echo a; }"));
+        assert!(is_complete("f() { echo a; }"));
+        assert!(false == is_complete("function f {"));
+        // { } 는 command 자리에서만 예약어
+        assert!(is_complete("echo {"));
+    }
+
+    #[test]
+    fn loops() {
+        assert!(false == is_complete("while true; do"));
+        assert!(false == is_complete("until false\ndo echo a"));
+        assert!(is_complete("while true; do echo a; done"));
+        assert!(false == is_complete("for i in 1 2; do echo $i"));
+        assert!(is_complete("for i in 1 2\ndo\necho $i\ndone"));
+        // for 의 단어 자리의 done 은 예약어가 아님
+        assert!(false == is_complete("for i in done; do echo"));
+        assert!(is_complete("echo while"));
+    }
+
+    #[test]
+    fn if_statements() {
+        assert!(false == is_complete("if true"));
+        assert!(false == is_complete("if true; then echo a"));
+        assert!(false == is_complete("if true; then echo a; else"));
+        assert!(is_complete("if true; then echo a; elif false; then echo b; else echo c; fi"));
+        assert!(is_complete("if true\nthen\n  if false; then echo a; fi\nfi"));
+        assert!(is_complete("echo if"));
+    }
+
+    #[test]
+    fn case_statements() {
+        assert!(false == is_complete("case $x in"));
+        assert!(false == is_complete("case $x in a) echo a;;"));
+        assert!(is_complete("case $x in a|b) echo a;; *) echo other;; esac"));
+        assert!(is_complete("case $x in\n(a) echo a\nesac"));
+        // pattern 자리의 예약어는 pattern
+        assert!(is_complete("case $x in if) echo if;; done) echo done;; esac"));
+    }
+
+    #[test]
+    fn here_documents() {
+        assert!(false == is_complete("cat <<EOF"));
+        assert!(false == is_complete("cat <<EOF\nbody"));
+        assert!(is_complete("cat <<EOF\nbody 'quote\nEOF"));
+        assert!(is_complete("cat <<'END'\n$x\nEND"));
+        assert!(is_complete("cat <<-EOF\n\tbody\n\tEOF"));
+        assert!(false == is_complete("cat <<A <<B\na\nA\nb"));
+        assert!(is_complete("cat <<A <<B\na\nA\nb\nB"));
+        // here string 과 shift 연산자는 here document 가 아님
+        assert!(is_complete("cat <<< word"));
+        assert!(is_complete("echo $(( 1 << 2 ))"));
+    }
+
+    #[test]
+    fn comments_are_ignored() {
+        assert!(is_complete("echo a # it's"));
+        assert!(is_complete("# while"));
+        assert!(false == is_complete("echo a#'b"));
+    }
+
+    #[test]
+    fn line_continuations_are_removed() {
+        assert_eq!(remove_line_continuations("echo a \\\nb"), "echo a b");
+        assert_eq!(remove_line_continuations("echo \"a\\\nb\""), "echo \"ab\"");
+        // '' 안과 here document 본문은 그대로
+        assert_eq!(remove_line_continuations("echo 'a\\\nb'"), "echo 'a\\\nb'");
+        assert_eq!(remove_line_continuations("cat <<E\na\\\nE"), "cat <<E\na\\\nE");
+        assert_eq!(remove_line_continuations("echo a\\\\\nb"), "echo a\\\\\nb");
+    }
+}
@@ -0,0 +1,6 @@
+pub mod arithmetic;
+pub mod command_list;
+pub mod expansion;
+pub mod history_expansion;
+pub mod input_completeness;
+pub mod pattern;
\ No newline at end of file
@@ -0,0 +1,120 @@
+// shell glob pattern 매칭
+//   *      : 0 개 이상의 아무 문자
+//   ?      : 1 개의 아무 문자
+//   [abc]  : 괄호 안의 문자 중 하나, [a-z] 범위, [!a] / [^a] 부정
+//   \x     : x 를 문자 그대로 매칭
+
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    glob_match_chars(&pattern, &text)
+}
+
+fn glob_match_chars(pattern: &[char], text: &[char]) -> bool {
+    let mut pattern_idx = 0;
+    let mut text_idx = 0;
+    // 마지막 * 위치와 그때의 text 위치 (backtracking 용)
+    let mut star_idx: Option<usize> = None;
+    let mut star_text_idx = 0;
+
+    while text_idx < text.len() {
+        if pattern_idx < pattern.len() {
+            match pattern[pattern_idx] {
+                '*' => {
+                    star_idx = Some(pattern_idx);
+                    star_text_idx = text_idx;
+                    pattern_idx += 1;
+                    continue;
+                }
+                '?' => {
+                    pattern_idx += 1;
+                    text_idx += 1;
+                    continue;
+                }
+                '[' => {
+                    if let Some((is_matched, next_pattern_idx)) = match_bracket(pattern, pattern_idx, text[text_idx])
+                        && is_matched {
+                        pattern_idx = next_pattern_idx;
+                        text_idx += 1;
+                        continue;
+                    }
+                    // 닫히지 않은 [ 는 문자 그대로 매칭
+                    if match_bracket(pattern, pattern_idx, text[text_idx]).is_none() && text[text_idx] == '[' {
+                        pattern_idx += 1;
+                        text_idx += 1;
+                        continue;
+                    }
+                }
+                '\\' if pattern_idx + 1 < pattern.len() => {
+                    if pattern[pattern_idx + 1] == text[text_idx] {
+                        pattern_idx += 2;
+                        text_idx += 1;
+                        continue;
+                    }
+                }
+                char => {
+                    if char == text[text_idx] {
+                        pattern_idx += 1;
+                        text_idx += 1;
+                        continue;
+                    }
+                }
+            }
+        }
+
+        // 매칭 실패 시 마지막 * 가 한 글자 더 먹도록 backtracking
+        let Some(last_star_idx) = star_idx else {
+            return false;
+        };
+        pattern_idx = last_star_idx + 1;
+        star_text_idx += 1;
+        text_idx = star_text_idx;
+    }
+
+    // 남은 pattern 이 전부 * 이면 매칭
+    pattern[pattern_idx..].iter().all(|c| *c == '*')
+}
+
+// [ ... ] 에 char 가 매칭되는지와 ] 다음 인덱스, 닫는 ] 가 없으면 None
+fn match_bracket(pattern: &[char], start: usize, char: char) -> Option<(bool, usize)> {
+    let mut idx = start + 1;
+    let mut is_negate = false;
+    if idx < pattern.len() && (pattern[idx] == '!' || pattern[idx] == '^') {
+        is_negate = true;
+        idx += 1;
+    }
+
+    let mut is_matched = false;
+    let mut is_first = true;
+    while idx < pattern.len() {
+        // 맨 앞의 ] 는 문자 그대로
+        if pattern[idx] == ']' && false == is_first {
+            return Some((is_matched != is_negate, idx + 1));
+        }
+        is_first = false;
+
+        let mut range_start = pattern[idx];
+        if range_start == '\\' && idx + 1 < pattern.len() {
+            idx += 1;
+            range_start = pattern[idx];
+        }
+
+        // a-z 범위
+        if idx + 2 < pattern.len() && pattern[idx + 1] == '-' && pattern[idx + 2] != ']' {
+            let range_end = pattern[idx + 2];
+            if range_start <= char && char <= range_end {
+                is_matched = true;
+            }
+            idx += 3;
+            continue;
+        }
+
+        if range_start == char {
+            is_matched = true;
+        }
+        idx += 1;
+    }
+
+    None
+}
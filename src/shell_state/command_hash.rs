@@ -0,0 +1,181 @@
+// PATH 의 실행 파일 cache 와 hash builtin 의 hash table
+// completion 과 command 실행이 같은 cache 를 사용한다
+//   PATH 값이 바뀌거나 PATH 디렉토리의 mtime 이 바뀌면 다시 scan (디렉토리 stat 만 하고 매번 read_dir 하지 않음)
+//   hash table 에는 실행했던 command 의 경로와 사용 횟수를 기억, PATH 가 바뀌면 비움
+
+use std::{collections::BTreeMap, env, ffi::OsString, fs, path::{Path, PathBuf}, sync::Mutex, time::SystemTime};
+
+use is_executable::IsExecutable;
+
+use crate::shell_state::variables::get_variable;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashedCommand {
+    pub name: String,
+    pub path: PathBuf,
+    pub hits: usize,
+}
+
+struct CommandCache {
+    // scan 했을 때의 PATH, None 이면 아직 scan 하지 않음
+    path_value: Option<String>,
+    // PATH 디렉토리별 scan 했을 때의 mtime
+    dir_mtimes: Vec<(PathBuf, Option<SystemTime>)>,
+    // command 이름 -> PATH 에서 처음 찾은 실행 파일
+    executables: BTreeMap<String, PathBuf>,
+    // hash builtin 에 보이는 기억된 command
+    hashed_commands: BTreeMap<String, HashedCommand>,
+}
+
+static COMMAND_CACHE: Mutex<CommandCache> = Mutex::new(CommandCache {
+    path_value: None,
+    dir_mtimes: vec![],
+    executables: BTreeMap::new(),
+    hashed_commands: BTreeMap::new(),
+});
+
+fn get_dir_mtime(dir: &Path) -> Option<SystemTime> {
+    fs::metadata(dir).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn refresh_command_cache(command_cache: &mut CommandCache) {
+    let path_value = get_variable("PATH").unwrap_or_default();
+
+    if command_cache.path_value.as_ref() == Some(&path_value) {
+        let is_dir_changed = command_cache.dir_mtimes.iter().any(|(dir, mtime)| get_dir_mtime(dir) != *mtime);
+        if false == is_dir_changed {
+            return;
+        }
+    } else {
+        // PATH 가 바뀌면 기억된 경로도 의미가 없음
+        command_cache.hashed_commands.clear();
+    }
+
+    command_cache.executables.clear();
+    command_cache.dir_mtimes.clear();
+
+    for dir in env::split_paths(&OsString::from(&path_value)) {
+        command_cache.dir_mtimes.push((dir.to_owned(), get_dir_mtime(&dir)));
+
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for dir_entry in read_dir.flatten() {
+            let Some(file_name) = dir_entry.file_name().to_str().map(|name| name.to_string()) else {
+                continue;
+            };
+            // 앞쪽 디렉토리가 우선
+            if command_cache.executables.contains_key(&file_name) {
+                continue;
+            }
+
+            let full_path = dir_entry.path();
+            if full_path.is_dir() || false == full_path.is_executable() {
+                continue;
+            }
+            command_cache.executables.insert(file_name, full_path);
+        }
+    }
+
+    command_cache.path_value = Some(path_value);
+}
+
+fn search_path(path_value: &str, name: &str) -> Option<PathBuf> {
+    env::split_paths(&OsString::from(path_value))
+        .map(|dir| dir.join(name))
+        .find(|full_path| false == full_path.is_dir() && full_path.is_executable())
+}
+
+// PATH 에 있는 모든 실행 파일 이름 (정렬됨)
+pub fn get_executable_command_names() -> Vec<String> {
+    let mut command_cache = COMMAND_CACHE.lock().unwrap();
+    refresh_command_cache(&mut command_cache);
+
+    command_cache.executables.keys().cloned().collect()
+}
+
+// hash table 에 기억하지 않고 PATH 에서만 찾음 (completion 용)
+pub fn get_executable_path(name: &str) -> Option<PathBuf> {
+    let mut command_cache = COMMAND_CACHE.lock().unwrap();
+    refresh_command_cache(&mut command_cache);
+
+    command_cache.executables.get(name).cloned()
+}
+
+// 실행할 command 의 경로, 찾으면 hash table 에 기억하고 사용 횟수 증가
+pub fn find_command_path(name: &str) -> Option<PathBuf> {
+    let mut command_cache = COMMAND_CACHE.lock().unwrap();
+    refresh_command_cache(&mut command_cache);
+
+    // 기억된 경로가 지워졌으면 다시 찾음
+    if let Some(hashed_command) = command_cache.hashed_commands.get_mut(name)
+        && hashed_command.path.is_executable() {
+        hashed_command.hits += 1;
+        return Some(hashed_command.path.to_owned());
+    }
+    command_cache.hashed_commands.remove(name);
+
+    // chmod +x 처럼 디렉토리 mtime 이 바뀌지 않는 변경도 있어서, cache 에 없으면 PATH 를 직접 확인
+    let path = match command_cache.executables.get(name) {
+        Some(path) => path.to_owned(),
+        None => {
+            let path = search_path(command_cache.path_value.as_deref().unwrap_or(""), name)?;
+            command_cache.executables.insert(name.to_string(), path.to_owned());
+            path
+        }
+    };
+    command_cache.hashed_commands.insert(name.to_string(), HashedCommand {
+        name: name.to_string(),
+        path: path.to_owned(),
+        hits: 1,
+    });
+
+    Some(path)
+}
+
+// hash name : 실행하지 않고 경로만 기억
+pub fn hash_command(name: &str) -> Option<PathBuf> {
+    let path = find_command_path(name)?;
+    if let Some(hashed_command) = COMMAND_CACHE.lock().unwrap().hashed_commands.get_mut(name) {
+        hashed_command.hits -= 1;
+    }
+    Some(path)
+}
+
+// hash -p path name
+pub fn set_hashed_command(name: &str, path: &Path) {
+    let mut command_cache = COMMAND_CACHE.lock().unwrap();
+    refresh_command_cache(&mut command_cache);
+
+    command_cache.hashed_commands.insert(name.to_string(), HashedCommand {
+        name: name.to_string(),
+        path: path.to_path_buf(),
+        hits: 0,
+    });
+}
+
+pub fn get_hashed_command(name: &str) -> Option<HashedCommand> {
+    let mut command_cache = COMMAND_CACHE.lock().unwrap();
+    refresh_command_cache(&mut command_cache);
+
+    command_cache.hashed_commands.get(name).cloned()
+}
+
+pub fn get_hashed_commands() -> Vec<HashedCommand> {
+    let mut command_cache = COMMAND_CACHE.lock().unwrap();
+    refresh_command_cache(&mut command_cache);
+
+    command_cache.hashed_commands.values().cloned().collect()
+}
+
+pub fn remove_hashed_command(name: &str) -> bool {
+    COMMAND_CACHE.lock().unwrap().hashed_commands.remove(name).is_some()
+}
+
+// hash -r : 기억된 경로를 비우고 다음에 PATH 를 다시 scan
+pub fn clear_command_cache() {
+    let mut command_cache = COMMAND_CACHE.lock().unwrap();
+    command_cache.hashed_commands.clear();
+    command_cache.path_value = None;
+}
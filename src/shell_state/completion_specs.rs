@@ -0,0 +1,114 @@
+// complete builtin 으로 등록한 command 별 completion 규칙
+use std::{collections::BTreeMap, sync::Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompletionAction {
+    Builtin,
+    Command,
+    Directory,
+    File,
+    Function,
+    Hostname,
+    Job,
+    User,
+    Variable,
+}
+
+impl CompletionAction {
+    // -A action 이름
+    pub fn from_name(name: &str) -> Option<Self> {
+        let action = match name {
+            "builtin" => Self::Builtin,
+            "command" => Self::Command,
+            "directory" => Self::Directory,
+            "file" => Self::File,
+            "function" => Self::Function,
+            "hostname" => Self::Hostname,
+            "job" => Self::Job,
+            "user" => Self::User,
+            "variable" => Self::Variable,
+            _ => return None,
+        };
+        Some(action)
+    }
+
+    // -b, -c, -d 같은 한 글자 flag
+    pub fn from_flag(flag: char) -> Option<Self> {
+        let action = match flag {
+            'b' => Self::Builtin,
+            'c' => Self::Command,
+            'd' => Self::Directory,
+            'f' => Self::File,
+            'j' => Self::Job,
+            'u' => Self::User,
+            'v' => Self::Variable,
+            _ => return None,
+        };
+        Some(action)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Builtin => "builtin",
+            Self::Command => "command",
+            Self::Directory => "directory",
+            Self::File => "file",
+            Self::Function => "function",
+            Self::Hostname => "hostname",
+            Self::Job => "job",
+            Self::User => "user",
+            Self::Variable => "variable",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompletionSpec {
+    // -A, -b, -c, -d, -f, -j, -u, -v
+    pub actions: Vec<CompletionAction>,
+    // -W : 공백으로 구분된 단어 목록 (completion 할 때 확장)
+    pub word_list: Option<String>,
+    // -F : COMPREPLY 배열에 결과를 담는 shell 함수
+    pub function: Option<String>,
+    // -C : 결과를 한 줄에 하나씩 출력하는 외부 command
+    pub command: Option<String>,
+    // -X : 후보에서 제외할 glob pattern, ! 로 시작하면 매칭되지 않는 후보를 제외
+    pub filter_pattern: Option<String>,
+    // -o : nospace, default, dirnames, filenames
+    pub options: Vec<String>,
+}
+
+impl CompletionSpec {
+    pub fn has_option(&self, option: &str) -> bool {
+        self.options.iter().any(|spec_option| spec_option == option)
+    }
+}
+
+static COMPLETION_SPECS: Mutex<BTreeMap<String, CompletionSpec>> = Mutex::new(BTreeMap::new());
+
+pub fn get_completion_spec(command: &str) -> Option<CompletionSpec> {
+    let completion_specs = COMPLETION_SPECS.lock().unwrap();
+
+    // /usr/bin/git 처럼 경로로 입력해도 git 의 규칙을 사용
+    if let Some(completion_spec) = completion_specs.get(command) {
+        return Some(completion_spec.to_owned());
+    }
+    let command_name = command.rsplit('/').next().unwrap_or(command);
+    completion_specs.get(command_name).cloned()
+}
+
+pub fn set_completion_spec(command: &str, completion_spec: CompletionSpec) {
+    COMPLETION_SPECS.lock().unwrap().insert(command.to_string(), completion_spec);
+}
+
+pub fn remove_completion_spec(command: &str) -> bool {
+    COMPLETION_SPECS.lock().unwrap().remove(command).is_some()
+}
+
+pub fn clear_completion_specs() {
+    COMPLETION_SPECS.lock().unwrap().clear();
+}
+
+pub fn get_all_completion_specs() -> Vec<(String, CompletionSpec)> {
+    COMPLETION_SPECS.lock().unwrap().iter().map(|(command, completion_spec)| (command.to_owned(), completion_spec.to_owned())).collect()
+}
@@ -0,0 +1,182 @@
+// z, j builtin 이 사용하는 방문한 디렉토리 기록
+//   ZDATA : 기록 파일 경로 (기본값 ~/.shell_z), 빈 값이면 기록하지 않음
+// 파일은 z.sh 와 같은 "path|rank|time" 형식이라 ZDATA=~/.z 로 z.sh 의 기록을 그대로 쓸 수 있다
+// cd 로 이동할 때마다 rank 를 1 올리고, rank 합이 MAX_TOTAL_RANK 를 넘으면 모두 0.99 배 하고 1 미만은 지움
+// 여러 shell 이 같이 쓰도록 방문할 때마다 파일을 다시 읽어서 갱신한다
+// 점수(frecency)는 rank 에 마지막 방문이 1시간 이내면 x4, 하루 이내면 x2, 일주일 이내면 /2, 그 이상은 /4
+
+use std::{fs, path::{Path, PathBuf}};
+
+use crate::shell_state::history::get_unix_time;
+use crate::shell_state::variables::get_variable;
+
+const DEFAULT_FRECENCY_FILE_NAME: &str = ".shell_z";
+const MAX_TOTAL_RANK: f64 = 9000.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryRecord {
+    pub path: String,
+    // 방문 횟수 (오래되면 줄어듦)
+    pub rank: f64,
+    pub last_access: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrecencySort {
+    Frecency,
+    // 방문 횟수만
+    Rank,
+    // 마지막 방문 시각만
+    Recent,
+}
+
+pub fn get_frecency_file_path() -> Option<PathBuf> {
+    if let Some(frecency_file) = get_variable("ZDATA") {
+        if frecency_file.is_empty() {
+            return None;
+        }
+        return Some(PathBuf::from(frecency_file));
+    }
+
+    get_variable("HOME").map(|home| Path::new(&home).join(DEFAULT_FRECENCY_FILE_NAME))
+}
+
+// 파일이 없거나 형식이 맞지 않는 줄은 무시
+pub fn read_directory_records() -> Vec<DirectoryRecord> {
+    let Some(frecency_file_path) = get_frecency_file_path() else {
+        return vec![];
+    };
+    let Ok(contents) = fs::read_to_string(frecency_file_path) else {
+        return vec![];
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| {
+            // 경로에 | 가 있을 수 있어서 뒤에서부터
+            let mut fields = line.rsplitn(3, '|');
+            let last_access = fields.next()?.parse().ok()?;
+            let rank = fields.next()?.parse().ok()?;
+            let path = fields.next()?.to_string();
+            Some(DirectoryRecord { path, rank, last_access })
+        })
+        .collect()
+}
+
+// 임시 파일에 쓰고 rename 해서 다른 shell 이 중간 상태를 읽지 않게
+fn write_directory_records(path: &Path, records: &[DirectoryRecord]) -> Result<(), String> {
+    let mut contents = String::new();
+    for record in records {
+        contents.push_str(&format!("{}|{}|{}\n", record.path, record.rank, record.last_access));
+    }
+
+    let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temp_path, contents).map_err(|e| format!("{}: {}", temp_path.display(), e))?;
+    fs::rename(&temp_path, path).map_err(|e| {
+        fs::remove_file(&temp_path).ok();
+        format!("{}: {}", path.display(), e)
+    })
+}
+
+// cd 로 이동한 디렉토리 기록, HOME 과 / 는 기록하지 않음
+pub fn add_directory_visit(dir: &str) {
+    let home = get_variable("HOME").unwrap_or_default();
+    if dir == "/" || dir == home.trim_end_matches('/') {
+        return;
+    }
+    let Some(frecency_file_path) = get_frecency_file_path() else {
+        return;
+    };
+
+    let mut records = read_directory_records();
+    let now = get_unix_time();
+    match records.iter_mut().find(|record| record.path == dir) {
+        Some(record) => {
+            record.rank += 1.0;
+            record.last_access = now;
+        }
+        None => records.push(DirectoryRecord {
+            path: dir.to_string(),
+            rank: 1.0,
+            last_access: now,
+        }),
+    }
+
+    if records.iter().map(|record| record.rank).sum::<f64>() > MAX_TOTAL_RANK {
+        for record in records.iter_mut() {
+            record.rank *= 0.99;
+        }
+        records.retain(|record| record.rank >= 1.0);
+    }
+
+    if let Err(e) = write_directory_records(&frecency_file_path, &records) {
+        println!("directory frecency save error. {}", e);
+    }
+}
+
+pub fn remove_directory_record(dir: &str) -> Result<bool, String> {
+    let Some(frecency_file_path) = get_frecency_file_path() else {
+        return Ok(false);
+    };
+
+    let mut records = read_directory_records();
+    let len = records.len();
+    records.retain(|record| record.path != dir);
+    if records.len() == len {
+        return Ok(false);
+    }
+
+    write_directory_records(&frecency_file_path, &records)?;
+    Ok(true)
+}
+
+pub fn get_directory_score(record: &DirectoryRecord, frecency_sort: FrecencySort, now: i64) -> f64 {
+    match frecency_sort {
+        FrecencySort::Rank => record.rank,
+        // 최근일수록 큰 값
+        FrecencySort::Recent => -((now - record.last_access) as f64),
+        FrecencySort::Frecency => match now - record.last_access {
+            elapsed if elapsed < 60 * 60 => record.rank * 4.0,
+            elapsed if elapsed < 24 * 60 * 60 => record.rank * 2.0,
+            elapsed if elapsed < 7 * 24 * 60 * 60 => record.rank / 2.0,
+            _ => record.rank / 4.0,
+        },
+    }
+}
+
+// 경로에 keyword 가 순서대로 들어있는 디렉토리를 점수가 높은 순으로
+// 대소문자를 구분해서 찾고, 없으면 구분하지 않고 다시 찾음, 지금 없는 디렉토리는 제외
+pub fn find_matching_directories(keywords: &[String], frecency_sort: FrecencySort) -> Vec<(DirectoryRecord, f64)> {
+    let now = get_unix_time();
+    let records: Vec<DirectoryRecord> = read_directory_records().into_iter().filter(|record| Path::new(&record.path).is_dir()).collect();
+
+    let mut matched_records: Vec<(DirectoryRecord, f64)> = vec![];
+    for is_ignore_case in [false, true] {
+        matched_records = records
+            .iter()
+            .filter(|record| is_keywords_matched(&record.path, keywords, is_ignore_case))
+            .map(|record| (record.to_owned(), get_directory_score(record, frecency_sort, now)))
+            .collect();
+        if false == matched_records.is_empty() {
+            break;
+        }
+    }
+
+    matched_records.sort_by(|(_, score), (_, other_score)| other_score.total_cmp(score));
+    matched_records
+}
+
+fn is_keywords_matched(path: &str, keywords: &[String], is_ignore_case: bool) -> bool {
+    let path = if is_ignore_case { path.to_lowercase() } else { path.to_string() };
+
+    let mut rest = path.as_str();
+    for keyword in keywords {
+        let keyword = if is_ignore_case { keyword.to_lowercase() } else { keyword.to_string() };
+        let Some(match_idx) = rest.find(&keyword) else {
+            return false;
+        };
+        rest = &rest[match_idx + keyword.len()..];
+    }
+
+    true
+}
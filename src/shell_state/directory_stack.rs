@@ -0,0 +1,46 @@
+// pushd, popd, dirs 의 directory stack
+// 0 번째(맨 위)는 항상 현재 디렉토리(PWD)라서 저장하지 않고 그 아래 entry 만 저장한다
+
+use std::sync::Mutex;
+
+use crate::shell_builtin::cd_command::get_logical_current_dir;
+
+static DIRECTORY_STACK: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+// 현재 디렉토리를 포함한 전체 stack
+pub fn get_directory_stack() -> Vec<String> {
+    let mut directory_stack = vec![get_logical_current_dir().to_string_lossy().into_owned()];
+    directory_stack.extend(DIRECTORY_STACK.lock().unwrap().iter().cloned());
+    directory_stack
+}
+
+// 현재 디렉토리를 제외한 entry 로 교체
+pub fn set_saved_directories(directories: Vec<String>) {
+    *DIRECTORY_STACK.lock().unwrap() = directories;
+}
+
+// +N 은 왼쪽(맨 위)부터, -N 은 오른쪽부터 0 으로 시작, 부호가 없으면 +N
+// N 이 숫자가 아니거나 범위를 벗어나면 None
+pub fn parse_directory_stack_index(index: &str, stack_len: usize) -> Option<usize> {
+    let (is_from_right, number) = match index.strip_prefix('-') {
+        Some(number) => (true, number),
+        None => (false, index.strip_prefix('+').unwrap_or(index)),
+    };
+    if false == number.chars().all(|char| char.is_ascii_digit()) {
+        return None;
+    }
+
+    let number = number.parse::<usize>().ok()?;
+    if number >= stack_len {
+        return None;
+    }
+
+    Some(if is_from_right { stack_len - 1 - number } else { number })
+}
+
+// ~N, ~+N, ~-N 에 해당하는 entry
+pub fn get_directory_stack_entry(index: &str) -> Option<String> {
+    let directory_stack = get_directory_stack();
+    let index = parse_directory_stack_index(index, directory_stack.len())?;
+    directory_stack.get(index).cloned()
+}
@@ -0,0 +1,19 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use crate::shell_parser::command_list::CommandNode;
+
+// name() { ...; } 로 정의된 shell 함수
+// subshell 에서 정의한 함수는 fork 된 자식에만 남음
+static SHELL_FUNCTIONS: Mutex<BTreeMap<String, CommandNode>> = Mutex::new(BTreeMap::new());
+
+pub fn get_function(name: &str) -> Option<CommandNode> {
+    SHELL_FUNCTIONS.lock().unwrap().get(name).cloned()
+}
+
+pub fn set_function(name: &str, body: CommandNode) {
+    SHELL_FUNCTIONS.lock().unwrap().insert(name.to_string(), body);
+}
+
+pub fn get_function_names() -> Vec<String> {
+    SHELL_FUNCTIONS.lock().unwrap().keys().cloned().collect()
+}
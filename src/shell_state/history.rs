@@ -0,0 +1,432 @@
+// command history 저장소
+// rustyline Editor 의 History 구현(SharedHistory)과 history builtin 이 같은 저장소를 사용한다
+//   HISTFILE    : history 파일 경로 (기본값 ~/.shell_history)
+//   HISTSIZE    : 메모리와 파일에 유지할 최대 개수 (기본값 500)
+//   HISTCONTROL : ignoredups, ignorespace, ignoreboth, erasedups 를 ':' 로 구분
+// 여러 줄 command 도 하나의 entry 로 저장
+// 이 shell 에서 실행한 entry 는 시작 시각, 걸린 시간, exit status, 디렉토리, session 을 함께 기억
+
+use std::{borrow::Cow, fs::{self, OpenOptions}, io::Write, path::{Path, PathBuf}, sync::{Mutex, OnceLock}, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use rustyline::history::{History, SearchDirection, SearchResult};
+
+use crate::shell_builtin::cd_command::get_logical_current_dir;
+use crate::shell_state::variables::get_variable;
+
+const DEFAULT_HISTORY_FILE_NAME: &str = ".shell_history";
+const DEFAULT_HISTORY_SIZE: usize = 500;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub line: String,
+    // 실행 정보, history 파일에서 읽은 entry 는 None
+    pub metadata: Option<HistoryMetadata>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryMetadata {
+    // 실행 시작 시각 (unix time 초)
+    pub start_time: i64,
+    // 아래 둘은 실행이 끝나야 채워짐
+    pub duration_ms: Option<u64>,
+    pub exit_status: Option<i32>,
+    pub cwd: String,
+    pub session_id: String,
+}
+
+struct HistoryStore {
+    entries: Vec<HistoryEntry>,
+    // 아직 history 파일에 append 되지 않은 첫번째 entry 인덱스
+    new_entry_start: usize,
+}
+
+static SHELL_HISTORY: Mutex<HistoryStore> = Mutex::new(HistoryStore {
+    entries: vec![],
+    new_entry_start: 0,
+});
+
+// 같은 shell 에서 실행한 command 를 구분하기 위한 id (시작 시각과 pid)
+static SESSION_ID: OnceLock<String> = OnceLock::new();
+
+pub fn get_session_id() -> &'static str {
+    SESSION_ID.get_or_init(|| format!("{:x}-{}", get_unix_time(), std::process::id()))
+}
+
+pub fn get_unix_time() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs() as i64).unwrap_or(0)
+}
+
+// 지금 시작하는 command 의 실행 정보
+pub fn new_history_metadata() -> HistoryMetadata {
+    HistoryMetadata {
+        start_time: get_unix_time(),
+        duration_ms: None,
+        exit_status: None,
+        // cd, prompt 와 같은 논리 경로 (symlink 를 풀지 않음)
+        cwd: get_logical_current_dir().to_string_lossy().into_owned(),
+        session_id: get_session_id().to_string(),
+    }
+}
+
+pub fn get_history_file_path() -> Option<PathBuf> {
+    if let Some(history_file) = get_variable("HISTFILE") {
+        // HISTFILE 이 빈 값이면 파일에 저장하지 않음
+        if history_file.is_empty() {
+            return None;
+        }
+        return Some(PathBuf::from(history_file));
+    }
+
+    get_variable("HOME").map(|home| Path::new(&home).join(DEFAULT_HISTORY_FILE_NAME))
+}
+
+pub fn get_history_size() -> usize {
+    get_variable("HISTSIZE")
+        .and_then(|history_size| history_size.trim().parse::<usize>().ok())
+        .unwrap_or(DEFAULT_HISTORY_SIZE)
+}
+
+pub fn has_history_control(option: &str) -> bool {
+    let Some(history_control) = get_variable("HISTCONTROL") else {
+        return false;
+    };
+
+    history_control.split(':').any(|control| {
+        control == option || (control == "ignoreboth" && (option == "ignoredups" || option == "ignorespace"))
+    })
+}
+
+fn truncate_history(store: &mut HistoryStore, max_len: usize) {
+    if store.entries.len() <= max_len {
+        return;
+    }
+
+    let remove_count = store.entries.len() - max_len;
+    store.entries.drain(..remove_count);
+    store.new_entry_start = store.new_entry_start.saturating_sub(remove_count);
+}
+
+// HISTCONTROL 규칙에 따라 무시되면 false
+pub fn add_history_entry(line: &str) -> bool {
+    if line.trim().is_empty() {
+        return false;
+    }
+
+    if line.starts_with(' ') && has_history_control("ignorespace") {
+        return false;
+    }
+
+    let mut store = SHELL_HISTORY.lock().unwrap();
+
+    if has_history_control("ignoredups") && store.entries.last().is_some_and(|entry| entry.line == line) {
+        return false;
+    }
+
+    // 이전에 같은 command 가 있으면 모두 지움
+    if has_history_control("erasedups") {
+        let mut idx = 0;
+        while idx < store.entries.len() {
+            if store.entries[idx].line == line {
+                store.entries.remove(idx);
+                if idx < store.new_entry_start {
+                    store.new_entry_start -= 1;
+                }
+                continue;
+            }
+            idx += 1;
+        }
+    }
+
+    store.entries.push(HistoryEntry {
+        line: line.to_string(),
+        metadata: Some(new_history_metadata()),
+    });
+    truncate_history(&mut store, get_history_size());
+
+    true
+}
+
+// 실행이 끝난 command 의 exit status 와 걸린 시간 기록
+pub fn finish_history_entry(line: &str, exit_status: i32, duration: Duration) {
+    let mut store = SHELL_HISTORY.lock().unwrap();
+    let session_id = get_session_id();
+
+    let running_metadata = store
+        .entries
+        .iter_mut()
+        .rev()
+        .filter(|entry| entry.line == line)
+        .filter_map(|entry| entry.metadata.as_mut())
+        .find(|metadata| metadata.session_id == session_id && metadata.exit_status.is_none());
+    if let Some(metadata) = running_metadata {
+        metadata.exit_status = Some(exit_status);
+        metadata.duration_ms = Some(duration.as_millis() as u64);
+    }
+}
+
+pub fn get_history_entries() -> Vec<HistoryEntry> {
+    SHELL_HISTORY.lock().unwrap().entries.to_owned()
+}
+
+// prefix 로 시작하는 가장 최근 entry (prefix 와 같은 entry 는 제외)
+pub fn find_history_entry_by_prefix(prefix: &str) -> Option<String> {
+    let store = SHELL_HISTORY.lock().unwrap();
+    store
+        .entries
+        .iter()
+        .rev()
+        .find(|entry| entry.line.len() > prefix.len() && entry.line.starts_with(prefix))
+        .map(|entry| entry.line.to_owned())
+}
+
+pub fn clear_history() {
+    let mut store = SHELL_HISTORY.lock().unwrap();
+    store.entries.clear();
+    store.new_entry_start = 0;
+}
+
+// index 는 0 부터
+pub fn delete_history_entry(index: usize) -> bool {
+    let mut store = SHELL_HISTORY.lock().unwrap();
+    if index >= store.entries.len() {
+        return false;
+    }
+
+    store.entries.remove(index);
+    if index < store.new_entry_start {
+        store.new_entry_start -= 1;
+    }
+
+    true
+}
+
+// 여러 줄 command 는 마지막 줄을 제외한 각 줄 끝에 \ 를 붙여서 저장 (zsh 와 같은 형식)
+fn format_history_file_entry(line: &str) -> String {
+    line.replace('\n', "\\\n")
+}
+
+// 줄 끝의 \ 개수가 홀수면 다음 줄과 같은 entry
+fn parse_history_file_entries(contents: &str) -> Vec<String> {
+    let mut entries = vec![];
+    let mut continued_entry: Option<String> = None;
+
+    for line in contents.lines() {
+        let mut entry = match continued_entry.take() {
+            Some(mut continued_entry) => {
+                continued_entry.push('\n');
+                continued_entry.push_str(line);
+                continued_entry
+            }
+            None => line.to_string(),
+        };
+
+        let trailing_backslash_count = line.chars().rev().take_while(|char| *char == '\\').count();
+        if trailing_backslash_count % 2 == 1 {
+            entry.pop();
+            continued_entry = Some(entry);
+            continue;
+        }
+        entries.push(entry);
+    }
+    entries.extend(continued_entry);
+
+    entries
+}
+
+// 파일 내용을 현재 history 뒤에 추가
+pub fn read_history_file(path: &Path) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let mut store = SHELL_HISTORY.lock().unwrap();
+    for line in parse_history_file_entries(&contents) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        store.entries.push(HistoryEntry { line, metadata: None });
+    }
+    truncate_history(&mut store, get_history_size());
+    // 파일에서 읽어온 entry 는 다시 append 하지 않음
+    store.new_entry_start = store.entries.len();
+
+    Ok(())
+}
+
+// 현재 history 전체로 파일을 덮어씀
+pub fn write_history_file(path: &Path) -> Result<(), String> {
+    let mut store = SHELL_HISTORY.lock().unwrap();
+
+    let mut contents = String::new();
+    for entry in &store.entries {
+        contents.push_str(&format_history_file_entry(&entry.line));
+        contents.push('\n');
+    }
+    fs::write(path, contents).map_err(|e| format!("{}: {}", path.display(), e))?;
+    store.new_entry_start = store.entries.len();
+
+    Ok(())
+}
+
+// 아직 파일에 쓰지 않은 entry 만 파일 뒤에 추가하고, 파일이 HISTSIZE 를 넘으면 앞쪽을 잘라냄
+pub fn append_history_file(path: &Path) -> Result<(), String> {
+    let mut store = SHELL_HISTORY.lock().unwrap();
+    if store.new_entry_start >= store.entries.len() {
+        return Ok(());
+    }
+
+    {
+        let mut file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        for entry in &store.entries[store.new_entry_start..] {
+            writeln!(file, "{}", format_history_file_entry(&entry.line)).map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+    }
+    store.new_entry_start = store.entries.len();
+
+    let history_size = get_history_size();
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let entries = parse_history_file_entries(&contents);
+    if entries.len() > history_size {
+        let mut truncated = String::new();
+        for entry in &entries[entries.len() - history_size..] {
+            truncated.push_str(&format_history_file_entry(entry));
+            truncated.push('\n');
+        }
+        fs::write(path, truncated).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    Ok(())
+}
+
+// shell 시작 시 HISTFILE 로드
+pub fn load_history() {
+    let Some(history_file_path) = get_history_file_path() else {
+        return;
+    };
+
+    if false == history_file_path.exists() {
+        return;
+    }
+
+    if let Err(e) = read_history_file(&history_file_path) {
+        eprintln!("history load error. {}", e);
+    }
+}
+
+// shell 종료 시 이번 세션의 history 를 HISTFILE 에 append
+pub fn save_history() {
+    let Some(history_file_path) = get_history_file_path() else {
+        return;
+    };
+
+    if let Err(e) = append_history_file(&history_file_path) {
+        eprintln!("history save error. {}", e);
+    }
+}
+
+// rustyline Editor 에서 사용하는 History 구현, 실제 데이터는 SHELL_HISTORY 에 있음
+#[derive(Debug, Default)]
+pub struct SharedHistory;
+
+impl SharedHistory {
+    fn search_match<F>(&self, term: &str, start: usize, dir: SearchDirection, test: F) -> Option<SearchResult<'_>>
+    where
+        F: Fn(&str) -> Option<usize>,
+    {
+        let store = SHELL_HISTORY.lock().unwrap();
+        if term.is_empty() || start >= store.entries.len() {
+            return None;
+        }
+
+        let indexes: Box<dyn Iterator<Item = usize>> = match dir {
+            SearchDirection::Reverse => Box::new((0..=start).rev()),
+            SearchDirection::Forward => Box::new(start..store.entries.len()),
+        };
+
+        for idx in indexes {
+            let entry = &store.entries[idx].line;
+            if let Some(pos) = test(entry) {
+                return Some(SearchResult {
+                    entry: Cow::Owned(entry.to_owned()),
+                    idx,
+                    pos,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+impl History for SharedHistory {
+    fn get(&self, index: usize, _dir: SearchDirection) -> rustyline::Result<Option<SearchResult<'_>>> {
+        let store = SHELL_HISTORY.lock().unwrap();
+        Ok(store.entries.get(index).map(|entry| SearchResult {
+            entry: Cow::Owned(entry.line.to_owned()),
+            idx: index,
+            pos: 0,
+        }))
+    }
+
+    fn add(&mut self, line: &str) -> rustyline::Result<bool> {
+        Ok(add_history_entry(line))
+    }
+
+    fn add_owned(&mut self, line: String) -> rustyline::Result<bool> {
+        Ok(add_history_entry(&line))
+    }
+
+    fn len(&self) -> usize {
+        SHELL_HISTORY.lock().unwrap().entries.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        SHELL_HISTORY.lock().unwrap().entries.is_empty()
+    }
+
+    fn set_max_len(&mut self, len: usize) -> rustyline::Result<()> {
+        truncate_history(&mut SHELL_HISTORY.lock().unwrap(), len);
+        Ok(())
+    }
+
+    // 중복/공백 무시 여부는 HISTCONTROL 변수로 제어
+    fn ignore_dups(&mut self, _yes: bool) -> rustyline::Result<()> {
+        Ok(())
+    }
+
+    fn ignore_space(&mut self, _yes: bool) {}
+
+    fn save(&mut self, path: &Path) -> rustyline::Result<()> {
+        write_history_file(path).map_err(|e| rustyline::error::ReadlineError::Io(std::io::Error::other(e)))
+    }
+
+    fn append(&mut self, path: &Path) -> rustyline::Result<()> {
+        append_history_file(path).map_err(|e| rustyline::error::ReadlineError::Io(std::io::Error::other(e)))
+    }
+
+    fn load(&mut self, path: &Path) -> rustyline::Result<()> {
+        read_history_file(path).map_err(|e| rustyline::error::ReadlineError::Io(std::io::Error::other(e)))
+    }
+
+    fn clear(&mut self) -> rustyline::Result<()> {
+        clear_history();
+        Ok(())
+    }
+
+    fn search(&self, term: &str, start: usize, dir: SearchDirection) -> rustyline::Result<Option<SearchResult<'_>>> {
+        Ok(self.search_match(term, start, dir, |entry| entry.find(term)))
+    }
+
+    fn starts_with(&self, term: &str, start: usize, dir: SearchDirection) -> rustyline::Result<Option<SearchResult<'_>>> {
+        Ok(self.search_match(term, start, dir, |entry| {
+            if entry.starts_with(term) {
+                Some(term.len())
+            } else {
+                None
+            }
+        }))
+    }
+}
@@ -0,0 +1,207 @@
+// 실행한 command 기록 (history builtin 의 --cwd, --failed, --since, --json 으로 조회)
+//   HISTDB : 기록 파일 경로 (기본값 ~/.shell_history.jsonl), 빈 값이면 기록하지 않음
+// command 가 끝날 때마다 한 줄의 JSON 으로 파일 끝에 추가만 하고 지우거나 고치지 않는다
+//   {"start":1760000000,"duration_ms":12,"status":0,"cwd":"/tmp","session":"68f4-1234","command":"ls"}
+// HISTSIZE, HISTCONTROL 의 ignoredups, erasedups 와 상관없이 모두 기록 (ignorespace 만 적용)
+// 여러 shell 이 같은 파일에 기록해도 한 줄씩 한번에 write 하므로 줄이 섞이지 않음
+// 읽을 수 없는 줄(쓰다가 중단된 줄 등)은 건너뜀
+
+use std::{fs::{self, OpenOptions}, io::Write, path::{Path, PathBuf}};
+
+use crate::shell_state::history::{HistoryEntry, HistoryMetadata, has_history_control};
+use crate::shell_state::variables::get_variable;
+
+const DEFAULT_HISTORY_DATABASE_FILE_NAME: &str = ".shell_history.jsonl";
+
+#[derive(Debug, Clone, PartialEq)]
+enum JsonValue {
+    Null,
+    Number(i64),
+    String(String),
+}
+
+pub fn get_history_database_path() -> Option<PathBuf> {
+    if let Some(history_database) = get_variable("HISTDB") {
+        if history_database.is_empty() {
+            return None;
+        }
+        return Some(PathBuf::from(history_database));
+    }
+
+    get_variable("HOME").map(|home| Path::new(&home).join(DEFAULT_HISTORY_DATABASE_FILE_NAME))
+}
+
+// 실행이 끝난 command 를 기록
+pub fn append_history_record(line: &str, metadata: &HistoryMetadata) {
+    if line.trim().is_empty() || (line.starts_with(' ') && has_history_control("ignorespace")) {
+        return;
+    }
+    let Some(history_database_path) = get_history_database_path() else {
+        return;
+    };
+
+    let record = format!("{}\n", format_history_record(line, metadata));
+    let result = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(&history_database_path)
+        .and_then(|mut file| file.write_all(record.as_bytes()));
+    if let Err(e) = result {
+        println!("history database error. {}: {}", history_database_path.display(), e);
+    }
+}
+
+// 파일에 기록된 순서대로
+pub fn read_history_records(path: &Path) -> Result<Vec<HistoryEntry>, String> {
+    if false == path.exists() {
+        return Ok(vec![]);
+    }
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    Ok(contents.lines().filter_map(parse_history_record).collect())
+}
+
+pub fn format_history_record(line: &str, metadata: &HistoryMetadata) -> String {
+    let format_number = |number: Option<i64>| number.map(|number| number.to_string()).unwrap_or("null".to_string());
+
+    format!(
+        "{{\"start\":{},\"duration_ms\":{},\"status\":{},\"cwd\":{},\"session\":{},\"command\":{}}}",
+        metadata.start_time,
+        format_number(metadata.duration_ms.map(|duration_ms| duration_ms as i64)),
+        format_number(metadata.exit_status.map(|exit_status| exit_status as i64)),
+        format_json_string(&metadata.cwd),
+        format_json_string(&metadata.session_id),
+        format_json_string(line),
+    )
+}
+
+fn parse_history_record(record: &str) -> Option<HistoryEntry> {
+    let fields = parse_json_object(record)?;
+    let get_field = |name: &str| fields.iter().find(|(key, _)| key == name).map(|(_, value)| value);
+    let get_number = |name: &str| match get_field(name) {
+        Some(JsonValue::Number(number)) => Some(*number),
+        _ => None,
+    };
+    let get_string = |name: &str| match get_field(name) {
+        Some(JsonValue::String(text)) => Some(text.to_owned()),
+        _ => None,
+    };
+
+    Some(HistoryEntry {
+        line: get_string("command")?,
+        metadata: Some(HistoryMetadata {
+            start_time: get_number("start")?,
+            duration_ms: get_number("duration_ms").map(|duration_ms| duration_ms as u64),
+            exit_status: get_number("status").map(|exit_status| exit_status as i32),
+            cwd: get_string("cwd").unwrap_or_default(),
+            session_id: get_string("session").unwrap_or_default(),
+        }),
+    })
+}
+
+fn format_json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for char in text.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            char if (char as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", char as u32)),
+            char => json.push(char),
+        }
+    }
+    json.push('"');
+    json
+}
+
+// 값이 문자열, 정수, null 인 한 단계의 object 만 읽음
+fn parse_json_object(text: &str) -> Option<Vec<(String, JsonValue)>> {
+    let chars: Vec<char> = text.trim().chars().collect();
+    let mut idx = 0;
+    let mut fields = vec![];
+
+    let skip_whitespace = |idx: &mut usize| {
+        while *idx < chars.len() && chars[*idx].is_whitespace() {
+            *idx += 1;
+        }
+    };
+
+    if chars.first() != Some(&'{') {
+        return None;
+    }
+    idx += 1;
+    skip_whitespace(&mut idx);
+    if chars.get(idx) == Some(&'}') {
+        return Some(fields);
+    }
+
+    loop {
+        skip_whitespace(&mut idx);
+        let key = parse_json_string(&chars, &mut idx)?;
+        skip_whitespace(&mut idx);
+        if chars.get(idx) != Some(&':') {
+            return None;
+        }
+        idx += 1;
+        skip_whitespace(&mut idx);
+
+        let value = match chars.get(idx)? {
+            '"' => JsonValue::String(parse_json_string(&chars, &mut idx)?),
+            'n' if chars[idx..].starts_with(&['n', 'u', 'l', 'l']) => {
+                idx += 4;
+                JsonValue::Null
+            }
+            _ => {
+                let start = idx;
+                while idx < chars.len() && (chars[idx] == '-' || chars[idx].is_ascii_digit()) {
+                    idx += 1;
+                }
+                JsonValue::Number(chars[start..idx].iter().collect::<String>().parse().ok()?)
+            }
+        };
+        fields.push((key, value));
+
+        skip_whitespace(&mut idx);
+        match chars.get(idx)? {
+            ',' => idx += 1,
+            '}' => return Some(fields),
+            _ => return None,
+        }
+    }
+}
+
+fn parse_json_string(chars: &[char], idx: &mut usize) -> Option<String> {
+    if chars.get(*idx) != Some(&'"') {
+        return None;
+    }
+    *idx += 1;
+
+    let mut text = String::new();
+    loop {
+        let char = *chars.get(*idx)?;
+        *idx += 1;
+        match char {
+            '"' => return Some(text),
+            '\\' => {
+                let escaped = *chars.get(*idx)?;
+                *idx += 1;
+                match escaped {
+                    'n' => text.push('\n'),
+                    'r' => text.push('\r'),
+                    't' => text.push('\t'),
+                    'b' => text.push('\u{8}'),
+                    'f' => text.push('\u{c}'),
+                    'u' => {
+                        let hex: String = chars.get(*idx..*idx + 4)?.iter().collect();
+                        *idx += 4;
+                        text.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?).unwrap_or('\u{fffd}'));
+                    }
+                    _ => text.push(escaped),
+                }
+            }
+            _ => text.push(char),
+        }
+    }
+}
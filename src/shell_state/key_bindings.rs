@@ -0,0 +1,69 @@
+// bind builtin 과 inputrc 파일로 설정한 key binding
+// 실제 rustyline Editor 에는 main 의 readline 직전에 apply_key_bindings 로 반영한다
+
+use std::sync::Mutex;
+
+use rustyline::KeyEvent;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyBindingAction {
+    // beginning-of-line 같은 editor 함수 이름
+    Function(String),
+    // 입력할 문자열 (escape 는 bind 할 때 해석)
+    Macro(String),
+    // bind -x 로 실행할 shell command
+    ShellCommand(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBinding {
+    // 입력한 그대로의 key sequence ("\C-x\C-r" 혹은 Control-a), 출력할 때 사용
+    pub key_sequence: String,
+    pub keys: Vec<KeyEvent>,
+    pub action: KeyBindingAction,
+}
+
+struct KeyBindingStore {
+    bindings: Vec<KeyBinding>,
+    // Editor 에 마지막으로 반영한 뒤 바뀌었는지
+    is_changed: bool,
+}
+
+static KEY_BINDINGS: Mutex<KeyBindingStore> = Mutex::new(KeyBindingStore {
+    bindings: vec![],
+    // 처음에는 기본 binding 을 반영해야 함
+    is_changed: true,
+});
+
+// 같은 key 의 binding 은 교체
+pub fn set_key_binding(key_binding: KeyBinding) {
+    let mut store = KEY_BINDINGS.lock().unwrap();
+    store.bindings.retain(|binding| binding.keys != key_binding.keys);
+    store.bindings.push(key_binding);
+    store.is_changed = true;
+}
+
+pub fn remove_key_binding(keys: &[KeyEvent]) -> bool {
+    let mut store = KEY_BINDINGS.lock().unwrap();
+    let len = store.bindings.len();
+    store.bindings.retain(|binding| binding.keys != keys);
+    if store.bindings.len() == len {
+        return false;
+    }
+    store.is_changed = true;
+    true
+}
+
+pub fn get_key_bindings() -> Vec<KeyBinding> {
+    KEY_BINDINGS.lock().unwrap().bindings.to_owned()
+}
+
+// 마지막으로 가져간 뒤 바뀌었으면 전체 binding
+pub fn take_changed_key_bindings() -> Option<Vec<KeyBinding>> {
+    let mut store = KEY_BINDINGS.lock().unwrap();
+    if false == store.is_changed {
+        return None;
+    }
+    store.is_changed = false;
+    Some(store.bindings.to_owned())
+}
@@ -0,0 +1,10 @@
+pub mod command_hash;
+pub mod completion_specs;
+pub mod directory_frecency;
+pub mod directory_stack;
+pub mod functions;
+pub mod history;
+pub mod history_database;
+pub mod key_bindings;
+pub mod options;
+pub mod variables;
\ No newline at end of file
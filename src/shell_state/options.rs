@@ -0,0 +1,58 @@
+// set -o / set +o 로 켜고 끄는 shell option
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub struct ShellOption {
+    pub name: &'static str,
+    // set -H 처럼 한 글자로 지정하는 flag
+    pub flag: Option<char>,
+    value: AtomicBool,
+}
+
+pub static SHELL_OPTIONS: [ShellOption; 7] = [
+    // 입력중인 line 뒤에 history 에서 찾은 나머지 부분을 흐리게 표시
+    ShellOption { name: "autosuggest", flag: None, value: AtomicBool::new(true) },
+    // history 에 없으면 현재 단어로 시작하는 path 도 제안
+    ShellOption { name: "autosuggestpath", flag: None, value: AtomicBool::new(false) },
+    // emacs 방식의 line 편집 (vi 와 둘 중 하나만 켜짐)
+    ShellOption { name: "emacs", flag: None, value: AtomicBool::new(true) },
+    // !! 같은 history expansion
+    ShellOption { name: "histexpand", flag: Some('H'), value: AtomicBool::new(true) },
+    // TAB completion 을 fuzzy 매칭과 선택 menu 로
+    ShellOption { name: "menucomplete", flag: None, value: AtomicBool::new(false) },
+    // vi 방식의 line 편집
+    ShellOption { name: "vi", flag: None, value: AtomicBool::new(false) },
+    // 실행하기 전에 PS4 와 함께 확장된 command 출력
+    ShellOption { name: "xtrace", flag: Some('x'), value: AtomicBool::new(false) },
+];
+
+impl ShellOption {
+    pub fn is_enabled(&self) -> bool {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+pub fn find_shell_option(name: &str) -> Option<&'static ShellOption> {
+    SHELL_OPTIONS.iter().find(|option| option.name == name)
+}
+
+pub fn find_shell_option_by_flag(flag: char) -> Option<&'static ShellOption> {
+    SHELL_OPTIONS.iter().find(|option| option.flag == Some(flag))
+}
+
+pub fn is_shell_option_enabled(name: &str) -> bool {
+    find_shell_option(name).is_some_and(|option| option.is_enabled())
+}
+
+pub fn set_shell_option(option: &ShellOption, is_enabled: bool) {
+    option.value.store(is_enabled, Ordering::Relaxed);
+
+    // 편집 방식은 vi 와 emacs 중 하나
+    let other_edit_mode = match option.name {
+        "vi" => "emacs",
+        "emacs" => "vi",
+        _ => return,
+    };
+    if let Some(other_option) = find_shell_option(other_edit_mode) {
+        other_option.value.store(false == is_enabled, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,123 @@
+use std::{collections::BTreeMap, env, sync::Mutex};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShellVariable {
+    Scalar(String),
+    Array(Vec<String>),
+}
+
+// shell 변수 저장소
+// subshell 은 fork 된 자식 프로세스에서 실행되기 때문에 자식에서의 변경은 부모로 새어나가지 않음
+static SHELL_VARIABLES: Mutex<BTreeMap<String, ShellVariable>> = Mutex::new(BTreeMap::new());
+
+// 함수 호출 시의 $1, $2, ... ($0 은 제외)
+static POSITIONAL_PARAMETERS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+// `NAME=value command` 의 임시 대입, command 가 끝나면 복구되고 외부 command 에는 환경변수로 전달
+// 함수 안에서 다시 임시 대입을 하면 바깥 대입 위에 쌓임
+static TEMPORARY_ASSIGNMENTS: Mutex<Vec<(String, String)>> = Mutex::new(vec![]);
+
+pub fn is_valid_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first_char) = chars.next() else {
+        return false;
+    };
+
+    if false == (first_char.is_ascii_alphabetic() || first_char == '_') {
+        return false;
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// 새 positional parameter 로 바꾸고 이전 값을 반환 (함수 호출이 끝나면 복구용)
+pub fn replace_positional_parameters(parameters: Vec<String>) -> Vec<String> {
+    std::mem::replace(&mut POSITIONAL_PARAMETERS.lock().unwrap(), parameters)
+}
+
+// for 의 in 이 없을 때 반복할 $1, $2, ...
+pub fn get_positional_parameters() -> Vec<String> {
+    POSITIONAL_PARAMETERS.lock().unwrap().to_owned()
+}
+
+// shell 변수에 없으면 환경변수에서 찾는다
+// 배열 변수를 이름만으로 참조하면 bash 처럼 0 번째 요소
+pub fn get_variable(name: &str) -> Option<String> {
+    match name {
+        "?" => return Some(crate::LAST_EXIT_STATUS.load(std::sync::atomic::Ordering::Relaxed).to_string()),
+        "#" => return Some(POSITIONAL_PARAMETERS.lock().unwrap().len().to_string()),
+        "@" | "*" => return Some(POSITIONAL_PARAMETERS.lock().unwrap().join(" ")),
+        "0" => return env::args().next(),
+        _ => {}
+    }
+
+    // $1, $2, ...
+    if let Ok(index) = name.parse::<usize>() {
+        return index.checked_sub(1).and_then(|index| POSITIONAL_PARAMETERS.lock().unwrap().get(index).cloned());
+    }
+
+    if let Some(variable) = SHELL_VARIABLES.lock().unwrap().get(name) {
+        return match variable {
+            ShellVariable::Scalar(value) => Some(value.to_owned()),
+            ShellVariable::Array(values) => values.first().cloned(),
+        };
+    }
+
+    env::var(name).ok()
+}
+
+pub fn get_array_variable(name: &str) -> Option<Vec<String>> {
+    if let Some(variable) = SHELL_VARIABLES.lock().unwrap().get(name) {
+        return match variable {
+            ShellVariable::Scalar(value) => Some(vec![value.to_owned()]),
+            ShellVariable::Array(values) => Some(values.to_owned()),
+        };
+    }
+
+    env::var(name).ok().map(|value| vec![value])
+}
+
+pub fn set_variable(name: &str, value: &str) {
+    SHELL_VARIABLES.lock().unwrap().insert(name.to_string(), ShellVariable::Scalar(value.to_string()));
+}
+
+pub fn set_array_variable(name: &str, values: Vec<String>) {
+    SHELL_VARIABLES.lock().unwrap().insert(name.to_string(), ShellVariable::Array(values));
+}
+
+// set 출력용, 이름순 정렬
+pub fn get_all_variables() -> Vec<(String, ShellVariable)> {
+    SHELL_VARIABLES.lock().unwrap().iter().map(|(name, variable)| (name.to_owned(), variable.to_owned())).collect()
+}
+
+// 임시 대입을 shell 변수에 적용하고 복구용으로 이전 값을 반환
+pub fn push_temporary_assignments(assignments: Vec<(String, String)>) -> Vec<(String, Option<ShellVariable>)> {
+    let mut shell_variables = SHELL_VARIABLES.lock().unwrap();
+    let previous_variables = assignments
+        .iter()
+        .map(|(name, value)| (name.to_owned(), shell_variables.insert(name.to_owned(), ShellVariable::Scalar(value.to_owned()))))
+        .collect();
+
+    TEMPORARY_ASSIGNMENTS.lock().unwrap().extend(assignments);
+    previous_variables
+}
+
+// 나중에 대입한 것부터 역순으로 이전 값을 복구 (이전에 없던 변수는 삭제)
+pub fn pop_temporary_assignments(previous_variables: Vec<(String, Option<ShellVariable>)>) {
+    let mut temporary_assignments = TEMPORARY_ASSIGNMENTS.lock().unwrap();
+    let len = temporary_assignments.len().saturating_sub(previous_variables.len());
+    temporary_assignments.truncate(len);
+
+    let mut shell_variables = SHELL_VARIABLES.lock().unwrap();
+    for (name, previous_variable) in previous_variables.into_iter().rev() {
+        match previous_variable {
+            Some(variable) => shell_variables.insert(name, variable),
+            None => shell_variables.remove(&name),
+        };
+    }
+}
+
+// 외부 command 에 환경변수로 넘길 임시 대입
+pub fn get_temporary_assignments() -> Vec<(String, String)> {
+    TEMPORARY_ASSIGNMENTS.lock().unwrap().to_owned()
+}